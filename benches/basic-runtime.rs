@@ -2,7 +2,7 @@ use std::{hint::black_box, time::Duration};
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use des::{
-    runtime::{Application, Builder, Event as EventSet, EventLifecycle},
+    runtime::{Application, Builder, Event as EventSet, EventLifecycle, EventQueueKind},
     time::SimTime,
 };
 
@@ -16,7 +16,7 @@ fn sim_lock_aquire() {
     let _ = Builder::new().max_itr(1000).build(black_box(App));
 }
 
-fn ping_pong_application(n: usize) {
+fn ping_pong_application(n: usize, queue: EventQueueKind) {
     struct App {
         n: usize,
     }
@@ -52,12 +52,13 @@ fn ping_pong_application(n: usize) {
 
     let _ = Builder::new()
         .max_itr(10_000)
+        .event_queue(queue)
         .quiet()
         .build(App { n })
         .run();
 }
 
-fn unevenly_spaced_events(n: usize) {
+fn unevenly_spaced_events(n: usize, queue: EventQueueKind) {
     struct App {
         n: usize,
     }
@@ -85,6 +86,7 @@ fn unevenly_spaced_events(n: usize) {
 
     let _ = Builder::new()
         .max_itr(10_000)
+        .event_queue(queue)
         .quiet()
         .build(App { n })
         .run();
@@ -94,26 +96,30 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("sim-lock-aquire", |b| b.iter(|| sim_lock_aquire()));
 
     let mut ping_pong = c.benchmark_group("ping-pong");
-    for parallelism in [1, 10, 100, 1000] {
-        ping_pong.throughput(Throughput::Bytes(parallelism));
-        ping_pong.bench_with_input(
-            BenchmarkId::from_parameter(parallelism),
-            &parallelism,
-            |b, &parallelism| b.iter(|| ping_pong_application(parallelism as usize)),
-        );
+    for queue in EventQueueKind::all() {
+        for parallelism in [1, 10, 100, 1000] {
+            ping_pong.throughput(Throughput::Bytes(parallelism));
+            ping_pong.bench_with_input(
+                BenchmarkId::new(queue.to_string(), parallelism),
+                &parallelism,
+                |b, &parallelism| b.iter(|| ping_pong_application(parallelism as usize, queue)),
+            );
+        }
     }
     ping_pong.finish();
 
-    let mut ping_pong = c.benchmark_group("uneven-events");
-    for parallelism in [1, 10, 100, 1000] {
-        ping_pong.throughput(Throughput::Bytes(parallelism));
-        ping_pong.bench_with_input(
-            BenchmarkId::from_parameter(parallelism),
-            &parallelism,
-            |b, &parallelism| b.iter(|| unevenly_spaced_events(parallelism as usize)),
-        );
+    let mut uneven = c.benchmark_group("uneven-events");
+    for queue in EventQueueKind::all() {
+        for parallelism in [1, 10, 100, 1000] {
+            uneven.throughput(Throughput::Bytes(parallelism));
+            uneven.bench_with_input(
+                BenchmarkId::new(queue.to_string(), parallelism),
+                &parallelism,
+                |b, &parallelism| b.iter(|| unevenly_spaced_events(parallelism as usize, queue)),
+            );
+        }
     }
-    ping_pong.finish();
+    uneven.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -82,6 +82,24 @@ impl<T> DualLinkedList<T> {
         }
     }
 
+    pub(super) fn front(&self) -> Option<(&T, Duration)> {
+        // SAFTEY:
+        // Value is guranteed to be valid since head->next is allways valid
+        let front = unsafe { &*self.head.next };
+        if front.next.is_null() {
+            None
+        } else {
+            front.value.as_ref().map(|v| (v, front.time))
+        }
+    }
+
+    pub(super) fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            cur: self.head.next,
+            _list: self,
+        }
+    }
+
     /// Inserts a new element into the queue, returing a Handle to
     /// cancel the event at will
     pub(super) fn add(&mut self, event: T, time: Duration, event_id: usize) {
@@ -164,6 +182,28 @@ impl<T> DualLinkedList<T> {
     }
 }
 
+pub(super) struct Iter<'a, T> {
+    cur: *mut EventNode<T>,
+    _list: &'a DualLinkedList<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a T, Duration, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFTEY:
+        // cur is allways a valid node of the borrowed list, since
+        // the list cannot be modified while the iterator exists.
+        let node: &'a EventNode<T> = unsafe { &*self.cur };
+        if node.next.is_null() {
+            // Reached the tail
+            None
+        } else {
+            self.cur = node.next;
+            node.value.as_ref().map(|v| (v, node.time, node.id))
+        }
+    }
+}
+
 impl<T> Drop for DualLinkedList<T> {
     fn drop(&mut self) {
         while self.pop_min().is_some() {}
@@ -58,6 +58,39 @@ pub struct EventHandle<E> {
     time: Duration,
 }
 
+impl<E> EventHandle<E> {
+    /// Reconstructs a handle from its raw parts, as returned by
+    /// [`id`](EventHandle::id) and [`time`](EventHandle::time).
+    #[must_use]
+    pub fn from_raw_parts(id: usize, time: Duration) -> Self {
+        Self {
+            _phantom: PhantomData,
+            id,
+            time,
+        }
+    }
+
+    /// The queue-unique identifier of the event.
+    #[must_use]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The timestamp the event was scheduled for.
+    #[must_use]
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+impl<E> Clone for EventHandle<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for EventHandle<E> {}
+
 impl<E> CQueue<E> {
     /// Returns a String describing the datatype and its parameters.
     #[must_use]
@@ -178,28 +211,87 @@ impl<E> CQueue<E> {
         }
     }
 
+    ///
+    /// Removes the event identified by the handle from the queue.
+    ///
+    /// Returns `true` if the event was still pending, or `false` if
+    /// the event was already fetched or cancelled.
+    ///
     #[allow(clippy::needless_pass_by_value)]
-    pub fn cancel(&mut self, handle: EventHandle<E>) {
-        if handle.time >= self.t_current {
-            if handle.time == self.t_current {
-                if let Some(i) = self.zero_event_bucket.iter().position(|v| v.2 == handle.id) {
-                    self.zero_event_bucket.remove(i);
-                    self.len -= 1;
-                }
-            } else {
-                let time_mod = handle.time.as_nanos().rem(self.t_all);
+    pub fn cancel(&mut self, handle: EventHandle<E>) -> bool {
+        if handle.time < self.t_current {
+            return false;
+        }
+
+        if handle.time == self.t_current {
+            if let Some(i) = self.zero_event_bucket.iter().position(|v| v.2 == handle.id) {
+                self.zero_event_bucket.remove(i);
+                self.len -= 1;
+                return true;
+            }
+            // Events scheduled before t_current was reached, may still
+            // reside in their bucket.
+        }
+
+        let time_mod = handle.time.as_nanos().rem(self.t_all);
 
-                let index = time_mod / self.t_nanos;
-                let index: usize = index as usize;
-                let index = index % self.n;
+        let index = time_mod / self.t_nanos;
+        let index: usize = index as usize;
+        let index = index % self.n;
 
-                if self.buckets[index].cancel(&handle) {
-                    self.len -= 1;
+        if self.buckets[index].cancel(&handle) {
+            self.len -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Returns a reference to the smallest event in the calender queue,
+    /// without removing it.
+    ///
+    /// Returns `None` if the queue is empty.
+    ///
+    #[must_use]
+    pub fn peek(&self) -> Option<(&E, Duration)> {
+        if !self.bucket_precedes_zero_events() {
+            if let Some((event, time, _)) = self.zero_event_bucket.front() {
+                return Some((event, *time));
+            }
+        }
+        if self.is_empty() {
+            return None;
+        }
+
+        // Same traversal as fetch_next, but without moving the head
+        let mut head = self.head;
+        let mut t1 = self.t1;
+        loop {
+            if let Some((event, time)) = self.buckets[head].front() {
+                if time <= t1 {
+                    return Some((event, time));
                 }
             }
+            head = (head + 1) % self.n;
+            t1 += self.t;
         }
     }
 
+    ///
+    /// Returns an iterator over all pending events, in no particular order.
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (&E, EventHandle<E>)> {
+        self.zero_event_bucket
+            .iter()
+            .map(|(event, time, id)| (event, EventHandle::from_raw_parts(*id, *time)))
+            .chain(self.buckets.iter().flat_map(|bucket| {
+                bucket
+                    .iter()
+                    .map(|(event, time, id)| (event, EventHandle::from_raw_parts(id, time)))
+            }))
+    }
+
     ///
     /// Fetches the smalles event from the calender queue.
     ///
@@ -211,9 +303,11 @@ impl<E> CQueue<E> {
     pub fn fetch_next(&mut self) -> (E, Duration) {
         assert!(!self.is_empty(), "Cannot fetch from empty queue");
 
-        if let Some((event, time, _)) = self.zero_event_bucket.pop_front() {
-            self.len -= 1;
-            return (event, time);
+        if !self.bucket_precedes_zero_events() {
+            if let Some((event, time, _)) = self.zero_event_bucket.pop_front() {
+                self.len -= 1;
+                return (event, time);
+            }
         }
 
         loop {
@@ -244,6 +338,17 @@ impl<E> CQueue<E> {
     }
 }
 
+impl<E> CQueue<E> {
+    ///
+    /// Indicates whether the current bucket contains events at `t_current`.
+    /// Such events were added before `t_current` was reached, so they
+    /// must be emitted before all events in the zero-event bucket.
+    ///
+    fn bucket_precedes_zero_events(&self) -> bool {
+        self.buckets[self.head].front_time() == self.t_current
+    }
+}
+
 impl<E> Default for CQueue<E> {
     fn default() -> Self {
         Self::new(1024, Duration::from_millis(5))
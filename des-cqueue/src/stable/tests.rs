@@ -678,3 +678,90 @@ fn cqueue_cancel_validity_2() {
 
     assert_eq!(c, 4)
 }
+
+#[test]
+fn cqueue_cancel_in_bucket_at_current_time() {
+    let mut cqueue = CQueue::new(10, Duration::new(1, 0));
+    let _ = cqueue.add(Duration::from_secs(2), 1);
+    let handle = cqueue.add(Duration::from_secs(2), 2);
+    let _ = cqueue.add(Duration::from_secs(3), 3);
+
+    // Forward to t=2, the second event remains in its bucket
+    assert_eq!(cqueue.fetch_next(), (1, Duration::from_secs(2)));
+    assert_eq!(cqueue.time(), Duration::from_secs(2));
+    assert_eq!(cqueue.len_zero(), 0);
+
+    assert!(cqueue.cancel(handle));
+    assert!(!cqueue.cancel(handle));
+    assert_eq!(cqueue.len(), 1);
+    assert_eq!(cqueue.fetch_next(), (3, Duration::from_secs(3)));
+}
+
+#[test]
+fn cqueue_peek() {
+    let mut cqueue = CQueue::new(4, Duration::new(1, 0));
+    assert_eq!(cqueue.peek(), None);
+
+    let _ = cqueue.add(Duration::from_secs(13), 13);
+    let _ = cqueue.add(Duration::from_millis(5500), 5);
+    let _ = cqueue.add(Duration::from_secs(2), 2);
+
+    let mut c = 0;
+    while !cqueue.is_empty() {
+        let (peeked, peeked_time) = cqueue.peek().map(|(e, t)| (*e, t)).unwrap();
+        let (event, time) = cqueue.fetch_next();
+        assert_eq!(peeked, event);
+        assert_eq!(peeked_time, time);
+
+        if c == 0 {
+            // zero bucket takes precedence
+            let _ = cqueue.add(time, 0);
+            assert_eq!(cqueue.peek(), Some((&0, time)));
+        }
+        c += 1;
+    }
+    assert_eq!(c, 4);
+    assert_eq!(cqueue.peek(), None);
+}
+
+#[test]
+fn cqueue_iter() {
+    let mut cqueue = CQueue::new(8, Duration::new(1, 0));
+    let handles = (0..32)
+        .map(|i| cqueue.add(Duration::from_millis(750 * i), i))
+        .collect::<Vec<_>>();
+    cqueue.cancel(handles[5]);
+
+    let _ = cqueue.fetch_next();
+    let _ = cqueue.add(Duration::ZERO, 100);
+
+    let mut events = cqueue.iter().map(|(e, h)| (*e, h)).collect::<Vec<_>>();
+    assert_eq!(events.len(), cqueue.len());
+    events.sort_by_key(|(_, h)| (h.time(), h.id()));
+
+    let expected = handles
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(i, _)| *i != 5)
+        .map(|(_, h)| (h.id() as u64, *h))
+        .collect::<Vec<_>>();
+    assert_eq!(events[0].0, 100);
+    assert_eq!(&events[1..], &expected[..]);
+}
+
+#[test]
+fn cqueue_same_time_events_fifo() {
+    let mut cqueue = CQueue::new(32, Duration::from_secs(1));
+    cqueue.add(Duration::from_secs(1), 1);
+    cqueue.add(Duration::from_secs(1), 2);
+
+    assert_eq!(cqueue.fetch_next(), (1, Duration::from_secs(1)));
+
+    // Added at t_current, but after event 2
+    cqueue.add(Duration::from_secs(1), 3);
+    assert_eq!(cqueue.peek(), Some((&2, Duration::from_secs(1))));
+    assert_eq!(cqueue.fetch_next(), (2, Duration::from_secs(1)));
+    assert_eq!(cqueue.fetch_next(), (3, Duration::from_secs(1)));
+    assert!(cqueue.is_empty());
+}
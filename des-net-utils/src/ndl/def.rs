@@ -94,6 +94,10 @@ pub struct ModuleDef {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub connections: Vec<ConnectionDef>,
+    /// A collection of broadcast media, shared between local gates and the gates of children
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<MediumDef>,
 }
 
 /// A gate or gate-cluster on a module.
@@ -117,6 +121,18 @@ pub struct ConnectionDef {
     pub link: Option<String>,
 }
 
+/// A broadcast medium shared by any number of gates within a module definition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MediumDef {
+    /// The peers attached to the medium. Gate clusters attach all their gates.
+    pub peers: Vec<ConnectionEndpointDef>,
+    /// A link-symbol that will apply channel behaviour to the medium.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
 /// A connection endpoint.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ConnectionEndpointDef {
@@ -34,6 +34,7 @@ pub struct Span {
     pub submodule: Option<String>,
     pub gate: Option<String>,
     pub connection: Option<usize>,
+    pub medium: Option<usize>,
 }
 
 impl Error {
@@ -60,6 +61,12 @@ impl Error {
         self.span.connection = Some(connection);
         self
     }
+
+    #[must_use]
+    pub fn span_medium(mut self, medium: usize) -> Self {
+        self.span.medium = Some(medium);
+        self
+    }
 }
 
 impl From<ErrorKind> for Error {
@@ -162,6 +169,10 @@ impl Display for Span {
                 return write!(f, "modules > {module} > connections > {connection}");
             }
 
+            if let Some(ref medium) = self.medium {
+                return write!(f, "modules > {module} > media > {medium}");
+            }
+
             return write!(f, "modules > {module}");
         }
 
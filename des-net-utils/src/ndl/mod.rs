@@ -6,12 +6,12 @@ pub mod error;
 pub mod tree;
 
 use def::{
-    ConnectionDef, ConnectionEndpointDef, Def, FieldDef, GateDef, Kardinality, ModuleDef,
-    ModuleGenericsDef, TypClause,
+    ConnectionDef, ConnectionEndpointDef, Def, FieldDef, GateDef, Kardinality, MediumDef,
+    ModuleDef, ModuleGenericsDef, TypClause,
 };
 use error::{Error, ErrorKind, Result};
 use tree::{
-    Connection, ConnectionEndpoint, ConnectionEndpointAccessor, Gate, Link, Network, Node,
+    Connection, ConnectionEndpoint, ConnectionEndpointAccessor, Gate, Link, Medium, Network, Node,
     Submodule, Symbol,
 };
 
@@ -112,6 +112,7 @@ fn transform_module(
 
     // (3) Defer connections computation after inherit, to use known gates.
     let mut connections = Vec::new();
+    let mut media = Vec::new();
 
     // (4) Inherit known symbols and definitions
     if let Some(ref parent) = def.inherit {
@@ -121,12 +122,14 @@ fn transform_module(
         gates.extend(arch.gates.iter().cloned());
         submodules.extend(arch.submodules.iter().cloned());
         connections.extend(arch.connections.iter().cloned());
+        media.extend(arch.media.iter().cloned());
     }
 
     // (5) Parse connections with elsewise fully defined node. If the node is generic, connections on the generic node
     // must work with the placeholders only
     let connections =
         transform_connections(connections, &def.connections, &submodules, &gates, links)?;
+    let media = transform_media(media, &def.media, &submodules, &gates, links)?;
 
     Ok((
         Node {
@@ -134,6 +137,7 @@ fn transform_module(
             gates,
             submodules,
             connections,
+            media,
         },
         ident.args.clone(),
     ))
@@ -311,6 +315,46 @@ fn transform_connection(
     Ok(())
 }
 
+fn transform_media(
+    initial: Vec<Medium>,
+    defs: &[MediumDef],
+    submodules: &[Submodule],
+    gates: &FxHashSet<Gate>,
+    links: &FxHashMap<String, Link>,
+) -> Result<Vec<Medium>> {
+    let mut results = initial;
+    for (idx, def) in defs.iter().enumerate() {
+        let medium =
+            transform_medium(def, submodules, gates, links).map_err(|e| e.span_medium(idx))?;
+        results.push(medium);
+    }
+
+    Ok(results)
+}
+
+fn transform_medium(
+    def: &MediumDef,
+    submodules: &[Submodule],
+    gates: &FxHashSet<Gate>,
+    links: &FxHashMap<String, Link>,
+) -> Result<Medium> {
+    let mut peers = Vec::new();
+    for peer in &def.peers {
+        peers.extend(transform_connection_endpoint(peer, submodules, gates)?);
+    }
+    let link = match def.link {
+        Some(ref link_def) => Some(
+            links
+                .get(link_def)
+                .cloned()
+                .ok_or_else(|| ErrorKind::UnknownLink(link_def.clone()))?,
+        ),
+        None => None,
+    };
+
+    Ok(Medium { peers, link })
+}
+
 fn transform_connection_endpoint(
     def: &ConnectionEndpointDef,
     submodules: &[Submodule],
@@ -12,6 +12,8 @@ pub struct Node {
     pub submodules: Vec<Submodule>,
     pub gates: FxHashSet<Gate>,
     pub connections: Vec<Connection>,
+    #[serde(default)]
+    pub media: Vec<Medium>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -28,6 +30,12 @@ pub struct Connection {
     pub link: Option<Link>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Medium {
+    pub peers: Vec<ConnectionEndpoint>,
+    pub link: Option<Link>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConnectionEndpoint {
     pub accessors: Vec<ConnectionEndpointAccessor>,
@@ -67,6 +75,14 @@ impl Node {
             return false;
         }
 
+        if !interface
+            .media
+            .iter()
+            .all(|medium| self.media.iter().any(|other| other == medium))
+        {
+            return false;
+        }
+
         true
     }
 }
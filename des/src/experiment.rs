@@ -0,0 +1,719 @@
+//! Parameter sweeps and replications of simulations.
+//!
+//! An [`Experiment`] executes a simulation once for each combination of
+//! parameters in a [`ParameterGrid`], repeating each combination for a
+//! given number of replications. Each run uses its own seed, derived from
+//! the seed of the experiment, so that experiments are reproducable, while
+//! all runs remain independent of each other.
+//!
+//! Runs are distributed over multiple threads, since independent runtimes
+//! may run in parallel. The collected [`ExperimentResults`] can be exported
+//! as CSV or JSON.
+//!
+//! # Examples
+//!
+//! ```
+//! use des::prelude::*;
+//! use des::experiment::{Experiment, ParameterGrid};
+//! # use des::net::blocks::ModuleFn;
+//!
+//! let experiment = Experiment::new(|run| {
+//!     let mut sim = Sim::new(());
+//!     sim.include_cfg(&run.parameters().to_cfg());
+//!     sim.node("alice", ModuleFn::new(
+//!         || {
+//!             let rate = current().prop::<f64>("rate").unwrap().or(1.0).get();
+//!             schedule_in(Message::default(), Duration::from_secs_f64(1.0 / rate));
+//!         },
+//!         |_, _| {},
+//!     ));
+//!     sim.freeze()
+//! })
+//! .grid(ParameterGrid::new().axis("alice.rate", [1.0, 2.0, 4.0]))
+//! .replications(3)
+//! .seed(42);
+//!
+//! let results = experiment.run(|_sim, _profiler| ());
+//! assert_eq!(results.len(), 9);
+//!
+//! let csv = results.to_csv();
+//! ```
+
+use std::{
+    fmt::{self, Write as _},
+    io,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use serde_yml::{Mapping, Value};
+
+use crate::{
+    runtime::{Application, Builder, Profiler},
+    stats::Estimate,
+    time::SimTime,
+};
+
+/// A cartesian product of parameter values.
+///
+/// Each axis assigns a set of values to a parameter key. Keys are
+/// dotted paths, as used by [`SimBuilder::include_cfg`](crate::net::SimBuilder::include_cfg),
+/// e.g. `alice.rate` for the property `rate` of the module `alice`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterGrid {
+    axes: Vec<(String, Vec<Value>)>,
+}
+
+impl ParameterGrid {
+    /// Creates a new grid without axes, consisting of a single
+    /// parameter point without parameters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an axis to the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value cannot be represented as YAML.
+    #[must_use]
+    pub fn axis<T: Serialize>(
+        mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let values = values
+            .into_iter()
+            .map(|v| serde_yml::to_value(v).expect("parameter value must be representable as YAML"))
+            .collect();
+        self.axes.push((key.into(), values));
+        self
+    }
+
+    /// The number of parameter points in the grid.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.axes.iter().map(|(_, values)| values.len()).product()
+    }
+
+    /// Indicates whether the grid contains no parameter points, which
+    /// is the case if any axis is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All parameter points of the grid. The last axis varies fastest.
+    #[must_use]
+    pub fn points(&self) -> Vec<Parameters> {
+        let mut points = vec![Parameters::default()];
+        for (key, values) in &self.axes {
+            points = points
+                .into_iter()
+                .flat_map(|point| {
+                    values.iter().map(move |value| {
+                        let mut point = point.clone();
+                        point.values.push((key.clone(), value.clone()));
+                        point
+                    })
+                })
+                .collect();
+        }
+        points
+    }
+}
+
+/// A single point of a [`ParameterGrid`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Parameters {
+    values: Vec<(String, Value)>,
+}
+
+impl Parameters {
+    /// The value of a parameter.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// An iterator over all parameters, in the order of the axes.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Formats the parameters as a YAML configuration, that can be
+    /// applied using [`SimBuilder::include_cfg`](crate::net::SimBuilder::include_cfg).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters cannot be serialized.
+    #[must_use]
+    pub fn to_cfg(&self) -> String {
+        if self.values.is_empty() {
+            return String::new();
+        }
+        let map = self
+            .values
+            .iter()
+            .map(|(k, v)| (Value::String(k.clone()), v.clone()))
+            .collect::<Mapping>();
+        serde_yml::to_string(&map).expect("parameters must be serializable")
+    }
+}
+
+impl fmt::Display for Parameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}={}", yaml_to_string(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// A single run of an [`Experiment`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    point: usize,
+    replication: usize,
+    seed: u64,
+    parameters: Parameters,
+}
+
+impl Run {
+    /// The index of the parameter point in the grid.
+    #[must_use]
+    pub fn point(&self) -> usize {
+        self.point
+    }
+
+    /// The index of the replication of the parameter point.
+    #[must_use]
+    pub fn replication(&self) -> usize {
+        self.replication
+    }
+
+    /// The seed of the RNG used by this run.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The parameters of this run.
+    #[must_use]
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+}
+
+type Setup<A> = Box<dyn Fn(&Run) -> A + Send + Sync>;
+type Configure = Box<dyn Fn(Builder) -> Builder + Send + Sync>;
+
+/// A set of simulation runs, over a parameter grid and multiple replications.
+///
+/// See the [module level documentation](self) for more information.
+pub struct Experiment<A> {
+    setup: Setup<A>,
+    configure: Configure,
+    grid: ParameterGrid,
+    replications: usize,
+    seed: u64,
+    threads: usize,
+}
+
+impl<A: Application> Experiment<A> {
+    /// Creates a new experiment, using `setup` to create the application
+    /// for each run.
+    ///
+    /// By default, the experiment consists of a single run, seeded with `0`,
+    /// using all available cores.
+    pub fn new(setup: impl Fn(&Run) -> A + Send + Sync + 'static) -> Self {
+        Self {
+            setup: Box::new(setup),
+            configure: Box::new(Builder::quiet),
+            grid: ParameterGrid::new(),
+            replications: 1,
+            seed: 0,
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    /// Sets the parameter grid.
+    #[must_use]
+    pub fn grid(mut self, grid: ParameterGrid) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    /// Sets the number of replications per parameter point.
+    #[must_use]
+    pub fn replications(mut self, replications: usize) -> Self {
+        self.replications = replications;
+        self
+    }
+
+    /// Sets the seed, from which the seeds of all runs are derived.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the maximum number of runs executed in parallel.
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Configures the [`Builder`] of each run, e.g. to set limits.
+    ///
+    /// The builder passed to `configure` is already seeded and quiet.
+    #[must_use]
+    pub fn configure(
+        mut self,
+        configure: impl Fn(Builder) -> Builder + Send + Sync + 'static,
+    ) -> Self {
+        self.configure = Box::new(move |builder| configure(builder.quiet()));
+        self
+    }
+
+    /// All runs of the experiment, ordered by parameter point and replication.
+    #[must_use]
+    pub fn runs(&self) -> Vec<Run> {
+        let mut runs = Vec::with_capacity(self.grid.len() * self.replications);
+        for (point, parameters) in self.grid.points().into_iter().enumerate() {
+            let point_seed = derive_seed(self.seed, point as u64);
+            for replication in 0..self.replications {
+                runs.push(Run {
+                    point,
+                    replication,
+                    seed: derive_seed(point_seed, replication as u64),
+                    parameters: parameters.clone(),
+                });
+            }
+        }
+        runs
+    }
+
+    /// Executes all runs and collects their results.
+    ///
+    /// After each run, `measure` extracts the output of the run from
+    /// the final application and the profiler. Runs that fail with a
+    /// [`RuntimeError`](crate::runtime::RuntimeError) are recorded with
+    /// the error instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any run panics.
+    pub fn run<T, F>(&self, measure: F) -> ExperimentResults<T>
+    where
+        T: Send,
+        F: Fn(A, &Profiler<A::EventSet>) -> T + Sync,
+    {
+        let runs = self.runs();
+        let next = AtomicUsize::new(0);
+        let records = Mutex::new(Vec::with_capacity(runs.len()));
+
+        let worker = || loop {
+            let i = next.fetch_add(1, Ordering::SeqCst);
+            let Some(run) = runs.get(i) else {
+                break;
+            };
+            let record = self.execute(run, &measure);
+            records.lock().expect("poisoned").push(record);
+        };
+
+        let threads = self.threads.min(runs.len());
+        if threads <= 1 {
+            worker();
+        } else {
+            thread::scope(|s| {
+                for _ in 0..threads {
+                    s.spawn(worker);
+                }
+            });
+        }
+
+        let mut records = records.into_inner().expect("poisoned");
+        records.sort_by_key(|record: &RunRecord<T>| (record.run.point, record.run.replication));
+        ExperimentResults {
+            keys: self.grid.axes.iter().map(|(key, _)| key.clone()).collect(),
+            records,
+        }
+    }
+
+    fn execute<T>(
+        &self,
+        run: &Run,
+        measure: &impl Fn(A, &Profiler<A::EventSet>) -> T,
+    ) -> RunRecord<T> {
+        let app = (self.setup)(run);
+        let builder = (self.configure)(Builder::seeded(run.seed));
+
+        let start = Instant::now();
+        let result = builder.build(app).run();
+        let wall_time = start.elapsed();
+
+        match result {
+            Ok((app, sim_time, profiler)) => RunRecord {
+                run: run.clone(),
+                sim_time,
+                event_count: profiler.event_count,
+                wall_time,
+                output: Ok(measure(app, &profiler)),
+            },
+            Err(e) => RunRecord {
+                run: run.clone(),
+                sim_time: SimTime::now(),
+                event_count: 0,
+                wall_time,
+                output: Err(e.to_string()),
+            },
+        }
+    }
+}
+
+impl<A> fmt::Debug for Experiment<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Experiment")
+            .field("grid", &self.grid)
+            .field("replications", &self.replications)
+            .field("seed", &self.seed)
+            .field("threads", &self.threads)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Derives a seed for the given index, using the `SplitMix64` finalizer,
+/// so that adjacent indices use unrelated seeds.
+///
+/// Runs derive their seed first from the parameter point, then from the
+/// replication, so that changing the number of replications does not
+/// change the seeds of existing runs.
+fn derive_seed(seed: u64, index: u64) -> u64 {
+    let mut z = seed.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The result of a single run of an [`Experiment`].
+#[derive(Debug, Clone)]
+pub struct RunRecord<T> {
+    /// The executed run.
+    pub run: Run,
+    /// The simulation time at the end of the run.
+    pub sim_time: SimTime,
+    /// The number of events dispatched by the run.
+    pub event_count: usize,
+    /// The real time it took to execute the run.
+    pub wall_time: Duration,
+    /// The output of the run, or the error message, should the run have failed.
+    pub output: Result<T, String>,
+}
+
+/// The results of all runs of an [`Experiment`], ordered by
+/// parameter point and replication.
+#[derive(Debug, Clone)]
+pub struct ExperimentResults<T> {
+    keys: Vec<String>,
+    records: Vec<RunRecord<T>>,
+}
+
+impl<T> ExperimentResults<T> {
+    /// The number of runs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Indicates whether the experiment contained no runs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// The results of all runs.
+    #[must_use]
+    pub fn records(&self) -> &[RunRecord<T>] {
+        &self.records
+    }
+
+    /// The results of all replications of a parameter point.
+    pub fn point(&self, point: usize) -> impl Iterator<Item = &RunRecord<T>> {
+        self.records.iter().filter(move |r| r.run.point == point)
+    }
+
+    /// Estimates the mean of a metric over all successful replications of
+    /// a parameter point, see [`Estimate::replications`].
+    ///
+    /// Returns `None` if less than two replications succeeded.
+    pub fn estimate(&self, point: usize, metric: impl Fn(&T) -> f64) -> Option<Estimate> {
+        Estimate::replications(
+            self.point(point)
+                .filter_map(|record| record.output.as_ref().ok())
+                .map(metric),
+        )
+    }
+}
+
+impl<T: Serialize> ExperimentResults<T> {
+    /// Formats the results as a table of comma-separated values, with one
+    /// row per run.
+    ///
+    /// Outputs that serialize to a struct or map are split into one column
+    /// per field, all other outputs are stored in a column `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an output cannot be serialized.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_csv(&mut buf)
+            .expect("writing to a vec cannot fail");
+        String::from_utf8(buf).expect("csv output is valid UTF-8")
+    }
+
+    /// Writes the results as CSV, see [`ExperimentResults::to_csv`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an output cannot be serialized.
+    pub fn write_csv(&self, mut w: impl io::Write) -> io::Result<()> {
+        let outputs = self.outputs();
+
+        let mut output_columns = Vec::<String>::new();
+        for output in outputs.iter().flatten() {
+            for key in output.keys() {
+                if !output_columns.contains(key) {
+                    output_columns.push(key.clone());
+                }
+            }
+        }
+
+        let mut header = vec!["point", "replication", "seed"];
+        header.extend(self.keys.iter().map(String::as_str));
+        header.extend(["sim_time", "event_count", "wall_time"]);
+        header.extend(output_columns.iter().map(String::as_str));
+        header.push("error");
+        writeln!(w, "{}", csv_row(header.into_iter().map(str::to_string)))?;
+
+        for (record, output) in self.records.iter().zip(&outputs) {
+            let mut row = vec![
+                record.run.point.to_string(),
+                record.run.replication.to_string(),
+                record.run.seed.to_string(),
+            ];
+            row.extend(self.keys.iter().map(|key| {
+                record
+                    .run
+                    .parameters
+                    .get(key)
+                    .map(yaml_to_string)
+                    .unwrap_or_default()
+            }));
+            row.extend([
+                record.sim_time.as_secs_f64().to_string(),
+                record.event_count.to_string(),
+                record.wall_time.as_secs_f64().to_string(),
+            ]);
+            row.extend(output_columns.iter().map(|column| {
+                output
+                    .as_ref()
+                    .and_then(|output| output.get(column))
+                    .map(json_to_string)
+                    .unwrap_or_default()
+            }));
+            row.push(record.output.as_ref().err().cloned().unwrap_or_default());
+            writeln!(w, "{}", csv_row(row.into_iter()))?;
+        }
+        Ok(())
+    }
+
+    /// Formats the results as a JSON array, with one object per run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an output cannot be serialized.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.json()).expect("json values are serializable")
+    }
+
+    /// Writes the results as JSON, see [`ExperimentResults::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an output cannot be serialized.
+    pub fn write_json(&self, w: impl io::Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(w, &self.json()).map_err(io::Error::from)
+    }
+
+    fn json(&self) -> JsonValue {
+        let records = self
+            .records
+            .iter()
+            .map(|record| {
+                let parameters = record
+                    .run
+                    .parameters
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), yaml_to_json(value)))
+                    .collect::<Map<_, _>>();
+
+                let mut object = Map::new();
+                object.insert("point".into(), record.run.point.into());
+                object.insert("replication".into(), record.run.replication.into());
+                object.insert("seed".into(), record.run.seed.into());
+                object.insert("parameters".into(), parameters.into());
+                object.insert("sim_time".into(), record.sim_time.as_secs_f64().into());
+                object.insert("event_count".into(), record.event_count.into());
+                object.insert("wall_time".into(), record.wall_time.as_secs_f64().into());
+                match &record.output {
+                    Ok(output) => {
+                        let output =
+                            serde_json::to_value(output).expect("output must be serializable");
+                        object.insert("output".into(), output);
+                    }
+                    Err(e) => {
+                        object.insert("error".into(), e.clone().into());
+                    }
+                }
+                JsonValue::Object(object)
+            })
+            .collect();
+        JsonValue::Array(records)
+    }
+
+    fn outputs(&self) -> Vec<Option<Map<String, JsonValue>>> {
+        self.records
+            .iter()
+            .map(|record| {
+                let output = record.output.as_ref().ok()?;
+                let value = serde_json::to_value(output).expect("output must be serializable");
+                Some(match value {
+                    JsonValue::Object(map) => map,
+                    value => Map::from_iter([("output".to_string(), value)]),
+                })
+            })
+            .collect()
+    }
+}
+
+fn yaml_to_json(value: &Value) -> JsonValue {
+    serde_json::to_value(value).unwrap_or(JsonValue::Null)
+}
+
+fn yaml_to_string(value: &Value) -> String {
+    json_to_string(&yaml_to_json(value))
+}
+
+fn json_to_string(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => String::new(),
+        JsonValue::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+pub(crate) fn csv_row(fields: impl Iterator<Item = String>) -> String {
+    let mut row = String::new();
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            row.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            let _ = write!(row, "\"{}\"", field.replace('"', "\"\""));
+        } else {
+            row.push_str(&field);
+        }
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_points() {
+        let grid = ParameterGrid::new()
+            .axis("a.x", [1, 2])
+            .axis("b.y", ["u", "v", "w"]);
+        assert_eq!(grid.len(), 6);
+
+        let points = grid.points();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0].to_string(), "a.x=1, b.y=u");
+        assert_eq!(points[1].to_string(), "a.x=1, b.y=v");
+        assert_eq!(points[5].to_string(), "a.x=2, b.y=w");
+
+        assert_eq!(ParameterGrid::new().points(), vec![Parameters::default()]);
+        assert!(ParameterGrid::new().axis::<u8>("a", []).is_empty());
+    }
+
+    #[test]
+    fn parameters_to_cfg() {
+        let points = ParameterGrid::new().axis("alice.rate", [1.5]).points();
+        assert_eq!(points[0].to_cfg(), "alice.rate: 1.5\n");
+        assert_eq!(Parameters::default().to_cfg(), "");
+    }
+
+    #[test]
+    fn seeds_are_distinct() {
+        let seeds = (0..1000).map(|i| derive_seed(42, i)).collect::<Vec<_>>();
+        for (i, seed) in seeds.iter().enumerate() {
+            assert!(!seeds[..i].contains(seed));
+        }
+        assert_ne!(derive_seed(42, 0), derive_seed(43, 0));
+    }
+
+    #[test]
+    fn seeds_are_independent_of_replications() {
+        let grid = ParameterGrid::new().axis("a", [1, 2]);
+        let seeds = |replications| {
+            Experiment::new(|_| || ())
+                .grid(grid.clone())
+                .replications(replications)
+                .seed(42)
+                .runs()
+                .into_iter()
+                .map(|run| ((run.point, run.replication), run.seed))
+                .collect::<Vec<_>>()
+        };
+        let short = seeds(2);
+        let long = seeds(3);
+        assert!(short.iter().all(|entry| long.contains(entry)));
+    }
+
+    #[test]
+    fn csv_escaping() {
+        assert_eq!(
+            csv_row(["a", "b,c", "d\"e"].into_iter().map(str::to_string)),
+            "a,\"b,c\",\"d\"\"e\""
+        );
+    }
+}
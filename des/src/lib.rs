@@ -130,7 +130,9 @@ pub mod runtime;
 pub mod time;
 
 cfg_net! {
+    pub mod experiment;
     pub mod net;
+    pub mod stats;
     pub mod tracing;
     pub(crate) use des_net_utils::sync;
 }
@@ -144,7 +146,7 @@ cfg_macros! {
 // | Feature          | Description                                                              |
 // |------------------|--------------------------------------------------------------------------|
 // | net              | Adds a module oriented design-abstraction that provides its own events.  |
-// | cqueue           | Configures the runtime to use a calender queue for better performance.   |
+// | cqueue           | Provides a calender queue event set, used by default for better performance. |
 // | metrics | Collects internal metrics about the runtime, to improve parametrization. |
 // | async            | Provides utilites and modifications for simulating asynchronous systems including a full reexport of safe tokio funtions. |
 //
@@ -99,3 +99,13 @@ macro_rules! cfg_not_miri {
         )*
     }
 }
+
+macro_rules! cfg_serde {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "serde")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+            $item
+        )*
+    }
+}
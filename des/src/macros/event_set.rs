@@ -58,6 +58,14 @@ macro_rules! event_set {
                     )+
                 }
             }
+
+            fn describe(&self) -> String {
+                match self {
+                    $(
+                        Self::$variant(_) => stringify!($variant).to_string(),
+                    )+
+                }
+            }
         }
 
         $(
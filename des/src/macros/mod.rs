@@ -1,9 +1,6 @@
 #[macro_use]
 mod cfg;
 
-#[doc(hidden)]
-pub mod support;
-
 cfg_macros! {
     #[macro_use]
     mod event_set;
@@ -0,0 +1,129 @@
+use std::{fmt::Debug, sync::Arc};
+
+use rand::{
+    distr::{Distribution, Uniform},
+    Rng, RngCore,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::time::Duration;
+
+/// The distribution of the jitter of a channel, added on top of the
+/// latency and transmission time of each message.
+///
+/// The delay model of a channel is set using [`Channel::set_delay_model`],
+/// the delay model of a medium using [`Medium::set_delay_model`].
+/// The built-in distributions are scaled by the
+/// [`jitter`](super::ChannelMetrics::jitter) of the channel. A channel
+/// without jitter thus adds no delay, unless a custom distribution
+/// is used. All delays are drawn from the simulation RNG.
+///
+/// [`Channel::set_delay_model`]: super::Channel::set_delay_model
+/// [`Medium::set_delay_model`]: super::Medium::set_delay_model
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DelayModel {
+    /// A uniform delay between zero and the jitter.
+    #[default]
+    Uniform,
+    /// A half-normal delay, using the jitter as standard deviation.
+    Normal,
+    /// An exponentially distributed delay, using the jitter as mean.
+    Exponential,
+    /// A heavy-tailed Pareto delay (Lomax) with the given shape,
+    /// using the jitter as scale.
+    ///
+    /// Smaller shapes produce heavier tails. The mean delay is
+    /// `jitter / (shape - 1)` for shapes greater than one.
+    Pareto(f64),
+    /// A delay drawn from an arbitrary distribution, ignoring the jitter.
+    ///
+    /// Custom delay models cannot be serialized.
+    #[serde(skip)]
+    Custom(CustomDelay),
+}
+
+impl DelayModel {
+    /// Creates a delay model, that draws delays from the given distribution.
+    ///
+    /// Clones of the returned model share the distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use des::prelude::*;
+    /// use rand::distr::Uniform;
+    ///
+    /// let delay = DelayModel::custom(
+    ///     Uniform::new(Duration::from_millis(5), Duration::from_millis(10)).unwrap()
+    /// );
+    /// let channel = Channel::new(ChannelMetrics::new(
+    ///     1000,
+    ///     Duration::from_millis(100),
+    ///     Duration::ZERO,
+    ///     ChannelDropBehaviour::Drop,
+    /// ));
+    /// channel.set_delay_model(delay);
+    /// ```
+    pub fn custom(dist: impl Distribution<Duration> + Send + Sync + 'static) -> Self {
+        DelayModel::Custom(CustomDelay(Arc::new(dist)))
+    }
+
+    /// Draws the delay added to a message, given the jitter of the channel.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn sample(&self, jitter: Duration, rng: &mut dyn RngCore) -> Duration {
+        if let DelayModel::Custom(custom) = self {
+            return custom.0.sample_delay(rng);
+        }
+        if jitter == Duration::ZERO {
+            return Duration::ZERO;
+        }
+
+        let scale = jitter.as_secs_f64();
+        let secs = match *self {
+            DelayModel::Uniform => rng.sample(Uniform::new(0.0f64, scale).unwrap()),
+            DelayModel::Normal => {
+                // Box-Muller transform, using (0, 1] to avoid ln(0)
+                let u1 = 1.0 - rng.random::<f64>();
+                let u2 = rng.random::<f64>();
+                let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+                z.abs() * scale
+            }
+            DelayModel::Exponential => -(1.0 - rng.random::<f64>()).ln() * scale,
+            DelayModel::Pareto(shape) => {
+                let u = 1.0 - rng.random::<f64>();
+                (u.powf(-1.0 / shape) - 1.0) * scale
+            }
+            DelayModel::Custom(_) => unreachable!(),
+        };
+        Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX)
+    }
+}
+
+/// A user-defined delay distribution, created by [`DelayModel::custom`].
+///
+/// Custom distributions compare equal, if they refer to the same distribution.
+#[derive(Clone)]
+pub struct CustomDelay(Arc<dyn SampleDelay + Send + Sync>);
+
+impl PartialEq for CustomDelay {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(Arc::as_ptr(&self.0), Arc::as_ptr(&other.0))
+    }
+}
+
+impl Debug for CustomDelay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CustomDelay")
+    }
+}
+
+/// An object-safe wrapper around `Distribution<Duration>`.
+trait SampleDelay {
+    fn sample_delay(&self, rng: &mut dyn RngCore) -> Duration;
+}
+
+impl<D: Distribution<Duration>> SampleDelay for D {
+    fn sample_delay(&self, rng: &mut dyn RngCore) -> Duration {
+        self.sample(rng)
+    }
+}
@@ -0,0 +1,345 @@
+use std::sync::{Arc, RwLock};
+
+use crate::net::{
+    gate::{Connection, GateKind, GateRef, GateRefWeak},
+    message::{Message, MessageBody},
+    runtime::{HandleMessageEvent, MediumTransmissionEnd, MessageExitingConnection},
+    NetEvents,
+};
+use crate::runtime::{rng, EventSink};
+use crate::time::SimTime;
+
+use super::{ChannelMetrics, DelayModel};
+
+/// A reference to a broadcast medium.
+pub type MediumRef = Arc<Medium>;
+
+/// A shared broadcast medium, like an Ethernet bus or a wireless cell.
+///
+/// In contrast to a [`Channel`](super::Channel), that connects exactly two
+/// gates, any number of gates can be attached to a medium. Each message
+/// transmitted by one attachee is delivered to all other attachees, once
+/// the transmission is complete and the delay of the medium has passed.
+/// The transmission time and delay are defined by the [`ChannelMetrics`] and
+/// the [`DelayModel`] of the medium, while the drop behaviour is ignored. Since each receiver
+/// gets its own copy, messages sent onto a medium must have a clonable body.
+///
+/// Transmissions, that overlap in time, collide. Collided transmissions are
+/// not delivered. Instead each sender is notified by a message containing
+/// a [`Collision`] body, received on the gate the message was sent on.
+///
+/// A gate is attached to a medium either directly, or as the end of a gate
+/// chain. Messages sent onto the chain are transmitted on the medium, once
+/// they reach its end. Received messages travel the chain in the reverse
+/// direction.
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::net::channel::Medium;
+/// # struct Host;
+/// # impl Module for Host {}
+/// # let mut sim = Sim::new(());
+/// # sim.node("a", Host);
+/// # sim.node("b", Host);
+/// # sim.node("c", Host);
+/// let medium = Medium::new(ChannelMetrics::new(
+///     10_000_000,
+///     Duration::from_micros(5),
+///     Duration::ZERO,
+///     ChannelDropBehaviour::Drop,
+/// ));
+/// for node in ["a", "b", "c"] {
+///     medium.attach(sim.gate(node, "eth"));
+/// }
+/// assert_eq!(medium.attachees().len(), 3);
+/// ```
+pub struct Medium {
+    inner: RwLock<MediumInner>,
+}
+
+struct MediumInner {
+    metrics: ChannelMetrics,
+    delay: DelayModel,
+    attachees: Vec<GateRefWeak>,
+    ongoing: Vec<Transmission>,
+    next_id: u64,
+    transmissions: usize,
+    collisions: usize,
+}
+
+struct Transmission {
+    id: u64,
+    sender: GateRef,
+    end: SimTime,
+    collided: bool,
+    msg: Message,
+}
+
+/// The body of a notification, received by the sender of a message
+/// that collided on a [`Medium`].
+#[derive(Debug, Clone)]
+pub struct Collision {
+    /// The message, whose transmission failed.
+    pub message: Message,
+    /// The time the collision was detected.
+    pub time: SimTime,
+}
+
+impl MessageBody for Collision {
+    fn byte_len(&self) -> usize {
+        self.message.length()
+    }
+}
+
+impl Medium {
+    /// Creates a new medium using the given metrics, without any attachees.
+    #[must_use]
+    pub fn new(metrics: ChannelMetrics) -> MediumRef {
+        MediumRef::new(Medium {
+            inner: RwLock::new(MediumInner {
+                metrics,
+                delay: DelayModel::default(),
+                attachees: Vec::new(),
+                ongoing: Vec::new(),
+                next_id: 0,
+                transmissions: 0,
+                collisions: 0,
+            }),
+        })
+    }
+
+    /// Attaches a gate to the medium.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gate is already attached to a medium, or if the gate is
+    /// a transit gate, connected to two other gates.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn attach(self: &MediumRef, gate: GateRef) {
+        assert!(
+            gate.kind() != GateKind::Transit,
+            "Cannot attach transit gate '{}' to a medium",
+            gate.path()
+        );
+        gate.set_medium(self.clone());
+        self.inner
+            .write()
+            .unwrap()
+            .attachees
+            .push(Arc::downgrade(&gate));
+    }
+
+    /// The gates attached to the medium.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn attachees(&self) -> Vec<GateRef> {
+        self.inner
+            .read()
+            .unwrap()
+            .attachees
+            .iter()
+            .filter_map(GateRefWeak::upgrade)
+            .collect()
+    }
+
+    /// A description of the mediums capabilities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn metrics(&self) -> ChannelMetrics {
+        self.inner.read().unwrap().metrics
+    }
+
+    /// The distribution of the jitter of the medium.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn delay_model(&self) -> DelayModel {
+        self.inner.read().unwrap().delay.clone()
+    }
+
+    /// Sets the distribution of the jitter of the medium.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_delay_model(&self, delay: DelayModel) {
+        self.inner.write().unwrap().delay = delay;
+    }
+
+    /// Indicates whether any attachee is currently transmitting
+    /// onto the medium.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        let now = SimTime::now();
+        self.inner
+            .read()
+            .unwrap()
+            .ongoing
+            .iter()
+            .any(|t| t.end > now)
+    }
+
+    /// The number of transmissions started on the medium.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn transmissions(&self) -> usize {
+        self.inner.read().unwrap().transmissions
+    }
+
+    /// The number of transmissions, that collided.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn collisions(&self) -> usize {
+        self.inner.read().unwrap().collisions
+    }
+
+    /// Starts the transmission of a message, sent by the given attachee.
+    pub(crate) fn transmit<S: EventSink<NetEvents>>(
+        self: Arc<Self>,
+        msg: Message,
+        sender: GateRef,
+        sink: &mut S,
+    ) {
+        let now = SimTime::now();
+        let mut inner = self.inner.write().unwrap();
+        let metrics = inner.metrics;
+        let end = now + metrics.calculate_busy(&msg);
+
+        let mut collided = false;
+        let mut notify = Vec::new();
+        for t in inner.ongoing.iter_mut().filter(|t| t.end > now) {
+            collided = true;
+            if !t.collided {
+                t.collided = true;
+                notify.push((t.sender.clone(), t.msg.clone()));
+            }
+        }
+        if collided {
+            notify.push((sender.clone(), msg.clone()));
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            "Gate '{}' transmitting message [{}] on medium (collided: {})",
+            sender.name(),
+            msg,
+            collided
+        );
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.transmissions += 1;
+        inner.collisions += notify.len();
+        inner.ongoing.push(Transmission {
+            id,
+            sender,
+            end,
+            collided,
+            msg,
+        });
+        drop(inner);
+
+        for (sender, message) in notify {
+            let header = message.header();
+            let note = Message::default()
+                .id(header.id)
+                .kind(header.kind)
+                .with_content(Collision { message, time: now });
+            deliver(note, &sender, now + metrics.latency, sink);
+        }
+
+        sink.add(
+            NetEvents::MediumTransmissionEnd(MediumTransmissionEnd { medium: self, id }),
+            end,
+        );
+    }
+
+    /// Completes a transmission, delivering the message to all other
+    /// attachees, unless it collided.
+    pub(crate) fn finish<S: EventSink<NetEvents>>(&self, id: u64, sink: &mut S) {
+        let mut inner = self.inner.write().unwrap();
+        let Some(idx) = inner.ongoing.iter().position(|t| t.id == id) else {
+            return;
+        };
+        let transmission = inner.ongoing.remove(idx);
+        if transmission.collided {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                "Gate '{}' lost message [{}] due to a collision on medium",
+                transmission.sender.name(),
+                transmission.msg,
+            );
+            return;
+        }
+
+        let metrics = inner.metrics;
+        let delay = inner.delay.clone();
+        let receivers = inner
+            .attachees
+            .iter()
+            .filter_map(GateRefWeak::upgrade)
+            .filter(|gate| !Arc::ptr_eq(gate, &transmission.sender))
+            .collect::<Vec<_>>();
+        drop(inner);
+
+        let time = SimTime::now() + metrics.latency + delay.sample(metrics.jitter, rng());
+        for receiver in receivers {
+            deliver(transmission.msg.clone(), &receiver, time, sink);
+        }
+    }
+}
+
+/// Delivers a message received from a medium to an attachee, forwarding it
+/// along the gate chain attached to the gate.
+fn deliver<S: EventSink<NetEvents>>(mut msg: Message, gate: &GateRef, time: SimTime, sink: &mut S) {
+    if gate.kind() == GateKind::Standalone {
+        msg.header.last_gate = Some(gate.clone());
+        sink.add(
+            NetEvents::HandleMessageEvent(HandleMessageEvent {
+                module: gate.owner(),
+                message: msg,
+            }),
+            time,
+        );
+    } else {
+        sink.add(
+            NetEvents::MessageExitingConnection(MessageExitingConnection {
+                con: Connection::new_unchecked(gate.clone()),
+                msg,
+            }),
+            time,
+        );
+    }
+}
+
+impl std::fmt::Debug for Medium {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.read().unwrap();
+        f.debug_struct("Medium")
+            .field("metrics", &inner.metrics)
+            .field("delay", &inner.delay)
+            .field("attachees", &inner.attachees.len())
+            .field("ongoing", &inner.ongoing.len())
+            .finish()
+    }
+}
@@ -0,0 +1,837 @@
+//! Physical link abstractions.
+#![allow(clippy::cast_precision_loss)]
+
+use rand::distr::Uniform;
+use rand::prelude::StdRng;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Display};
+use std::sync::{Arc, RwLock};
+
+use crate::net::{
+    message::Message, runtime::ChannelUnbusyNotif, MessageExitingConnection, NetEvents,
+};
+use crate::runtime::{rng, EventSink};
+use crate::time::{Duration, SimTime};
+
+use super::gate::Connection;
+
+mod delay;
+pub use self::delay::*;
+
+mod medium;
+pub use self::medium::*;
+
+mod probe;
+pub use self::probe::*;
+
+mod queue;
+pub use self::queue::*;
+
+/// A readonly reference to a channel.
+pub type ChannelRef = Arc<Channel>;
+
+/// A representation of a one directional delayed link,.
+pub struct Channel {
+    inner: RwLock<ChannelInner>,
+}
+
+#[allow(clippy::struct_excessive_bools)]
+struct ChannelInner {
+    metrics: ChannelMetrics,
+    loss: LossModel,
+    burst: bool,
+    corruption: LossModel,
+    corruption_burst: bool,
+    delay: DelayModel,
+    fifo: bool,
+    busy: bool,
+    active: bool,
+    up: bool,
+    transmission_finish_time: SimTime,
+    last_arrival: SimTime,
+    buffer: Box<dyn QueueDiscipline>,
+    dropped: usize,
+    probes: Vec<Box<dyn ChannelProbe>>,
+}
+
+/// Metrics that define a channels capabilitites.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChannelMetrics {
+    /// The maximum throughput of the channel in bit/s
+    pub bitrate: usize,
+    /// The latency a message endures while transversing a channel.
+    pub latency: Duration,
+    /// The variance in latency.
+    pub jitter: Duration,
+    /// The size of the channels queue in bytes, unless overridden
+    /// by [`Channel::set_queue_discipline`].
+    pub drop_behaviour: ChannelDropBehaviour,
+}
+
+/// A model for packets lost on the medium, independent of the load of a channel.
+///
+/// Loss models draw from the simulation RNG, so that runs using the same
+/// seed lose the same packets. Lost packets still occupy the channel for
+/// their transmission time, but never arrive at the next gate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LossModel {
+    /// No packets are lost.
+    #[default]
+    None,
+    /// Each packet is lost with the given probability.
+    Bernoulli(f64),
+    /// Each bit is corrupted with the given probability (bit error rate),
+    /// losing the packet. Thus the loss probability grows with the
+    /// [`length`](Message::length) of a packet.
+    BitError(f64),
+    /// A two-state Markov chain, modelling burst losses.
+    ///
+    /// Before each packet, the model switches from the good to the
+    /// bad state with probability `p`, and back with probability `r`.
+    /// The packet is then lost with the loss probability of the current state.
+    GilbertElliott {
+        /// The probability to switch from the good to the bad state.
+        p: f64,
+        /// The probability to switch from the bad to the good state.
+        r: f64,
+        /// The loss probability in the good state.
+        loss_good: f64,
+        /// The loss probability in the bad state.
+        loss_bad: f64,
+    },
+}
+
+impl LossModel {
+    /// A Gilbert-Elliott model, that loses all packets in the bad state,
+    /// and no packets in the good state.
+    #[must_use]
+    pub const fn gilbert(p: f64, r: f64) -> Self {
+        Self::GilbertElliott {
+            p,
+            r,
+            loss_good: 0.0,
+            loss_bad: 1.0,
+        }
+    }
+
+    /// The probability that a message is lost, given the current
+    /// state of the model (`burst` indicates the bad state).
+    #[must_use]
+    pub fn loss_probability(&self, msg: &Message, burst: bool) -> f64 {
+        match *self {
+            Self::None => 0.0,
+            Self::Bernoulli(p) => p,
+            Self::BitError(ber) => {
+                let bits = (msg.length() * 8) as f64;
+                -(bits * (-ber).ln_1p()).exp_m1()
+            }
+            Self::GilbertElliott {
+                loss_good,
+                loss_bad,
+                ..
+            } => {
+                if burst {
+                    loss_bad
+                } else {
+                    loss_good
+                }
+            }
+        }
+    }
+
+    /// Decides whether a message is lost, updating the state of the model.
+    fn is_lost(&self, msg: &Message, burst: &mut bool, rng: &mut dyn RngCore) -> bool {
+        match *self {
+            Self::None => false,
+            Self::GilbertElliott { p, r, .. } => {
+                let switch = if *burst { r } else { p };
+                if rng.random::<f64>() < switch {
+                    *burst = !*burst;
+                }
+                rng.random::<f64>() < self.loss_probability(msg, *burst)
+            }
+            _ => rng.random::<f64>() < self.loss_probability(msg, *burst),
+        }
+    }
+}
+
+/// The behaviour a link should follow, if it is oversubscribed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChannelDropBehaviour {
+    /// If a link is currently busy, drop packets
+    #[default]
+    Drop,
+    /// If a link is currently busy, queue packets up to a
+    /// provided queuelength (None means infinite queuelength)
+    Queue(Option<usize>),
+}
+
+impl ChannelInner {
+    fn dup(&self) -> Self {
+        Self {
+            metrics: self.metrics,
+            loss: self.loss,
+            burst: false,
+            corruption: self.corruption,
+            corruption_burst: false,
+            delay: self.delay.clone(),
+            fifo: self.fifo,
+            busy: false,
+            active: false,
+            up: self.up,
+            transmission_finish_time: SimTime::ZERO,
+            last_arrival: SimTime::ZERO,
+            buffer: self.buffer.empty(),
+            dropped: 0,
+            probes: Vec::new(),
+        }
+    }
+
+    fn notify(&mut self, mut f: impl FnMut(&mut dyn ChannelProbe, &ChannelMetrics)) {
+        for probe in &mut self.probes {
+            f(&mut **probe, &self.metrics);
+        }
+    }
+
+    fn record_drops(&mut self, dropped: Vec<Packet>) {
+        for packet in dropped {
+            self.dropped += 1;
+            self.notify(|probe, metrics| {
+                probe.on_message_dropped(metrics, &packet.msg, DropReason::Queue);
+            });
+
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                "Gate '{}' dropping message [{}] pushed onto busy channel",
+                packet.con.prev_hop().unwrap().name(),
+                packet.msg,
+            );
+        }
+    }
+
+    fn calculate_duration(&self, msg: &Message, rng: &mut dyn RngCore) -> Duration {
+        self.metrics.latency
+            + self.metrics.calculate_busy(msg)
+            + self.delay.sample(self.metrics.jitter, rng)
+    }
+
+    /// Starts the transmission of a message onto the medium, deciding
+    /// whether it is lost or corrupted. Returns whether the message is lost.
+    fn transmit(&mut self, msg: &mut Message, rng: &mut dyn RngCore) -> bool {
+        self.notify(|probe, metrics| probe.on_message_transmit(metrics, msg));
+
+        let lost = self.loss.is_lost(msg, &mut self.burst, rng);
+        if lost {
+            self.notify(|probe, metrics| probe.on_message_lost(metrics, msg));
+        } else if self
+            .corruption
+            .is_lost(msg, &mut self.corruption_burst, rng)
+        {
+            msg.header.corrupted = true;
+            self.notify(|probe, metrics| probe.on_message_corrupted(metrics, msg));
+        }
+        lost
+    }
+
+    fn record_link_down_drop(&mut self, msg: &Message) {
+        self.dropped += 1;
+        self.notify(|probe, metrics| probe.on_message_dropped(metrics, msg, DropReason::LinkDown));
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Channel dropping message [{}], since the link is down", msg);
+    }
+}
+
+impl Channel {
+    pub(super) fn dup(&self) -> Self {
+        Channel {
+            inner: RwLock::new(self.inner.read().unwrap().dup()),
+        }
+    }
+
+    /// A description of the channels capabilities,
+    /// independent from its current state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn metrics(&self) -> ChannelMetrics {
+        self.inner.read().unwrap().metrics
+    }
+
+    /// Replaces the metrics of the channel.
+    ///
+    /// The new metrics apply to all messages transmitted afterwards,
+    /// while messages already on the medium keep their arrival time.
+    /// The queue discipline of the channel is not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_metrics(&self, metrics: ChannelMetrics) {
+        self.inner.write().unwrap().metrics = metrics;
+    }
+
+    /// A indicator whether the link is up, and thus able to transmit messages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn is_up(&self) -> bool {
+        self.inner.read().unwrap().up
+    }
+
+    /// Takes the link down.
+    ///
+    /// All buffered packets are dropped. While the link is down, messages
+    /// sent onto the channel, as well as messages arriving from the medium,
+    /// are dropped. Drops are reported to the [`ChannelProbe`] and counted
+    /// by [`dropped`](Self::dropped).
+    ///
+    /// To drop all messages on the medium immediately, including those that
+    /// would arrive after the link is up again, use
+    /// [`Runtime::link_down_at`](crate::runtime::Runtime::link_down_at).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_down(&self) {
+        let mut chan = self.inner.write().unwrap();
+        chan.up = false;
+
+        let mut dropped = Vec::new();
+        while let Some(packet) = chan.buffer.dequeue(&mut dropped) {
+            dropped.push(packet);
+        }
+        for packet in dropped {
+            chan.record_link_down_drop(&packet.msg);
+        }
+    }
+
+    /// Brings the link up again, after it was taken down using
+    /// [`set_down`](Self::set_down).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_up(&self) {
+        self.inner.write().unwrap().up = true;
+    }
+
+    /// Reports a message, that arrived at the far end of the channel.
+    pub(crate) fn record_delivery(&self, msg: &Message) {
+        self.inner
+            .write()
+            .unwrap()
+            .notify(|probe, metrics| probe.on_message_delivered(metrics, msg));
+    }
+
+    /// Reports a message on the medium, that was dropped since
+    /// the link went down.
+    pub(crate) fn drop_in_flight(&self, msg: &Message) {
+        self.inner.write().unwrap().record_link_down_drop(msg);
+    }
+
+    /// A indicator whether a channel is currently busy transmissting a
+    /// packet onto the medium.
+    ///
+    /// Note that being non-busy does not mean that no packet is currently on the medium
+    /// it just means that all bits have been put onto the medium.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn is_busy(&self) -> bool {
+        self.inner.read().unwrap().busy
+    }
+
+    /// The loss model of the channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn loss_model(&self) -> LossModel {
+        self.inner.read().unwrap().loss
+    }
+
+    /// Sets the loss model of the channel, resetting the state of
+    /// the previous model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_loss_model(&self, loss: LossModel) {
+        let mut chan = self.inner.write().unwrap();
+        chan.loss = loss;
+        chan.burst = false;
+    }
+
+    /// The corruption model of the channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn corruption_model(&self) -> LossModel {
+        self.inner.read().unwrap().corruption
+    }
+
+    /// Sets the corruption model of the channel, resetting the state of
+    /// the previous model.
+    ///
+    /// The model is evaluated like a [`LossModel`] for each message that is
+    /// not lost, but affected messages are delivered with their
+    /// [`corrupted`](crate::net::message::Header::corrupted) flag set,
+    /// instead of being lost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_corruption_model(&self, corruption: LossModel) {
+        let mut chan = self.inner.write().unwrap();
+        chan.corruption = corruption;
+        chan.corruption_burst = false;
+    }
+
+    /// The distribution of the jitter of the channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn delay_model(&self) -> DelayModel {
+        self.inner.read().unwrap().delay.clone()
+    }
+
+    /// Sets the distribution of the jitter of the channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_delay_model(&self, delay: DelayModel) {
+        self.inner.write().unwrap().delay = delay;
+    }
+
+    /// A indicator whether messages are delivered in the order they were
+    /// transmitted, even if jitter would reorder them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn is_fifo(&self) -> bool {
+        self.inner.read().unwrap().fifo
+    }
+
+    /// Sets whether messages are delivered in the order they were transmitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_fifo(&self, fifo: bool) {
+        self.inner.write().unwrap().fifo = fifo;
+    }
+
+    /// Replaces the queue discipline, used to buffer packets while the
+    /// channel is busy. This overrides the
+    /// [`drop_behaviour`](ChannelMetrics::drop_behaviour) of the channel.
+    ///
+    /// Packets buffered by the previous discipline are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn set_queue_discipline(&self, discipline: impl QueueDiscipline) {
+        self.inner.write().unwrap().buffer = Box::new(discipline);
+    }
+
+    /// The number of packets currently buffered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn queue_len(&self) -> usize {
+        self.inner.read().unwrap().buffer.len()
+    }
+
+    /// The number of bytes currently buffered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn queue_bytes(&self) -> usize {
+        self.inner.read().unwrap().buffer.bytes()
+    }
+
+    /// The number of packets dropped by the queue discipline
+    /// since the channel was created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.inner.read().unwrap().dropped
+    }
+
+    /// Attaches a probe, in addition to all previously attached probes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel mutex was poisoned.
+    pub fn attach_probe(&self, probe: impl ChannelProbe) {
+        let mut chan = self
+            .inner
+            .write()
+            .expect("failed to get inner channel lock");
+        chan.probes.push(Box::new(probe));
+    }
+
+    /// Sets the channel busy, announcing that the message will be trabńsmitted
+    /// in '`sim_time`' time units.
+    pub(crate) fn set_busy_until(&self, sim_time: SimTime) {
+        let mut chan = self.inner.write().unwrap();
+        chan.busy = true;
+        chan.transmission_finish_time = sim_time;
+    }
+
+    /// Returns the time when the packet currently being transmitted onto the medium
+    /// has been fully transmitted, or [`SimTime::ZERO`] if no packet is currently being transmitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn transmission_finish_time(&self) -> SimTime {
+        self.inner.read().unwrap().transmission_finish_time
+    }
+
+    /// Creates a new channel using the given metrics,
+    /// with an initially unbusy state.
+    #[must_use]
+    pub fn new(metrics: ChannelMetrics) -> ChannelRef {
+        ChannelRef::new(Channel {
+            inner: RwLock::new(ChannelInner {
+                metrics,
+                loss: LossModel::None,
+                burst: false,
+                corruption: LossModel::None,
+                corruption_burst: false,
+                delay: DelayModel::Uniform,
+                fifo: false,
+                busy: false,
+                active: false,
+                up: true,
+                transmission_finish_time: SimTime::ZERO,
+                last_arrival: SimTime::ZERO,
+                buffer: Box::new(DropTail::from(metrics.drop_behaviour)),
+                dropped: 0,
+                probes: Vec::new(),
+            }),
+        })
+    }
+
+    /// Calcualtes the packet travel duration using the
+    /// underlying metric and delay model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    pub fn calculate_duration(&self, msg: &Message, rng: &mut StdRng) -> Duration {
+        self.inner.read().unwrap().calculate_duration(msg, rng)
+    }
+
+    /// Calcualtes the busy time of the channel using
+    /// the underlying metric.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation core was poisoned.
+    #[must_use]
+    pub fn calculate_busy(&self, msg: &Message) -> Duration {
+        self.inner.read().unwrap().metrics.calculate_busy(msg)
+    }
+
+    pub(super) fn send_message<S: EventSink<NetEvents>>(
+        self: Arc<Self>,
+        mut msg: Message,
+        via: Connection,
+        sink: &mut S,
+    ) {
+        let rng_ref = rng();
+        let mut chan = self.inner.write().unwrap();
+
+        if !chan.up {
+            chan.record_link_down_drop(&msg);
+        } else if chan.busy {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                "Gate '{}' added message [{}] to queue of channel",
+                via.prev_hop().unwrap().name(),
+                msg,
+            );
+
+            let depth = chan.buffer.len();
+            chan.notify(|probe, metrics| probe.on_message_enqueued(metrics, &msg, depth));
+
+            let mut dropped = Vec::new();
+            chan.buffer.enqueue(
+                Packet {
+                    msg,
+                    con: via,
+                    enqueued: SimTime::now(),
+                },
+                &mut dropped,
+            );
+            chan.record_drops(dropped);
+        } else {
+            let dur = chan.calculate_duration(&msg, rng_ref);
+            let lost = chan.transmit(&mut msg, rng_ref);
+            let ChannelInner {
+                probes,
+                metrics,
+                fifo,
+                last_arrival,
+                active,
+                ..
+            } = &mut *chan;
+
+            let busy = metrics.calculate_busy(&msg);
+
+            let mut next_event_time = SimTime::now() + dur;
+            if *fifo && !lost {
+                next_event_time = next_event_time.max(*last_arrival);
+                *last_arrival = next_event_time;
+            }
+
+            if busy == Duration::ZERO {
+                for probe in probes.iter_mut() {
+                    probe.on_transmission_end(metrics);
+                }
+                if *active {
+                    *active = false;
+                    for probe in probes.iter_mut() {
+                        probe.on_idle(metrics);
+                    }
+                }
+            } else {
+                if !*active {
+                    *active = true;
+                    for probe in probes.iter_mut() {
+                        probe.on_busy(metrics);
+                    }
+                }
+
+                let transmissin_finish = SimTime::now() + busy;
+
+                drop(chan);
+                self.set_busy_until(transmissin_finish);
+
+                sink.add(
+                    NetEvents::ChannelUnbusyNotif(ChannelUnbusyNotif {
+                        channel: self.clone(),
+                    }),
+                    transmissin_finish,
+                );
+            }
+
+            if lost {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    "Gate '{}' lost message [{}] on channel",
+                    via.prev_hop().unwrap().name(),
+                    msg,
+                );
+                drop(msg);
+                return;
+            }
+
+            sink.add(
+                NetEvents::MessageExitingConnection(MessageExitingConnection {
+                    con: via.clone(),
+                    msg,
+                }),
+                next_event_time,
+            );
+
+            // must break iteration,
+            // but not perform on-module handling
+        }
+    }
+
+    /// Provides access to the transmission state of the channel, including
+    /// all buffered packets.
+    pub(crate) fn with_transmission_state<R>(
+        &self,
+        f: impl FnOnce(bool, SimTime, &dyn QueueDiscipline) -> R,
+    ) -> R {
+        let chan = self.inner.read().unwrap();
+        f(chan.busy, chan.transmission_finish_time, &*chan.buffer)
+    }
+
+    /// Overrides the transmission state of the channel, replacing all
+    /// buffered packets.
+    pub(crate) fn set_transmission_state(
+        &self,
+        busy: bool,
+        transmission_finish_time: SimTime,
+        packets: Vec<Packet>,
+    ) {
+        let mut chan = self.inner.write().unwrap();
+        chan.busy = busy;
+        chan.transmission_finish_time = transmission_finish_time;
+        chan.buffer = chan.buffer.empty();
+        for packet in packets {
+            chan.buffer.restore(packet);
+        }
+    }
+
+    /// The arrival time of the last message delivered in FIFO order.
+    pub(crate) fn last_arrival(&self) -> SimTime {
+        self.inner.read().unwrap().last_arrival
+    }
+
+    /// Overrides the arrival time of the last message delivered in FIFO order.
+    pub(crate) fn set_last_arrival(&self, last_arrival: SimTime) {
+        self.inner.write().unwrap().last_arrival = last_arrival;
+    }
+
+    /// Indicates whether the loss model is in its bad state.
+    pub(crate) fn in_burst(&self) -> bool {
+        self.inner.read().unwrap().burst
+    }
+
+    /// Overrides the state of the loss model.
+    pub(crate) fn set_in_burst(&self, burst: bool) {
+        self.inner.write().unwrap().burst = burst;
+    }
+
+    /// Indicates whether the corruption model is in its bad state.
+    pub(crate) fn in_corruption_burst(&self) -> bool {
+        self.inner.read().unwrap().corruption_burst
+    }
+
+    /// Overrides the state of the corruption model.
+    pub(crate) fn set_in_corruption_burst(&self, burst: bool) {
+        self.inner.write().unwrap().corruption_burst = burst;
+    }
+
+    /// Resets the busy state of a channel.
+    pub(crate) fn unbusy<S: EventSink<NetEvents>>(self: Arc<Self>, sink: &mut S) {
+        let mut chan = self.inner.write().unwrap();
+
+        chan.busy = false;
+        chan.transmission_finish_time = SimTime::ZERO;
+
+        chan.notify(ChannelProbe::on_transmission_end);
+
+        let mut dropped = Vec::new();
+        let next = chan.buffer.dequeue(&mut dropped);
+        chan.record_drops(dropped);
+
+        if let Some(packet) = next {
+            drop(chan);
+            self.send_message(packet.msg, packet.con, sink);
+        } else if chan.active {
+            chan.active = false;
+            chan.notify(ChannelProbe::on_idle);
+        }
+    }
+}
+
+impl Debug for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[derive(Debug)]
+        #[allow(unused)]
+        enum FmtChannelState {
+            Idle,
+            Busy {
+                until: SimTime,
+                bytes: usize,
+                packets: usize,
+            },
+        }
+
+        impl FmtChannelState {
+            fn from(channel: &ChannelInner) -> Self {
+                if channel.busy {
+                    Self::Busy {
+                        until: channel.transmission_finish_time,
+                        bytes: channel.buffer.bytes(),
+                        packets: channel.buffer.len(),
+                    }
+                } else {
+                    Self::Idle
+                }
+            }
+        }
+
+        let this = self.inner.read().unwrap();
+
+        f.debug_struct("Channel")
+            .field("metrics", &this.metrics)
+            .field("state", &FmtChannelState::from(&this))
+            .finish()
+    }
+}
+
+impl ChannelMetrics {
+    /// Creates a new instance of channel metrics.
+    #[must_use]
+    pub const fn new(
+        bitrate: usize,
+        latency: Duration,
+        jitter: Duration,
+        drop_behaviour: ChannelDropBehaviour,
+    ) -> Self {
+        Self {
+            bitrate,
+            latency,
+            jitter,
+            drop_behaviour,
+        }
+    }
+
+    /// Calcualtes the duration a message travels on a link.
+    #[allow(clippy::if_same_then_else, clippy::missing_panics_doc)]
+    pub fn calculate_duration(&self, msg: &Message, rng: &mut dyn RngCore) -> Duration {
+        let transmission_time = self.calculate_busy(msg);
+        if self.jitter == Duration::ZERO {
+            self.latency + transmission_time
+        } else {
+            let perc = rng.sample(Uniform::new(0.0f64, self.jitter.as_secs_f64()).unwrap());
+            self.latency + transmission_time + Duration::from_secs_f64(perc)
+        }
+    }
+
+    /// Calculate the duration the channel is busy transmitting the
+    /// message onto the channel.
+    #[must_use]
+    pub fn calculate_busy(&self, msg: &Message) -> Duration {
+        if self.bitrate == 0 {
+            Duration::ZERO
+        } else {
+            let len = msg.length() * 8;
+            Duration::from_secs_f64(len as f64 / self.bitrate as f64)
+        }
+    }
+}
+
+impl Display for ChannelMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelMetrics")
+            .field("bitrate (bit/s)", &self.bitrate)
+            .field("lateny", &self.latency)
+            .field("jitter", &self.jitter)
+            .finish()
+    }
+}
+
+impl Eq for ChannelMetrics {}
@@ -0,0 +1,258 @@
+use std::sync::{Arc, Mutex};
+
+use crate::net::message::Message;
+use crate::time::{Duration, SimTime};
+
+use super::ChannelMetrics;
+
+/// A trait to define channel probing.
+///
+/// Probes are attached to a channel using [`Channel::attach_probe`](super::Channel::attach_probe)
+/// and are notified about all events on the channel. All hooks are
+/// optional, and called at the simulation time the event occurred.
+///
+/// Note that hooks are called while the channel is locked, so probes must
+/// not access the channel they are attached to.
+pub trait ChannelProbe: 'static {
+    /// Reacts to the start of the transmission of a message onto the medium.
+    fn on_message_transmit(&mut self, chan: &ChannelMetrics, msg: &Message) {
+        let _ = (chan, msg);
+    }
+
+    /// Reacts to the end of the transmission of a message onto the medium.
+    ///
+    /// The message may still travel on the medium, until it is
+    /// [delivered](Self::on_message_delivered).
+    fn on_transmission_end(&mut self, chan: &ChannelMetrics) {
+        let _ = chan;
+    }
+
+    /// Reacts to a message arriving at the busy channel, that is handed
+    /// to the queue discipline. The `depth` is the number of packets
+    /// buffered before the message arrived.
+    ///
+    /// The queue discipline may drop the message, or other packets in
+    /// response, which is reported using [`on_message_dropped`](Self::on_message_dropped).
+    fn on_message_enqueued(&mut self, chan: &ChannelMetrics, msg: &Message, depth: usize) {
+        let _ = (chan, msg, depth);
+    }
+
+    /// Reacts to a message lost on the medium, due to the
+    /// [`LossModel`](super::LossModel) of the channel.
+    fn on_message_lost(&mut self, chan: &ChannelMetrics, msg: &Message) {
+        let _ = (chan, msg);
+    }
+
+    /// Reacts to a message corrupted on the medium, due to the corruption
+    /// model of the channel. The message is still delivered, with its
+    /// [`corrupted`](crate::net::message::Header::corrupted) flag set.
+    fn on_message_corrupted(&mut self, chan: &ChannelMetrics, msg: &Message) {
+        let _ = (chan, msg);
+    }
+
+    /// Reacts to a message dropped by the channel.
+    fn on_message_dropped(&mut self, chan: &ChannelMetrics, msg: &Message, reason: DropReason) {
+        let _ = (chan, msg, reason);
+    }
+
+    /// Reacts to a message arriving at the gate at the far end of the channel.
+    fn on_message_delivered(&mut self, chan: &ChannelMetrics, msg: &Message) {
+        let _ = (chan, msg);
+    }
+
+    /// Reacts to the channel becoming busy, after being idle.
+    fn on_busy(&mut self, chan: &ChannelMetrics) {
+        let _ = chan;
+    }
+
+    /// Reacts to the channel becoming idle, once all buffered
+    /// messages are transmitted.
+    fn on_idle(&mut self, chan: &ChannelMetrics) {
+        let _ = chan;
+    }
+}
+
+/// The reason a message was dropped by a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DropReason {
+    /// The queue discipline rejected the message, or dropped it from the queue.
+    Queue,
+    /// The link was down, while the message was sent, buffered or on the medium.
+    LinkDown,
+}
+
+/// A probe that measures the throughput of a channel, based on
+/// the messages delivered at the far end.
+///
+/// The probe is a handle to shared state, so a clone can be attached
+/// to a channel, while the original is used to read the measurements.
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::net::channel::ThroughputProbe;
+/// # let channel = Channel::new(ChannelMetrics::new(
+/// #     1000, Duration::ZERO, Duration::ZERO, ChannelDropBehaviour::Drop,
+/// # ));
+/// let probe = ThroughputProbe::new();
+/// channel.attach_probe(probe.clone());
+/// // ... run the simulation
+/// println!("{} bit/s", probe.throughput());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThroughputProbe {
+    inner: Arc<Mutex<ThroughputState>>,
+}
+
+#[derive(Debug)]
+struct ThroughputState {
+    since: SimTime,
+    packets: usize,
+    bytes: usize,
+}
+
+impl ThroughputProbe {
+    /// Creates a new probe, measuring from the current simulation time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ThroughputState {
+                since: SimTime::now(),
+                packets: 0,
+                bytes: 0,
+            })),
+        }
+    }
+
+    /// The number of messages delivered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probe was poisoned.
+    #[must_use]
+    pub fn delivered(&self) -> usize {
+        self.inner.lock().unwrap().packets
+    }
+
+    /// The number of bytes delivered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probe was poisoned.
+    #[must_use]
+    pub fn delivered_bytes(&self) -> usize {
+        self.inner.lock().unwrap().bytes
+    }
+
+    /// The average throughput in bit/s, since the probe was created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probe was poisoned.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn throughput(&self) -> f64 {
+        let state = self.inner.lock().unwrap();
+        let elapsed = SimTime::now().saturating_duration_since(state.since);
+        if elapsed == Duration::ZERO {
+            0.0
+        } else {
+            (state.bytes * 8) as f64 / elapsed.as_secs_f64()
+        }
+    }
+}
+
+impl Default for ThroughputProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelProbe for ThroughputProbe {
+    fn on_message_delivered(&mut self, _: &ChannelMetrics, msg: &Message) {
+        let mut state = self.inner.lock().unwrap();
+        state.packets += 1;
+        state.bytes += msg.length();
+    }
+}
+
+/// A probe that measures the utilisation of a channel, the fraction
+/// of time the channel is busy transmitting messages.
+///
+/// The probe is a handle to shared state, so a clone can be attached
+/// to a channel, while the original is used to read the measurements.
+#[derive(Debug, Clone)]
+pub struct UtilisationProbe {
+    inner: Arc<Mutex<UtilisationState>>,
+}
+
+#[derive(Debug)]
+struct UtilisationState {
+    since: SimTime,
+    busy_since: Option<SimTime>,
+    busy: Duration,
+}
+
+impl UtilisationProbe {
+    /// Creates a new probe, measuring from the current simulation time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(UtilisationState {
+                since: SimTime::now(),
+                busy_since: None,
+                busy: Duration::ZERO,
+            })),
+        }
+    }
+
+    /// The total time the channel was busy, since the probe was created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probe was poisoned.
+    #[must_use]
+    pub fn busy_time(&self) -> Duration {
+        let state = self.inner.lock().unwrap();
+        state.busy
+            + state.busy_since.map_or(Duration::ZERO, |busy_since| {
+                SimTime::now().saturating_duration_since(busy_since)
+            })
+    }
+
+    /// The fraction of time the channel was busy, since the probe was created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probe was poisoned.
+    #[must_use]
+    pub fn utilisation(&self) -> f64 {
+        let since = self.inner.lock().unwrap().since;
+        let elapsed = SimTime::now().saturating_duration_since(since);
+        if elapsed == Duration::ZERO {
+            0.0
+        } else {
+            self.busy_time().as_secs_f64() / elapsed.as_secs_f64()
+        }
+    }
+}
+
+impl Default for UtilisationProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelProbe for UtilisationProbe {
+    fn on_busy(&mut self, _: &ChannelMetrics) {
+        self.inner.lock().unwrap().busy_since = Some(SimTime::now());
+    }
+
+    fn on_idle(&mut self, _: &ChannelMetrics) {
+        let mut state = self.inner.lock().unwrap();
+        if let Some(busy_since) = state.busy_since.take() {
+            state.busy += SimTime::now().saturating_duration_since(busy_since);
+        }
+    }
+}
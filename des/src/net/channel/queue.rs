@@ -0,0 +1,653 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, VecDeque},
+};
+
+use crate::{
+    net::{
+        gate::Connection,
+        message::{Message, MessageKind},
+    },
+    runtime::random,
+    time::{Duration, SimTime},
+};
+
+use super::ChannelDropBehaviour;
+
+/// A message buffered by a busy channel.
+#[derive(Debug)]
+pub struct Packet {
+    pub(crate) msg: Message,
+    pub(crate) con: Connection,
+    pub(crate) enqueued: SimTime,
+}
+
+impl Packet {
+    /// The buffered message.
+    pub fn message(&self) -> &Message {
+        &self.msg
+    }
+
+    /// The kind of the buffered message, used to classify packets.
+    #[must_use]
+    pub fn kind(&self) -> MessageKind {
+        self.msg.header().kind
+    }
+
+    /// The length of the buffered message in bytes.
+    #[must_use]
+    pub fn bytes(&self) -> usize {
+        self.msg.length()
+    }
+
+    /// The time the packet was buffered.
+    #[must_use]
+    pub fn enqueued(&self) -> SimTime {
+        self.enqueued
+    }
+}
+
+/// A queue discipline, that buffers packets while a channel is busy.
+///
+/// Disciplines decide which packets are buffered, and in which order
+/// buffered packets are transmitted once the channel becomes available.
+/// Packets dropped by the discipline are pushed onto `dropped`, so that
+/// the channel can account for them.
+pub trait QueueDiscipline: 'static {
+    /// Offers a packet to the queue.
+    fn enqueue(&mut self, packet: Packet, dropped: &mut Vec<Packet>);
+
+    /// Removes the next packet to be transmitted from the queue.
+    fn dequeue(&mut self, dropped: &mut Vec<Packet>) -> Option<Packet>;
+
+    /// The number of buffered packets.
+    fn len(&self) -> usize;
+
+    /// Indicates whether no packets are buffered.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of buffered bytes.
+    fn bytes(&self) -> usize;
+
+    /// An iterator over all buffered packets, in the order
+    /// they would be transmitted.
+    fn packets(&self) -> Box<dyn Iterator<Item = &Packet> + '_>;
+
+    /// Creates an empty queue with the same configuration.
+    fn empty(&self) -> Box<dyn QueueDiscipline>;
+
+    /// Adds a packet restored from a snapshot, bypassing all drop decisions.
+    ///
+    /// The default implementation offers the packet to the queue.
+    fn restore(&mut self, packet: Packet) {
+        self.enqueue(packet, &mut Vec::new());
+    }
+}
+
+/// A FIFO queue, dropping arriving packets once a limit is reached.
+#[derive(Debug, Default)]
+pub struct DropTail {
+    packets: VecDeque<Packet>,
+    bytes: usize,
+    packet_limit: Option<usize>,
+    byte_limit: Option<usize>,
+}
+
+impl DropTail {
+    /// Creates an unbounded queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of buffered packets.
+    #[must_use]
+    pub fn packet_limit(mut self, limit: usize) -> Self {
+        self.packet_limit = Some(limit);
+        self
+    }
+
+    /// Limits the number of buffered bytes.
+    #[must_use]
+    pub fn byte_limit(mut self, limit: usize) -> Self {
+        self.byte_limit = Some(limit);
+        self
+    }
+
+    fn fresh(&self) -> DropTail {
+        DropTail {
+            packets: VecDeque::new(),
+            bytes: 0,
+            packet_limit: self.packet_limit,
+            byte_limit: self.byte_limit,
+        }
+    }
+
+    fn fits(&self, packet: &Packet) -> bool {
+        self.packets.len() < self.packet_limit.unwrap_or(usize::MAX)
+            && self.bytes + packet.bytes() <= self.byte_limit.unwrap_or(usize::MAX)
+    }
+
+    fn push(&mut self, packet: Packet) {
+        self.bytes += packet.bytes();
+        self.packets.push_back(packet);
+    }
+
+    fn pop(&mut self) -> Option<Packet> {
+        let packet = self.packets.pop_front()?;
+        self.bytes -= packet.bytes();
+        Some(packet)
+    }
+}
+
+impl From<ChannelDropBehaviour> for DropTail {
+    fn from(behaviour: ChannelDropBehaviour) -> Self {
+        match behaviour {
+            ChannelDropBehaviour::Drop => DropTail::new().packet_limit(0),
+            ChannelDropBehaviour::Queue(None) => DropTail::new(),
+            ChannelDropBehaviour::Queue(Some(limit)) => DropTail::new().byte_limit(limit),
+        }
+    }
+}
+
+impl QueueDiscipline for DropTail {
+    fn enqueue(&mut self, packet: Packet, dropped: &mut Vec<Packet>) {
+        if self.fits(&packet) {
+            self.push(packet);
+        } else {
+            dropped.push(packet);
+        }
+    }
+
+    fn dequeue(&mut self, _: &mut Vec<Packet>) -> Option<Packet> {
+        self.pop()
+    }
+
+    fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    fn packets(&self) -> Box<dyn Iterator<Item = &Packet> + '_> {
+        Box::new(self.packets.iter())
+    }
+
+    fn empty(&self) -> Box<dyn QueueDiscipline> {
+        Box::new(self.fresh())
+    }
+
+    fn restore(&mut self, packet: Packet) {
+        self.push(packet);
+    }
+}
+
+/// Random early detection (RED).
+///
+/// Arriving packets are dropped with a probability, that grows linearly
+/// from zero to `max_p` while the average queue length grows from `min_th`
+/// to `max_th` packets. Above `max_th` all arriving packets are dropped.
+/// The average queue length is an exponentially weighted moving average,
+/// updated on each arrival. Drop decisions draw from the simulation RNG.
+#[derive(Debug)]
+pub struct Red {
+    queue: DropTail,
+    min_th: f64,
+    max_th: f64,
+    max_p: f64,
+    weight: f64,
+    avg: f64,
+}
+
+impl Red {
+    /// Creates a new queue with the given thresholds in packets, using
+    /// a queue weight of `0.002`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_th` is not less than `max_th`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(min_th: usize, max_th: usize, max_p: f64) -> Self {
+        assert!(min_th < max_th, "RED requires min_th < max_th");
+        Self {
+            queue: DropTail::new(),
+            min_th: min_th as f64,
+            max_th: max_th as f64,
+            max_p,
+            weight: 0.002,
+            avg: 0.0,
+        }
+    }
+
+    /// Sets the weight of the current queue length in the moving average.
+    #[must_use]
+    pub fn weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Limits the number of buffered packets.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.queue = self.queue.packet_limit(limit);
+        self
+    }
+
+    /// The current average queue length.
+    #[must_use]
+    pub fn average(&self) -> f64 {
+        self.avg
+    }
+}
+
+impl QueueDiscipline for Red {
+    #[allow(clippy::cast_precision_loss)]
+    fn enqueue(&mut self, packet: Packet, dropped: &mut Vec<Packet>) {
+        self.avg = (1.0 - self.weight) * self.avg + self.weight * self.queue.len() as f64;
+
+        let drop = if self.avg < self.min_th {
+            false
+        } else if self.avg >= self.max_th {
+            true
+        } else {
+            let p = self.max_p * (self.avg - self.min_th) / (self.max_th - self.min_th);
+            random::<f64>() < p
+        };
+
+        if drop {
+            dropped.push(packet);
+        } else {
+            self.queue.enqueue(packet, dropped);
+        }
+    }
+
+    fn dequeue(&mut self, dropped: &mut Vec<Packet>) -> Option<Packet> {
+        self.queue.dequeue(dropped)
+    }
+
+    fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn bytes(&self) -> usize {
+        self.queue.bytes()
+    }
+
+    fn packets(&self) -> Box<dyn Iterator<Item = &Packet> + '_> {
+        self.queue.packets()
+    }
+
+    fn empty(&self) -> Box<dyn QueueDiscipline> {
+        Box::new(Red {
+            queue: self.queue.fresh(),
+            min_th: self.min_th,
+            max_th: self.max_th,
+            max_p: self.max_p,
+            weight: self.weight,
+            avg: 0.0,
+        })
+    }
+
+    fn restore(&mut self, packet: Packet) {
+        self.queue.restore(packet);
+    }
+}
+
+/// Controlled delay (`CoDel`) active queue management.
+///
+/// Packets are dropped at the head of the queue, once their sojourn time
+/// exceeded `target` for at least `interval`. While dropping, the time
+/// between drops shrinks with the square root of the number of drops.
+#[derive(Debug)]
+pub struct CoDel {
+    queue: DropTail,
+    target: Duration,
+    interval: Duration,
+
+    first_above_time: Option<SimTime>,
+    dropping: bool,
+    drop_next: SimTime,
+    count: u32,
+    last_count: u32,
+}
+
+impl CoDel {
+    /// Creates a new queue with the given target sojourn time and interval.
+    #[must_use]
+    pub fn new(target: Duration, interval: Duration) -> Self {
+        Self {
+            queue: DropTail::new(),
+            target,
+            interval,
+            first_above_time: None,
+            dropping: false,
+            drop_next: SimTime::ZERO,
+            count: 0,
+            last_count: 0,
+        }
+    }
+
+    /// Limits the number of buffered packets.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.queue = self.queue.packet_limit(limit);
+        self
+    }
+
+    fn control_law(&self, t: SimTime) -> SimTime {
+        t + self.interval.div_f64(f64::from(self.count).sqrt())
+    }
+
+    fn ok_to_drop(&mut self, packet: &Packet, now: SimTime) -> bool {
+        if now - packet.enqueued < self.target {
+            self.first_above_time = None;
+            return false;
+        }
+        if let Some(first_above_time) = self.first_above_time {
+            now >= first_above_time
+        } else {
+            self.first_above_time = Some(now + self.interval);
+            false
+        }
+    }
+}
+
+impl Default for CoDel {
+    /// A queue using a target of 5ms and an interval of 100ms.
+    fn default() -> Self {
+        Self::new(Duration::from_millis(5), Duration::from_millis(100))
+    }
+}
+
+impl QueueDiscipline for CoDel {
+    fn enqueue(&mut self, packet: Packet, dropped: &mut Vec<Packet>) {
+        self.queue.enqueue(packet, dropped);
+    }
+
+    fn dequeue(&mut self, dropped: &mut Vec<Packet>) -> Option<Packet> {
+        let now = SimTime::now();
+        let Some(mut packet) = self.queue.pop() else {
+            self.first_above_time = None;
+            self.dropping = false;
+            return None;
+        };
+
+        let ok_to_drop = self.ok_to_drop(&packet, now);
+        if self.dropping {
+            if !ok_to_drop {
+                self.dropping = false;
+            }
+            while self.dropping && now >= self.drop_next {
+                dropped.push(packet);
+                self.count += 1;
+                let Some(next) = self.queue.pop() else {
+                    self.dropping = false;
+                    return None;
+                };
+                packet = next;
+                if self.ok_to_drop(&packet, now) {
+                    self.drop_next = self.control_law(self.drop_next);
+                } else {
+                    self.dropping = false;
+                }
+            }
+        } else if ok_to_drop {
+            dropped.push(packet);
+            packet = self.queue.pop()?;
+            self.ok_to_drop(&packet, now);
+            self.dropping = true;
+
+            let delta = self.count - self.last_count;
+            self.count = if delta > 1
+                && now.saturating_duration_since(self.drop_next) < self.interval * 16
+            {
+                delta
+            } else {
+                1
+            };
+            self.drop_next = self.control_law(now);
+            self.last_count = self.count;
+        }
+
+        Some(packet)
+    }
+
+    fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn bytes(&self) -> usize {
+        self.queue.bytes()
+    }
+
+    fn packets(&self) -> Box<dyn Iterator<Item = &Packet> + '_> {
+        self.queue.packets()
+    }
+
+    fn empty(&self) -> Box<dyn QueueDiscipline> {
+        Box::new(CoDel {
+            queue: self.queue.fresh(),
+            ..CoDel::new(self.target, self.interval)
+        })
+    }
+
+    fn restore(&mut self, packet: Packet) {
+        self.queue.restore(packet);
+    }
+}
+
+/// Strict priority scheduling, keyed on the [`kind`](crate::net::message::Header::kind)
+/// of messages.
+///
+/// Packets are served in order of the priority of their kind, highest first.
+/// Packets of the same priority are served in FIFO order. Kinds without an
+/// assigned priority use priority `0`.
+#[derive(Debug, Default)]
+pub struct StrictPriority {
+    priorities: BTreeMap<MessageKind, u8>,
+    queues: BTreeMap<Reverse<u8>, VecDeque<Packet>>,
+    len: usize,
+    bytes: usize,
+    limit: Option<usize>,
+}
+
+impl StrictPriority {
+    /// Creates an unbounded queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assigns a priority to a message kind.
+    #[must_use]
+    pub fn priority(mut self, kind: MessageKind, priority: u8) -> Self {
+        self.priorities.insert(kind, priority);
+        self
+    }
+
+    /// Limits the number of buffered packets over all priorities.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn push(&mut self, packet: Packet) {
+        let priority = self.priorities.get(&packet.kind()).copied().unwrap_or(0);
+        self.len += 1;
+        self.bytes += packet.bytes();
+        self.queues
+            .entry(Reverse(priority))
+            .or_default()
+            .push_back(packet);
+    }
+}
+
+impl QueueDiscipline for StrictPriority {
+    fn enqueue(&mut self, packet: Packet, dropped: &mut Vec<Packet>) {
+        if self.len < self.limit.unwrap_or(usize::MAX) {
+            self.push(packet);
+        } else {
+            dropped.push(packet);
+        }
+    }
+
+    fn dequeue(&mut self, _: &mut Vec<Packet>) -> Option<Packet> {
+        let packet = self.queues.values_mut().find_map(VecDeque::pop_front)?;
+        self.len -= 1;
+        self.bytes -= packet.bytes();
+        Some(packet)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    fn packets(&self) -> Box<dyn Iterator<Item = &Packet> + '_> {
+        Box::new(self.queues.values().flatten())
+    }
+
+    fn empty(&self) -> Box<dyn QueueDiscipline> {
+        Box::new(StrictPriority {
+            priorities: self.priorities.clone(),
+            limit: self.limit,
+            ..StrictPriority::default()
+        })
+    }
+
+    fn restore(&mut self, packet: Packet) {
+        self.push(packet);
+    }
+}
+
+/// Weighted fair queueing, keyed on the [`kind`](crate::net::message::Header::kind)
+/// of messages.
+///
+/// Each kind is served with a share of the channel proportional to its
+/// weight, measured in bytes. This implementation uses self-clocked fair
+/// queueing: each packet is tagged with a virtual finish time, and packets
+/// are served in order of their tags. Kinds without an assigned weight
+/// use weight `1.0`.
+#[derive(Debug, Default)]
+pub struct WeightedFair {
+    weights: BTreeMap<MessageKind, f64>,
+    queues: BTreeMap<MessageKind, VecDeque<(f64, Packet)>>,
+    last_finish: BTreeMap<MessageKind, f64>,
+    virtual_time: f64,
+    len: usize,
+    bytes: usize,
+    limit: Option<usize>,
+}
+
+impl WeightedFair {
+    /// Creates an unbounded queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assigns a weight to a message kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight is not positive.
+    #[must_use]
+    pub fn weight(mut self, kind: MessageKind, weight: f64) -> Self {
+        assert!(weight > 0.0, "weights must be positive");
+        self.weights.insert(kind, weight);
+        self
+    }
+
+    /// Limits the number of buffered packets over all kinds.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, packet: Packet) {
+        let kind = packet.kind();
+        let weight = self.weights.get(&kind).copied().unwrap_or(1.0);
+        let start = self
+            .last_finish
+            .get(&kind)
+            .copied()
+            .unwrap_or(0.0)
+            .max(self.virtual_time);
+        let finish = start + packet.bytes() as f64 / weight;
+        self.last_finish.insert(kind, finish);
+
+        self.len += 1;
+        self.bytes += packet.bytes();
+        self.queues
+            .entry(kind)
+            .or_default()
+            .push_back((finish, packet));
+    }
+}
+
+impl QueueDiscipline for WeightedFair {
+    fn enqueue(&mut self, packet: Packet, dropped: &mut Vec<Packet>) {
+        if self.len < self.limit.unwrap_or(usize::MAX) {
+            self.push(packet);
+        } else {
+            dropped.push(packet);
+        }
+    }
+
+    fn dequeue(&mut self, _: &mut Vec<Packet>) -> Option<Packet> {
+        let queue = self
+            .queues
+            .values_mut()
+            .filter(|queue| !queue.is_empty())
+            .min_by(|lhs, rhs| lhs[0].0.total_cmp(&rhs[0].0))?;
+        let (finish, packet) = queue.pop_front()?;
+
+        self.len -= 1;
+        self.bytes -= packet.bytes();
+        if self.len == 0 {
+            // Idle queues start a new busy period.
+            self.virtual_time = 0.0;
+            self.last_finish.clear();
+        } else {
+            self.virtual_time = finish;
+        }
+        Some(packet)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    fn packets(&self) -> Box<dyn Iterator<Item = &Packet> + '_> {
+        let mut packets = self.queues.values().flatten().collect::<Vec<_>>();
+        packets.sort_by(|lhs, rhs| lhs.0.total_cmp(&rhs.0));
+        Box::new(packets.into_iter().map(|(_, packet)| packet))
+    }
+
+    fn empty(&self) -> Box<dyn QueueDiscipline> {
+        Box::new(WeightedFair {
+            weights: self.weights.clone(),
+            limit: self.limit,
+            ..WeightedFair::default()
+        })
+    }
+
+    fn restore(&mut self, packet: Packet) {
+        self.push(packet);
+    }
+}
@@ -1,12 +1,12 @@
 //! Module-specific network ports.
 
-use crate::net::channel::ChannelRef;
+use crate::net::channel::{ChannelRef, MediumRef};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::sync::{Arc, Mutex, Weak};
 
 use super::module::{ModuleContext, ModuleRef, ModuleRefWeak};
-use super::ObjectPath;
+use super::{Globals, ObjectPath};
 
 /// A  reference to a gate.
 pub type GateRef = Arc<Gate>;
@@ -44,6 +44,7 @@ pub enum GateKind {
 
 struct Connections {
     connections: [Option<Connection>; 2],
+    medium: Option<MediumRef>,
 }
 
 /// A connection to a peering gate
@@ -76,10 +77,22 @@ impl Connection {
     }
 
     /// Create a connection, without checking the availability of the used gates.
+    ///
+    /// # Panics
+    ///
+    /// May panic on lock poisoning
     pub fn new_unchecked(gate: GateRef) -> Self {
+        // Enter through a free slot, so that the next hop is the
+        // remaining connection of an endpoint.
+        let endpoint_id = gate
+            .connections
+            .lock()
+            .expect("failed to get lock")
+            .free_slot()
+            .unwrap_or(1);
         Self {
             endpoint: gate,
-            endpoint_id: 1, // TODO: smarter ??
+            endpoint_id,
             channel: None,
         }
     }
@@ -131,6 +144,7 @@ impl Connections {
     fn new() -> Self {
         Self {
             connections: [None, None],
+            medium: None,
         }
     }
 
@@ -138,14 +152,8 @@ impl Connections {
         self.connections.iter().filter(|v| v.is_some()).count()
     }
 
-    fn put(&mut self, connection: Connection) {
-        for i in 0..2 {
-            if self.connections[i].is_none() {
-                self.connections[i] = Some(connection);
-                return;
-            }
-        }
-        unreachable!("Connections::put should not be called if no free slots are available")
+    fn free_slot(&self) -> Option<usize> {
+        self.connections.iter().position(Option::is_none)
     }
 }
 
@@ -274,26 +282,92 @@ impl Gate {
 
         let mut other_conns = other.connections.try_lock().expect("failed to get lock");
 
-        let conns_pos = conns.len();
-        let other_conns_pos = other_conns.len();
-        assert!(
-            conns_pos < 2 && other_conns_pos < 2,
-            "Cannot add connection, gates allready connected to multiple points"
-        );
+        let (Some(conns_pos), Some(other_conns_pos)) = (conns.free_slot(), other_conns.free_slot())
+        else {
+            panic!("Cannot add connection, gates allready connected to multiple points")
+        };
 
         let ch1 = channel.as_ref().map(|c| Arc::new(c.dup()));
         let ch2 = channel;
 
-        conns.put(Connection {
+        conns.connections[conns_pos] = Some(Connection {
             endpoint: other.clone(),
             endpoint_id: other_conns_pos,
             channel: ch1,
         });
-        other_conns.put(Connection {
+        other_conns.connections[other_conns_pos] = Some(Connection {
             endpoint: self.clone(),
             endpoint_id: conns_pos,
             channel: ch2,
         });
+
+        if let Some(globals) = Globals::try_current() {
+            globals.topology_changed();
+        }
+    }
+
+    /// Dissolves the connection between two gates, that was created
+    /// using [`Gate::connect`].
+    ///
+    /// The channels of both directions are taken down, dropping all
+    /// buffered packets, as well as messages still on the medium, once
+    /// they arrive. Returns `false` if the gates were not connected.
+    ///
+    /// # Panics
+    ///
+    /// May panic on lock poisoning
+    pub fn disconnect(self: &GateRef, other: &GateRef) -> bool {
+        let Some(forward) = self.take_connection(other) else {
+            return false;
+        };
+        let backward = other.take_connection(self);
+
+        for channel in [forward.channel, backward.and_then(|con| con.channel)]
+            .into_iter()
+            .flatten()
+        {
+            channel.set_down();
+        }
+        if let Some(globals) = Globals::try_current() {
+            globals.topology_changed();
+        }
+        true
+    }
+
+    /// Removes the connection to the given gate.
+    ///
+    /// The remaining connection keeps its slot, since connections of
+    /// peering gates refer to it by slot.
+    fn take_connection(&self, other: &GateRef) -> Option<Connection> {
+        let mut conns = self.connections.lock().expect("failed to get lock");
+        conns
+            .connections
+            .iter_mut()
+            .find(|con| {
+                con.as_ref()
+                    .is_some_and(|con| Arc::ptr_eq(&con.endpoint, other))
+            })?
+            .take()
+    }
+
+    /// The channels of the link between this gate and the next gate
+    /// on its path, in both directions.
+    pub(crate) fn link_channels(self: &GateRef) -> Vec<ChannelRef> {
+        let Some(con) = self.path_iter().and_then(|mut iter| iter.next()) else {
+            return Vec::new();
+        };
+        let backward = con
+            .endpoint
+            .connections
+            .lock()
+            .expect("failed to get lock")
+            .connections
+            .iter()
+            .flatten()
+            .find(|back| Arc::ptr_eq(&back.endpoint, self))
+            .and_then(|back| back.channel.clone());
+
+        con.channel.into_iter().chain(backward).collect()
     }
 
     /// Retrives the channel of the first connection on the path.
@@ -301,6 +375,31 @@ impl Gate {
         self.path_iter()?.nth(0).and_then(|con| con.channel)
     }
 
+    /// Retrieves the broadcast medium this gate is attached to.
+    ///
+    /// See [`Medium`](crate::net::channel::Medium) for more information.
+    ///
+    /// # Panics
+    ///
+    /// May panic on lock poisoning
+    #[must_use]
+    pub fn medium(&self) -> Option<MediumRef> {
+        self.connections
+            .lock()
+            .expect("failed to get lock")
+            .medium
+            .clone()
+    }
+
+    pub(crate) fn set_medium(&self, medium: MediumRef) {
+        let mut conns = self.connections.lock().expect("failed to get lock");
+        assert!(
+            conns.medium.is_none(),
+            "Cannot attach gate to multiple media"
+        );
+        conns.medium = Some(medium);
+    }
+
     /// Returns an iterator over the connections on a gate path.
     /// If the current gate is a transit gate, no iterator will be returned,
     /// since the direction of the iterator cannot be determined.
@@ -361,10 +460,21 @@ impl Gate {
         this
     }
 
+    /// Returns the connection stored in the given slot of the gate.
+    pub(crate) fn connection_slot(&self, slot: usize) -> Option<Connection> {
+        self.connections
+            .lock()
+            .expect("failed to get lock")
+            .connections
+            .get(slot)?
+            .clone()
+    }
+
     pub(crate) fn dissolve_paths(&self) {
         let Ok(mut conns) = self.connections.try_lock() else {
             return;
         };
+        conns.medium = None;
         for con in &mut conns.connections {
             if let Some(con) = con.take() {
                 con.endpoint.dissolve_paths();
@@ -517,6 +627,31 @@ mod tests {
         assert_eq!(gate.kind(), GateKind::Endpoint);
     }
 
+    #[test]
+    fn disconnect() {
+        let owner = ModuleContext::standalone("root".into());
+        let gate_a = owner.create_raw_gate("port-a", 1, 0);
+        let gate_b = owner.create_raw_gate("port-b", 1, 0);
+        let gate_c = owner.create_raw_gate("port-c", 1, 0);
+
+        // Chain should be a -- b -- c
+        gate_a.clone().connect(gate_b.clone(), None);
+        gate_b.clone().connect(gate_c.clone(), None);
+        assert_eq!(gate_b.kind(), GateKind::Transit);
+
+        assert!(gate_a.disconnect(&gate_b));
+        assert!(!gate_a.disconnect(&gate_b));
+        assert_eq!(gate_a.kind(), GateKind::Standalone);
+        assert_eq!(gate_b.kind(), GateKind::Endpoint);
+        assert_eq!(gate_b.path_end(), Some(gate_c.clone()));
+        assert_eq!(gate_c.path_end(), Some(gate_b.clone()));
+
+        // Chain should be b -- c -- a
+        gate_c.clone().connect(gate_a.clone(), None);
+        assert_eq!(gate_b.path_end(), Some(gate_a.clone()));
+        assert_eq!(gate_a.path_end(), Some(gate_b));
+    }
+
     #[test]
     fn into_gate() {
         let ctx = ModuleContext::standalone("root".into());
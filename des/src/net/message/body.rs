@@ -78,6 +78,11 @@ impl Body {
         self.length
     }
 
+    /// The name of the inner type stored in the message body.
+    pub(crate) fn type_name(&self) -> &'static str {
+        unsafe { (self.vtable.type_name)() }
+    }
+
     /// Tests which inner type is stored in the message body.
     ///
     /// See also `Any::is`.
@@ -39,6 +39,8 @@ pub struct Header {
 
     pub src: [u8; 6],
     pub dst: [u8; 6],
+
+    pub corrupted: bool, // Checksum
 }
 
 impl Clone for Header {
@@ -55,6 +57,8 @@ impl Clone for Header {
 
             src: self.src,
             dst: self.dst,
+
+            corrupted: self.corrupted,
         }
     }
 }
@@ -73,6 +77,8 @@ impl Default for Header {
 
             src: [0; 6],
             dst: [0; 6],
+
+            corrupted: false,
         }
     }
 }
@@ -155,6 +155,12 @@ impl Message {
         self.header.dst = dest;
         self
     }
+
+    /// **Builder** that marks the message as corrupted.
+    pub fn corrupted(mut self, corrupted: bool) -> Self {
+        self.header.corrupted = corrupted;
+        self
+    }
 }
 
 // # Content Accessing
@@ -15,7 +15,6 @@ pub mod topology;
 
 pub(crate) use self::runtime::HandleMessageEvent;
 pub(crate) use self::runtime::MessageExitingConnection;
-pub(crate) use self::runtime::NetEvents;
 
 pub use self::path::*;
 pub use self::runtime::*;
@@ -1,5 +1,6 @@
 use super::{DummyModule, ModuleId, ModuleRef, ModuleRefWeak, ModuleReferencingError};
 use crate::{
+    net::PanicError,
     prelude::{GateRef, ObjectPath},
     sync::SwapLock,
     time::SimTime,
@@ -19,10 +20,12 @@ use std::{
     time::Duration,
 };
 
-pub(crate) static MOD_CTX: SwapLock<Option<Arc<ModuleContext>>> = SwapLock::new(None);
+thread_local! {
+    pub(crate) static MOD_CTX: SwapLock<Option<Arc<ModuleContext>>> = const { SwapLock::new(None) };
+}
 
 pub(crate) fn module_ctx_drop() {
-    MOD_CTX.swap(&mut None);
+    MOD_CTX.with(|ctx| ctx.swap(&mut None));
 }
 
 cfg_async! {
@@ -33,7 +36,7 @@ cfg_async! {
 mod spawner;
 mod stereotyp;
 
-pub use stereotyp::Stereotyp;
+pub use stereotyp::{RestartOutcome, RestartPolicy, RestartRecord, Stereotyp};
 
 /// The topological components of a module, not including the attached
 /// software.
@@ -67,6 +70,8 @@ pub struct ModuleContext {
     // RUNTIME VALUES
     #[allow(clippy::option_option)]
     pub(crate) shutdown_task: RwLock<Option<Option<SimTime>>>,
+    pub(crate) restarts: RwLock<Vec<RestartRecord>>,
+    pub(crate) failure: RwLock<Option<PanicError>>,
 }
 
 impl ModuleContext {
@@ -99,6 +104,8 @@ impl ModuleContext {
             children: RwLock::new(FxHashMap::with_hasher(FxBuildHasher::default())),
 
             shutdown_task: RwLock::default(),
+            restarts: RwLock::default(),
+            failure: RwLock::default(),
         }))
     }
 
@@ -133,6 +140,8 @@ impl ModuleContext {
             children: RwLock::new(FxHashMap::with_hasher(FxBuildHasher::default())),
 
             shutdown_task: RwLock::default(),
+            restarts: RwLock::default(),
+            failure: RwLock::default(),
         }));
 
         parent
@@ -146,13 +155,13 @@ impl ModuleContext {
 
     pub(crate) fn place(self: Arc<Self>) -> Option<Arc<ModuleContext>> {
         let mut this = Some(self);
-        MOD_CTX.swap(&mut this);
+        MOD_CTX.with(|ctx| ctx.swap(&mut this));
         this
     }
 
     pub(crate) fn take() -> Option<Arc<ModuleContext>> {
         let mut this = None;
-        MOD_CTX.swap(&mut this);
+        MOD_CTX.with(|ctx| ctx.swap(&mut this));
         this
     }
 
@@ -241,7 +250,30 @@ impl ModuleContext {
         *self.shutdown_task.write() = Some(Some(restart_at));
     }
 
-    /// TODO
+    /// Returns a spawner, that creates and terminates children of this
+    /// module at runtime.
+    ///
+    /// > *This function requires a node-context within the simulation*
+    ///
+    /// ```
+    /// # use des::prelude::*;
+    /// # use des::net::processing::ProcessingStack;
+    /// struct Worker;
+    /// impl Module for Worker {}
+    ///
+    /// struct Master;
+    /// impl Module for Master {
+    ///     fn at_sim_start(&mut self, _: usize) {
+    ///         let worker = current()
+    ///             .spawner()
+    ///             .child("worker", Worker, ProcessingStack::default());
+    ///         assert_eq!(worker.path().as_str(), "master.worker");
+    ///     }
+    /// }
+    /// # let mut sim = Sim::new(());
+    /// # sim.node("master", Master);
+    /// # let _ = Builder::new().build(sim.freeze()).run();
+    /// ```
     pub fn spawner(&self) -> Spawner<'_> {
         Spawner { ctx: self }
     }
@@ -393,6 +425,15 @@ impl ModuleContext {
         self.stereotyp.set(new);
     }
 
+    /// Returns the restart history of this module, in chronological order.
+    ///
+    /// Each restart request is recorded together with the decision of the
+    /// [`RestartPolicy`]. The history remains available after the simulation
+    /// has finished.
+    pub fn restart_history(&self) -> Vec<RestartRecord> {
+        self.restarts.read().clone()
+    }
+
     /// Returns a reference to a parent module
     ///
     /// Use this handle to either access the parent modules topological
@@ -487,22 +528,26 @@ impl Drop for ModuleContext {
 }
 
 pub(crate) fn with_mod_ctx<R>(f: impl FnOnce(&Arc<ModuleContext>) -> R) -> R {
-    let lock = MOD_CTX.read();
-    let ctx = lock
-        .as_ref()
-        .expect("failed operation: no module currently in scope");
-    let r = f(ctx);
-    drop(lock);
-    r
+    MOD_CTX.with(|lock| {
+        let lock = lock.read();
+        let ctx = lock
+            .as_ref()
+            .expect("failed operation: no module currently in scope");
+        let r = f(ctx);
+        drop(lock);
+        r
+    })
 }
 
 pub(crate) fn try_with_mod_ctx<R>(f: impl FnOnce(&Arc<ModuleContext>) -> R) -> Option<R> {
-    let lock = MOD_CTX.read();
-    if let Some(ctx) = lock.as_real_inner() {
-        let r = f(ctx);
-        drop(lock);
-        Some(r)
-    } else {
-        None
-    }
+    MOD_CTX.with(|lock| {
+        let lock = lock.read();
+        if let Some(ctx) = lock.as_real_inner() {
+            let r = f(ctx);
+            drop(lock);
+            Some(r)
+        } else {
+            None
+        }
+    })
 }
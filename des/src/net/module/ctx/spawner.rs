@@ -1,18 +1,32 @@
 use super::super::ModuleExt;
 use crate::{
-    net::processing::ProcessingStack,
+    net::{buf_spawn, buf_terminate, processing::ProcessingStack, Globals},
     prelude::{Gate, Module, ModuleRef},
 };
 
-use super::ModuleContext;
+use super::{ModuleContext, MOD_CTX};
 
-/// A spawner.
+/// A spawner, that creates and destroys modules at runtime.
+///
+/// Spawned modules are children of the module the spawner was created
+/// from. They are full members of the simulation: they are visible
+/// through [`Globals`] and the [`Topology`](crate::net::topology::Topology),
+/// can be connected to other modules using gates, and receive the same
+/// lifecycle calls as modules created before the simulation started.
+///
+/// Lifecycle calls are deferred until the current event completes, so
+/// gates can be created and connected, before a spawned module is started.
 #[derive(Debug)]
 pub struct Spawner<'a> {
     pub(super) ctx: &'a ModuleContext,
 }
 
 impl Spawner<'_> {
+    /// Creates a cluster of gates on the spawning module.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the spawning module was already dropped.
     pub fn gate(&self, name: impl AsRef<str>, size: usize) {
         let mut gates = Vec::new();
 
@@ -25,36 +39,69 @@ impl Spawner<'_> {
         self.ctx.gates.write().extend(gates);
     }
 
+    /// Creates a new child module with the given name.
+    ///
+    /// The child is added to the module tree immediately, so it can be
+    /// referred to and connected to other modules using the returned
+    /// handle. All stages of [`Module::at_sim_start`] are executed once
+    /// the current event completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the spawning module already has a child with the given
+    /// name, or if not called from a simulation context.
     pub fn child<T: Module>(
         &self,
         name: impl AsRef<str>,
         module: T,
         stack: ProcessingStack,
     ) -> ModuleRef {
+        let name = name.as_ref();
+        assert!(
+            !self.ctx.children.read().contains_key(name),
+            "cannot spawn child '{name}' of '{}', since such a child already exists",
+            self.ctx.path
+        );
+
         let sref = self.ctx.me.read().as_ref().unwrap().upgrade().unwrap();
-        let ctx = ModuleContext::child_of(name.as_ref(), sref);
+        let child = ModuleContext::child_of(name, sref);
 
-        ctx.activate();
+        // The processing chain is created within the context of the child,
+        // while the spawning module remains active afterwards.
+        let mut prev = Some(child.ctx.clone());
+        MOD_CTX.with(|ctx| ctx.swap(&mut prev));
         let pe = module.to_processing_chain(stack);
-        ctx.upgrade_dummy(pe);
-        ctx.deactivate(&mut vec![]);
+        MOD_CTX.with(|ctx| ctx.swap(&mut prev));
+        child.upgrade_dummy(pe);
 
-        // TODO: start()
+        Globals::current()
+            .modules
+            .lock()
+            .expect("failed")
+            .add(child.clone());
+        buf_spawn(child.clone());
 
-        self.ctx
-            .children
-            .write()
-            .insert(name.as_ref().to_string(), ctx.clone());
-
-        ctx
+        child
     }
 
+    /// Terminates a child module, that was created by the spawning module.
+    ///
+    /// Once the current event completes, the child and all its descendants
+    /// are shut down using [`Module::at_sim_end`], and removed from the
+    /// module tree. All their gates are disconnected, so messages buffered
+    /// on or travelling along the attached channels are dropped. Pending
+    /// events of the terminated modules are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no child with the given name exists.
     pub fn terminate(&self, name: &str) {
         let Some(handle) = self.ctx.children.write().remove(name) else {
-            panic!("")
+            panic!(
+                "cannot terminate child '{name}' of '{}', since no such child exists",
+                self.ctx.path
+            )
         };
-
-        // TODO: must prevent message sending
-        let _ = handle.at_sim_end();
+        buf_terminate(handle);
     }
 }
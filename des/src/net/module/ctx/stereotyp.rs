@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use crate::time::SimTime;
+
 /// A stereotyp that defines a nodes behaviour on startup, shutdown or panic.
 ///
 /// The lifecycle of a node is defined as follows:
@@ -11,16 +15,33 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct Stereotyp {
-    /// TODO
+    /// Whether a panic is caught, shutting down only the module. Otherwise
+    /// the panic fails the simulation.
     pub on_panic_catch: bool,
-    /// TODO
+    /// Whether the module is dropped after a caught panic.
+    ///
+    /// A caught panic always shuts down the module, which then stays shut down
+    /// unless its supervisor restarts it, see
+    /// [`Supervision`](crate::net::module::Supervision). The supervisor is only
+    /// involved if `on_panic_inform_parent` is set, otherwise the module is
+    /// dropped independent of this flag.
     pub on_panic_drop: bool,
-    /// TODO
+    /// Whether the module is meant to be restarted after a panic.
+    ///
+    /// The runtime does not restart modules on its own. Restarts after a caught
+    /// panic are decided by the supervisor, and are subject to the
+    /// [`RestartPolicy`] of the module. If the policy gives up, the supervisor
+    /// gives up as well, and the failure is escalated regardless of this flag.
     pub on_panic_restart: bool,
-    /// TODO
+    /// Whether the children are shut down as well, when the module gives up
+    /// supervising them.
     pub on_panic_drop_submodules: bool,
-    /// TODO
+    /// Whether a caught panic is reported to the parent, see
+    /// [`Module::handle_child_failure`](crate::net::module::Module::handle_child_failure).
     pub on_panic_inform_parent: bool,
+    /// The policy applied, when the module requests a restart, e.g. using
+    /// [`shutdow_and_restart_in`](super::ModuleContext::shutdow_and_restart_in).
+    pub restart_policy: RestartPolicy,
 }
 
 impl Stereotyp {
@@ -32,6 +53,7 @@ impl Stereotyp {
 
         on_panic_drop_submodules: true,
         on_panic_inform_parent: false,
+        restart_policy: RestartPolicy::UNLIMITED,
     };
 
     /// TODO
@@ -42,6 +64,7 @@ impl Stereotyp {
 
         on_panic_drop_submodules: true,
         on_panic_inform_parent: true,
+        restart_policy: RestartPolicy::UNLIMITED,
     };
 }
 
@@ -50,3 +73,124 @@ impl Default for Stereotyp {
         Self::HOST
     }
 }
+
+/// A policy that limits and delays the restarts of a module, set using
+/// [`Stereotyp::restart_policy`].
+///
+/// Each restart request is checked against the restart history of the module.
+/// If the module was already restarted [`max_restarts`](Self::max_restarts) times
+/// within the last [`window`](Self::window), the policy gives up and the module
+/// stays shut down. Otherwise the restart is delayed by an exponential backoff,
+/// starting at [`backoff`](Self::backoff) and multiplied by
+/// [`backoff_factor`](Self::backoff_factor) for each restart within the window,
+/// up to [`max_backoff`](Self::max_backoff). Restarts are never scheduled earlier
+/// than requested.
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::net::module::{RestartPolicy, Stereotyp};
+/// let stereotyp = Stereotyp {
+///     restart_policy: RestartPolicy::UNLIMITED
+///         .with_limit(3, Duration::from_secs(60))
+///         .with_backoff(Duration::from_secs(1), 2, Duration::from_secs(10)),
+///     ..Stereotyp::HOST
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// The maximum number of restarts within the window. `None` allows
+    /// an unlimited number of restarts.
+    pub max_restarts: Option<usize>,
+    /// The duration of the sliding window, in which restarts are counted.
+    /// `None` counts all restarts of the simulation.
+    pub window: Option<Duration>,
+    /// The delay of the first restart within the window.
+    pub backoff: Duration,
+    /// The factor, by which the delay grows with each restart within the window.
+    pub backoff_factor: u32,
+    /// The upper bound of the delay.
+    pub max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    /// A policy that allows an unlimited number of restarts, without any delay.
+    pub const UNLIMITED: RestartPolicy = RestartPolicy {
+        max_restarts: None,
+        window: None,
+        backoff: Duration::ZERO,
+        backoff_factor: 1,
+        max_backoff: Duration::MAX,
+    };
+
+    /// Limits the number of restarts within a sliding window.
+    #[must_use]
+    pub const fn with_limit(mut self, max_restarts: usize, window: Duration) -> Self {
+        self.max_restarts = Some(max_restarts);
+        self.window = Some(window);
+        self
+    }
+
+    /// Delays restarts using an exponential backoff.
+    #[must_use]
+    pub const fn with_backoff(mut self, backoff: Duration, factor: u32, max: Duration) -> Self {
+        self.backoff = backoff;
+        self.backoff_factor = factor;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Decides when a restart, requested at `now` for `requested`, takes place.
+    /// Returns `None` if the policy gives up.
+    pub(crate) fn schedule(
+        &self,
+        history: &[RestartRecord],
+        now: SimTime,
+        requested: SimTime,
+    ) -> Option<SimTime> {
+        let recent = history
+            .iter()
+            .filter(|record| matches!(record.outcome, RestartOutcome::Scheduled(_)))
+            .filter(|record| {
+                self.window
+                    .is_none_or(|window| now.saturating_duration_since(record.time) < window)
+            })
+            .count();
+        if self.max_restarts.is_some_and(|max| recent >= max) {
+            return None;
+        }
+
+        let mut backoff = self.backoff.min(self.max_backoff);
+        for _ in 0..recent {
+            backoff = backoff
+                .saturating_mul(self.backoff_factor)
+                .min(self.max_backoff);
+        }
+        Some(requested.max(now + backoff))
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// An entry in the restart history of a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartRecord {
+    /// The time the module was shut down, to be restarted.
+    pub time: SimTime,
+    /// The decision of the [`RestartPolicy`].
+    pub outcome: RestartOutcome,
+}
+
+/// The decision of a [`RestartPolicy`] on a restart request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartOutcome {
+    /// The restart was scheduled at the given time.
+    Scheduled(SimTime),
+    /// The policy gave up, so the module stays shut down.
+    GaveUp,
+}
@@ -66,7 +66,11 @@
 //! Using the [`join`](ModuleContext::join) function, you can schedule a tokio task to be joined once the simulation ends.
 //! If that is not possible, an error will be returned from the simulation run.
 
-use crate::{net::message::Message, prelude::RuntimeError};
+use crate::{
+    net::{message::Message, PanicError},
+    prelude::RuntimeError,
+};
+use serde_yml::Value;
 use std::{
     any::Any,
     fmt,
@@ -83,7 +87,7 @@ mod refs;
 mod tests;
 
 pub(crate) use self::ctx::*;
-pub use self::ctx::{ModuleContext, Stereotyp};
+pub use self::ctx::{ModuleContext, RestartOutcome, RestartPolicy, RestartRecord, Stereotyp};
 pub use api::*;
 pub(crate) use dummy::*;
 pub use error::*;
@@ -215,6 +219,99 @@ pub trait Module: Any {
     fn at_sim_end(&mut self) -> Result<(), RuntimeError> {
         Ok(())
     }
+
+    ///
+    /// Captures the custom state of the module, when a snapshot of the
+    /// simulation is created using [`Runtime::checkpoint`](crate::runtime::Runtime::checkpoint).
+    ///
+    /// Modules that return `None` (the default) will be restored in the
+    /// state they were constructed with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use des::prelude::*;
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Counter {
+    ///     count: usize,
+    /// }
+    ///
+    /// impl Module for Counter {
+    ///     fn handle_message(&mut self, _msg: Message) {
+    ///         self.count += 1;
+    ///     }
+    ///
+    ///     fn checkpoint(&self) -> Option<serde_yml::Value> {
+    ///         serde_yml::to_value(self).ok()
+    ///     }
+    ///
+    ///     fn restore_checkpoint(&mut self, state: serde_yml::Value) {
+    ///         *self = serde_yml::from_value(state).expect("invalid snapshot");
+    ///     }
+    /// }
+    /// ```
+    fn checkpoint(&self) -> Option<Value> {
+        None
+    }
+
+    ///
+    /// Restores the custom state of the module from a value created
+    /// by [`Module::checkpoint`].
+    ///
+    fn restore_checkpoint(&mut self, _state: Value) {}
+
+    ///
+    /// A supervision hook, invoked when a child module failed.
+    ///
+    /// A child fails, if it panics while its [`Stereotyp`] has both `on_panic_catch`
+    /// and `on_panic_inform_parent` set, or if it gave up supervising its own children.
+    /// In the latter case, `err` is the panic of the descendant that caused the
+    /// escalation. The returned [`Supervision`] decides which children are restarted.
+    ///
+    /// The default implementation ignores the failure, leaving the child shut down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use des::prelude::*;
+    /// use des::net::{module::Supervision, PanicError};
+    ///
+    /// struct Supervisor;
+    ///
+    /// impl Module for Supervisor {
+    ///     fn handle_child_failure(&mut self, child: ModuleRef, err: &PanicError) -> Supervision {
+    ///         println!("child '{}' failed: {err}", child.path());
+    ///         Supervision::OneForOne
+    ///     }
+    /// }
+    /// ```
+    fn handle_child_failure(&mut self, _child: ModuleRef, _err: &PanicError) -> Supervision {
+        Supervision::Ignore
+    }
+}
+
+/// The decision of a supervisor on the failure of a child, returned by
+/// [`Module::handle_child_failure`].
+///
+/// All restarts are subject to the [`RestartPolicy`] of the restarted modules.
+/// Should any policy give up, the supervisor gives up as well.
+/// A supervisor that gives up is shut down, together with its descendants if
+/// `on_panic_drop_submodules` is set, and the failure is escalated to its own
+/// supervisor. Failures escalated beyond the root of the module tree fail the
+/// simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Supervision {
+    /// The failure is ignored, so the failed child stays shut down.
+    Ignore,
+    /// Only the failed child is restarted.
+    OneForOne,
+    /// All children are restarted.
+    OneForAll,
+    /// The failed child and all children created after it are restarted.
+    RestForOne,
+    /// The supervisor gives up, escalating the failure.
+    Escalate,
 }
 
 pub(crate) trait ModuleExt: Module {
@@ -46,10 +46,12 @@
 
 use crate::{
     net::{
-        self, channel::ChannelDropBehaviour, module::ModuleContext, Sim, SimBuilder,
-        SimBuilderScoped,
+        self,
+        channel::{ChannelDropBehaviour, Medium},
+        module::ModuleContext,
+        Sim, SimBuilder, SimBuilderScoped,
     },
-    prelude::{Channel, ChannelMetrics, ModuleRef, ObjectPath},
+    prelude::{Channel, ChannelMetrics, DelayModel, LossModel, ModuleRef, ObjectPath},
     time::Duration,
 };
 use des_net_utils::ndl::{
@@ -217,7 +219,7 @@ impl<A> SimBuilder<A> {
         Ok(())
     }
 
-    fn raw_ndl<L: Layer>(
+    pub(crate) fn raw_ndl<L: Layer>(
         &mut self,
         path: &ObjectPath,
         ty: &str,
@@ -299,13 +301,41 @@ impl<A> SimBuilderScoped<'_, A> {
 
             from.connect(
                 to,
-                connection
-                    .link
-                    .as_ref()
-                    .map(|link| Channel::new(ChannelMetrics::from(link))),
+                connection.link.as_ref().map(|link| {
+                    let channel = Channel::new(ChannelMetrics::from(link));
+                    channel.set_loss_model(LossModel::from(link));
+                    channel.set_delay_model(DelayModel::from(link));
+                    channel.set_fifo(
+                        link.other
+                            .get("fifo")
+                            .is_some_and(|v| v.parse().expect("bool")),
+                    );
+                    channel
+                }),
             );
         }
 
+        for medium in &node.media {
+            let metrics = medium.link.as_ref().map_or_else(
+                || {
+                    ChannelMetrics::new(
+                        0,
+                        Duration::ZERO,
+                        Duration::ZERO,
+                        ChannelDropBehaviour::Drop,
+                    )
+                },
+                ChannelMetrics::from,
+            );
+            let medium_ref = Medium::new(metrics);
+            if let Some(link) = &medium.link {
+                medium_ref.set_delay_model(DelayModel::from(link));
+            }
+            for peer in &medium.peers {
+                medium_ref.attach(access_gate(&ctx.ctx, &peer.accessors).expect("gate"));
+            }
+        }
+
         Ok(ctx)
     }
 }
@@ -342,3 +372,53 @@ impl From<&tree::Link> for ChannelMetrics {
         }
     }
 }
+
+/// Delay models are defined by the additional link field `delay-model`,
+/// one of `uniform`, `normal`, `exponential` or `pareto`. Pareto delays
+/// use the shape given by `pareto-shape`, defaulting to `2`.
+impl From<&tree::Link> for DelayModel {
+    fn from(value: &tree::Link) -> Self {
+        match value.other.get("delay-model").map(String::as_str) {
+            None | Some("uniform") => DelayModel::Uniform,
+            Some("normal") => DelayModel::Normal,
+            Some("exponential") => DelayModel::Exponential,
+            Some("pareto") => DelayModel::Pareto(
+                value
+                    .other
+                    .get("pareto-shape")
+                    .map_or(2.0, |v| v.parse().expect("number")),
+            ),
+            Some(other) => panic!("unknown delay model '{other}'"),
+        }
+    }
+}
+
+/// Loss models are defined by additional link fields:
+/// `gilbert-p` and `gilbert-r` (with the optional `gilbert-loss-good` and
+/// `gilbert-loss-bad`) for a Gilbert-Elliott model, `ber` for bit errors
+/// and `loss` for a Bernoulli model, in this order of precedence.
+impl From<&tree::Link> for LossModel {
+    fn from(value: &tree::Link) -> Self {
+        let field = |key: &str| {
+            value
+                .other
+                .get(key)
+                .map(|v| v.parse::<f64>().expect("number"))
+        };
+
+        if let (Some(p), Some(r)) = (field("gilbert-p"), field("gilbert-r")) {
+            LossModel::GilbertElliott {
+                p,
+                r,
+                loss_good: field("gilbert-loss-good").unwrap_or(0.0),
+                loss_bad: field("gilbert-loss-bad").unwrap_or(1.0),
+            }
+        } else if let Some(ber) = field("ber") {
+            LossModel::BitError(ber)
+        } else if let Some(p) = field("loss") {
+            LossModel::Bernoulli(p)
+        } else {
+            LossModel::None
+        }
+    }
+}
@@ -153,6 +153,11 @@ impl<L: Layer> Registry<L> {
         self.layer.resolve(path, symbol, stack)
     }
 
+    /// Indicates whether the registry provides software for `symbol`.
+    pub(crate) fn contains(&self, symbol: &str) -> bool {
+        self.layer.contains(symbol)
+    }
+
     /// Adds a symbol mapping to the registry.
     ///
     /// All nodes with the symbol `ty` will now use the provided genertator
@@ -387,6 +392,10 @@ pub trait Layer {
     ) -> Option<Processor> {
         None
     }
+    #[doc(hidden)]
+    fn contains(&self, _symbol: &str) -> bool {
+        false
+    }
 }
 
 impl Layer for EmptyLayer {}
@@ -415,6 +424,9 @@ where
             }
         })
     }
+    fn contains(&self, symbol: &str) -> bool {
+        self.inner.contains(symbol) || symbol == self.ty
+    }
 }
 
 impl<L, F, M> Layer for FallbackLayer<L, F, M>
@@ -436,6 +448,9 @@ where
                 .unwrap_or_else(|| (self.f)().to_processing_chain(stack())),
         )
     }
+    fn contains(&self, _symbol: &str) -> bool {
+        true
+    }
 }
 
 #[doc(hidden)]
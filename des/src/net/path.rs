@@ -1,4 +1,5 @@
 use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::sync::Arc;
 
 ///
@@ -204,6 +205,24 @@ impl Default for ObjectPath {
     }
 }
 
+impl Serialize for ObjectPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectPath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(ObjectPath::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,210 @@
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use super::{config::Configured, ProcessingElement};
+use crate::net::{
+    gate::GateRef,
+    message::{Message, MessageId, MessageKind},
+    module::current,
+    ObjectPath,
+};
+use crate::time::SimTime;
+
+/// The direction of a message, relative to the module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The message arrived at the module.
+    Incoming,
+    /// The message was sent by the module.
+    Outgoing,
+}
+
+/// The configuration of a [`PacketCapture`].
+///
+/// By default, all messages are captured in both directions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct CaptureConfig {
+    /// Whether incoming messages are captured.
+    pub incoming: bool,
+    /// Whether outgoing messages are captured.
+    pub outgoing: bool,
+    /// The maximum number of records captured by this module.
+    pub limit: Option<usize>,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            incoming: true,
+            outgoing: true,
+            limit: None,
+        }
+    }
+}
+
+/// The metadata of a captured message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureRecord {
+    /// The simulation time the message was captured.
+    pub time: SimTime,
+    /// The module that captured the message.
+    pub module: ObjectPath,
+    /// The direction of the message.
+    pub direction: Direction,
+    /// The gate the message arrived on, or was sent on.
+    pub gate: Option<ObjectPath>,
+    /// The id of the message.
+    pub id: MessageId,
+    /// The kind of the message.
+    pub kind: MessageKind,
+    /// The length of the message in bytes.
+    pub length: usize,
+    /// The source address of the message.
+    pub src: [u8; 6],
+    /// The destination address of the message.
+    pub dst: [u8; 6],
+}
+
+/// A processing element, that records the metadata of all messages
+/// passing through it.
+///
+/// The capture is a handle to shared state, so clones can be installed
+/// on multiple modules, while the original is used to read the records.
+/// The configuration is read from the prop `capture`, see [`CaptureConfig`].
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::net::processing::PacketCapture;
+/// let capture = PacketCapture::new();
+///
+/// let mut sim = Sim::new(());
+/// let handle = capture.clone();
+/// sim.set_stack(move || handle.clone());
+/// // ... create nodes and run the simulation
+/// for record in capture.records() {
+///     println!("{:?} {} {:?}", record.time, record.module, record.direction);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PacketCapture {
+    records: Arc<Mutex<Vec<CaptureRecord>>>,
+    config: Configured<CaptureConfig>,
+    captured: usize,
+}
+
+impl PacketCapture {
+    /// Creates a new empty capture.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            records: Arc::default(),
+            config: Configured::new("capture"),
+            captured: 0,
+        }
+    }
+
+    /// Reads the configuration from the prop `key`, instead of `capture`.
+    #[must_use]
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.config.set_key(key);
+        self
+    }
+
+    /// Uses the given configuration, instead of reading it from the props.
+    #[must_use]
+    pub fn with_config(mut self, config: CaptureConfig) -> Self {
+        self.config.set(config);
+        self
+    }
+
+    /// The records captured so far, in the order of capture.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capture was poisoned.
+    #[must_use]
+    pub fn records(&self) -> Vec<CaptureRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    /// The number of records captured so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capture was poisoned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    /// Indicates whether no records were captured.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capture was poisoned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.lock().unwrap().is_empty()
+    }
+
+    /// Removes all records captured so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capture was poisoned.
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+
+    fn capture(&mut self, msg: &Message, direction: Direction, gate: Option<&GateRef>) {
+        let config = self.config.get();
+        let enabled = match direction {
+            Direction::Incoming => config.incoming,
+            Direction::Outgoing => config.outgoing,
+        };
+        if !enabled || config.limit.is_some_and(|limit| self.captured >= limit) {
+            return;
+        }
+        self.captured += 1;
+
+        let header = msg.header();
+        self.records.lock().unwrap().push(CaptureRecord {
+            time: SimTime::now(),
+            module: current().path(),
+            direction,
+            gate: gate.map(|gate| gate.path()),
+            id: header.id,
+            kind: header.kind,
+            length: msg.length(),
+            src: header.src,
+            dst: header.dst,
+        });
+    }
+}
+
+impl Default for PacketCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessingElement for PacketCapture {
+    fn event_start(&mut self) {
+        self.config.get();
+    }
+
+    fn incoming(&mut self, msg: Message) -> Option<Message> {
+        let gate = msg.header().last_gate.clone();
+        self.capture(&msg, Direction::Incoming, gate.as_ref());
+        Some(msg)
+    }
+
+    fn outgoing(&mut self, msg: Message, gate: &GateRef) -> Option<Message> {
+        self.capture(&msg, Direction::Outgoing, Some(gate));
+        Some(msg)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::net::module::{current, PropType};
+
+/// The configuration of a processing element, loaded from the props
+/// of the current module on first use.
+#[derive(Debug, Clone)]
+pub(super) struct Configured<T> {
+    key: String,
+    value: Option<T>,
+    valid: Option<bool>,
+}
+
+impl<T: PropType + Clone + Default> Configured<T> {
+    pub(super) fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            value: None,
+            valid: None,
+        }
+    }
+
+    pub(super) fn set_key(&mut self, key: &str) {
+        self.key = key.to_string();
+    }
+
+    pub(super) fn set(&mut self, value: T) {
+        self.value = Some(value);
+        self.valid = None;
+    }
+
+    /// Returns the configuration, reading the prop under the configured
+    /// key if not yet loaded. Missing props fall back to the default configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prop exists, but does not contain a valid configuration.
+    pub(super) fn get(&mut self) -> &T {
+        let key = &self.key;
+        self.value.get_or_insert_with(|| {
+            current()
+                .prop::<T>(key)
+                .unwrap_or_else(|e| panic!("invalid configuration in prop '{key}': {e}"))
+                .get()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the configuration like [`Configured::get`], or `None` if it is
+    /// rejected by `validate`. Invalid configurations are reported once, when loaded.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(super) fn get_valid(&mut self, validate: fn(&T) -> Result<(), String>) -> Option<&T> {
+        self.get();
+        let value = self.value.as_ref()?;
+        let valid = *self.valid.get_or_insert_with(|| match validate(value) {
+            Ok(()) => true,
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!("invalid configuration '{}': {}", self.key, e);
+                false
+            }
+        });
+        valid.then_some(value)
+    }
+}
@@ -0,0 +1,237 @@
+use std::sync::{Arc, Mutex};
+
+use fxhash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::{config::Configured, ProcessingElement};
+use crate::net::{
+    gate::GateRef,
+    message::{Message, MessageKind},
+};
+
+/// The directions a processing element applies to.
+///
+/// By default, both directions are enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct DirectionConfig {
+    /// Whether incoming messages are processed.
+    pub incoming: bool,
+    /// Whether outgoing messages are processed.
+    pub outgoing: bool,
+}
+
+impl Default for DirectionConfig {
+    fn default() -> Self {
+        Self {
+            incoming: true,
+            outgoing: true,
+        }
+    }
+}
+
+/// A processing element, that drops all messages not matching a predicate.
+///
+/// The directions the filter applies to are read from the
+/// prop `filter`, see [`DirectionConfig`].
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::net::processing::Filter;
+/// let mut sim = Sim::new(());
+/// // drop all messages of kind 42
+/// sim.set_stack(|| Filter::new(|msg| msg.header().kind != 42));
+/// ```
+pub struct Filter {
+    predicate: Box<dyn FnMut(&Message) -> bool>,
+    config: Configured<DirectionConfig>,
+}
+
+impl Filter {
+    /// Creates a new filter, that only passes messages matching the predicate.
+    #[must_use]
+    pub fn new(predicate: impl FnMut(&Message) -> bool + 'static) -> Self {
+        Self {
+            predicate: Box::new(predicate),
+            config: Configured::new("filter"),
+        }
+    }
+
+    /// Reads the configuration from the prop `key`, instead of `filter`.
+    #[must_use]
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.config.set_key(key);
+        self
+    }
+
+    /// Uses the given configuration, instead of reading it from the props.
+    #[must_use]
+    pub fn with_config(mut self, config: DirectionConfig) -> Self {
+        self.config.set(config);
+        self
+    }
+
+    fn filter(&mut self, msg: Message, enabled: bool) -> Option<Message> {
+        if !enabled || (self.predicate)(&msg) {
+            Some(msg)
+        } else {
+            #[cfg(feature = "tracing")]
+            tracing::trace!("Filtering message [{}]", msg);
+            None
+        }
+    }
+}
+
+impl ProcessingElement for Filter {
+    fn event_start(&mut self) {
+        self.config.get();
+    }
+
+    fn incoming(&mut self, msg: Message) -> Option<Message> {
+        let enabled = self.config.get().incoming;
+        self.filter(msg, enabled)
+    }
+
+    fn outgoing(&mut self, msg: Message, _gate: &GateRef) -> Option<Message> {
+        let enabled = self.config.get().outgoing;
+        self.filter(msg, enabled)
+    }
+}
+
+impl std::fmt::Debug for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Filter")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A processing element, that counts messages per [`MessageKind`].
+///
+/// The counter is a handle to shared state, so clones can be installed
+/// on multiple modules, while the original is used to read the counts.
+/// The directions that are counted are read from the prop `kind-counter`,
+/// see [`DirectionConfig`].
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::net::processing::KindCounter;
+/// let counter = KindCounter::new();
+///
+/// let mut sim = Sim::new(());
+/// let handle = counter.clone();
+/// sim.set_stack(move || handle.clone());
+/// // ... create nodes and run the simulation
+/// for kind in counter.kinds() {
+///     println!("{kind}: {} in, {} out", counter.incoming(kind), counter.outgoing(kind));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct KindCounter {
+    counts: Arc<Mutex<FxHashMap<MessageKind, (usize, usize)>>>,
+    config: Configured<DirectionConfig>,
+}
+
+impl KindCounter {
+    /// Creates a new counter, with all counts zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counts: Arc::default(),
+            config: Configured::new("kind-counter"),
+        }
+    }
+
+    /// Reads the configuration from the prop `key`, instead of `kind-counter`.
+    #[must_use]
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.config.set_key(key);
+        self
+    }
+
+    /// Uses the given configuration, instead of reading it from the props.
+    #[must_use]
+    pub fn with_config(mut self, config: DirectionConfig) -> Self {
+        self.config.set(config);
+        self
+    }
+
+    /// The number of incoming messages of the given kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter was poisoned.
+    #[must_use]
+    pub fn incoming(&self, kind: MessageKind) -> usize {
+        self.counts.lock().unwrap().get(&kind).map_or(0, |c| c.0)
+    }
+
+    /// The number of outgoing messages of the given kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter was poisoned.
+    #[must_use]
+    pub fn outgoing(&self, kind: MessageKind) -> usize {
+        self.counts.lock().unwrap().get(&kind).map_or(0, |c| c.1)
+    }
+
+    /// All kinds that were counted, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter was poisoned.
+    #[must_use]
+    pub fn kinds(&self) -> Vec<MessageKind> {
+        let mut kinds = self
+            .counts
+            .lock()
+            .unwrap()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        kinds.sort_unstable();
+        kinds
+    }
+
+    /// Resets all counts to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter was poisoned.
+    pub fn clear(&self) {
+        self.counts.lock().unwrap().clear();
+    }
+}
+
+impl Default for KindCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessingElement for KindCounter {
+    fn event_start(&mut self) {
+        self.config.get();
+    }
+
+    fn incoming(&mut self, msg: Message) -> Option<Message> {
+        if self.config.get().incoming {
+            let mut counts = self.counts.lock().unwrap();
+            counts.entry(msg.header().kind).or_default().0 += 1;
+        }
+        Some(msg)
+    }
+
+    fn outgoing(&mut self, msg: Message, _gate: &GateRef) -> Option<Message> {
+        if self.config.get().outgoing {
+            let mut counts = self.counts.lock().unwrap();
+            counts.entry(msg.header().kind).or_default().1 += 1;
+        }
+        Some(msg)
+    }
+}
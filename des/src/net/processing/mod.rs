@@ -37,7 +37,7 @@
 //! all plugins (in reverse priority order). In this process they can be
 //! captured and thus **modified** or **deleted** by all plugins, closer
 //! to the network layer, than the message origin. This is done
-//! using the `Plugin::capture_outgoing` method. If messages
+//! using the `ProcessingElement::outgoing` method. If messages
 //! make it through all plugins they will be added to the networklayer,
 //! if not then not.
 //!
@@ -62,11 +62,44 @@
 //! still exists for the rest of the event cycle, and are only deleted
 //! once the next event arrives.
 //!
+//! # Provided elements
+//!
+//! Next to the [`Router`], this module provides a set of reusable elements:
+//! [`PacketCapture`], [`TokenBucket`], [`RandomDrop`], [`Delay`], [`KindCounter`]
+//! and [`Filter`]. Their configuration is read from the props of the module
+//! they are installed on, under an element-specific key, e.g. `delay`. The key
+//! can be changed using `with_key`, to stack multiple elements of the same type.
+//!
+//! ```
+//! # use des::prelude::*;
+//! # use des::net::processing::{Delay, RandomDrop};
+//! let mut sim = Sim::new(());
+//! sim.include_cfg("alice.delay: { delay: 0.01, jitter: 0.005 }");
+//! sim.include_cfg("alice.random-drop: { probability: 0.1 }");
+//! sim.set_stack(|| (RandomDrop::new(), Delay::new()));
+//! ```
 
 use std::{any::Any, fmt::Debug, ops::Deref};
 
-use super::module::Module;
-use crate::prelude::Message;
+mod capture;
+mod config;
+mod filter;
+mod routing;
+mod shaping;
+
+pub use self::capture::{CaptureConfig, CaptureRecord, Direction, PacketCapture};
+pub use self::filter::{DirectionConfig, Filter, KindCounter};
+pub use self::routing::{module_address, ForwardingTable, Route, Router};
+pub use self::shaping::{
+    Delay, DelayConfig, RandomDrop, RandomDropConfig, TokenBucket, TokenBucketConfig,
+};
+
+use super::{
+    gate::GateRef,
+    module::Module,
+    runtime::{buf_emit_at, buf_intercept, buf_take_outgoing},
+};
+use crate::{prelude::Message, time::SimTime};
 
 /// A subprogramm between the module application and the network layer.
 ///
@@ -115,6 +148,21 @@ pub trait ProcessingElement: Any {
     /// ```
     fn event_start(&mut self) {}
 
+    /// A handler for the sim start stages of the module.
+    ///
+    /// This function is called once per stage, before the stage is passed
+    /// to [`Module::at_sim_start`], in the same order as [`event_start`].
+    /// Like the module itself, elements run through the sim start stages
+    /// again, when their module is restarted.
+    ///
+    /// Use this function to set up state, that should be available
+    /// before the first message arrives.
+    ///
+    /// [`event_start`]: ProcessingElement::event_start
+    fn sim_start(&mut self, stage: usize) {
+        let _ = stage;
+    }
+
     /// A handler for when an the event processing of a message ends.
     ///
     /// This function is called only once per event. The call order
@@ -179,6 +227,37 @@ pub trait ProcessingElement: Any {
     fn incoming(&mut self, msg: Message) -> Option<Message> {
         Some(msg)
     }
+
+    /// A capture clause that can modify an outgoing message.
+    ///
+    /// This function is called for each message sent by the main application,
+    /// or by a processing element further from the network layer, in reverse
+    /// stack order. It receives the message and the gate it was sent on, before
+    /// the message enters the gate chain.
+    ///
+    /// This function can modify, pass-through or delete a message. Messages can be
+    /// duplicated or delayed, by sending them again using [`send`] or [`send_in`].
+    /// Such messages only pass the elements closer to the network layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use des::prelude::*;
+    /// struct Tagger;
+    ///
+    /// impl ProcessingElement for Tagger {
+    ///     fn outgoing(&mut self, msg: Message, _gate: &GateRef) -> Option<Message> {
+    ///         Some(msg.kind(42))
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`send`]: crate::net::message::send
+    /// [`send_in`]: crate::net::message::send_in
+    fn outgoing(&mut self, msg: Message, gate: &GateRef) -> Option<Message> {
+        let _ = gate;
+        Some(msg)
+    }
 }
 
 impl<T: Module> ProcessingElement for T {
@@ -212,21 +291,70 @@ impl Processor {
 
         let mut msg = msg;
         for i in 0..self.stack.items.len() {
+            buf_intercept(i);
             self.stack.items[i].event_start();
             if let Some(existing_msg) = msg {
                 msg = self.stack.items[i].incoming(existing_msg);
             }
+            self.flush_outgoing(i);
             self.state.bump_upstream();
         }
+        buf_intercept(0);
         msg
     }
 
+    /// Passes a sim start stage to all elements, before the handler.
+    pub(super) fn sim_start_upstream(&mut self, stage: usize) {
+        for i in 0..self.stack.items.len() {
+            buf_intercept(i);
+            self.stack.items[i].sim_start(stage);
+            self.flush_outgoing(i);
+        }
+        buf_intercept(0);
+    }
+
     pub(super) fn incoming_downstream(&mut self) {
         self.state = ProcessingState::Downstream(self.stack.items.len());
         for i in (0..self.stack.items.len()).rev() {
+            buf_intercept(i);
             self.stack.items[i].event_end();
+            self.flush_outgoing(i);
             self.state.bump_downstream();
         }
+        buf_intercept(0);
+    }
+
+    /// Intercepts all messages sent by the handler, until [`Processor::handler_end`].
+    pub(super) fn handler_start(&mut self) {
+        buf_intercept(self.stack.items.len());
+    }
+
+    /// Passes all messages sent by the handler through the processing stack.
+    pub(super) fn handler_end(&mut self) {
+        self.flush_outgoing(self.stack.items.len());
+        buf_intercept(0);
+    }
+
+    /// Passes all intercepted messages, sent by the element at index `origin`,
+    /// through all elements closer to the network layer.
+    fn flush_outgoing(&mut self, origin: usize) {
+        for (msg, gate, send_time) in buf_take_outgoing() {
+            self.outgoing_from(origin, msg, gate, send_time);
+        }
+    }
+
+    fn outgoing_from(&mut self, origin: usize, msg: Message, gate: GateRef, send_time: SimTime) {
+        let mut msg = msg;
+        for i in (0..origin).rev() {
+            buf_intercept(i);
+            let result = self.stack.items[i].outgoing(msg, &gate);
+            self.flush_outgoing(i);
+            match result {
+                Some(result) => msg = result,
+                None => return,
+            }
+        }
+        buf_emit_at(msg, gate, send_time);
     }
 }
 
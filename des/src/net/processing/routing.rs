@@ -0,0 +1,326 @@
+use fxhash::FxHashMap;
+
+use super::ProcessingElement;
+use crate::net::{
+    gate::GateRef,
+    message::{send, Header, Message},
+    module::{current, ModuleId, ModuleRef},
+    topology::{Edge, EdgeCostAttachment, Node, Topology},
+    Globals, ObjectPath,
+};
+
+/// A function assigning addresses to modules, used to route messages by their `dst` header field.
+type AddressFn = Box<dyn FnMut(&ModuleRef) -> Option<[u8; 6]>>;
+
+/// A function computing the weight of an edge, used to select the shortest paths.
+type WeightFn = Box<dyn FnMut(Edge<'_, (), EdgeCostAttachment>) -> f64>;
+
+/// The broadcast address, that is always considered local.
+const BROADCAST: [u8; 6] = [0xff; 6];
+
+/// A static forwarding table of a module, mapping destinations
+/// to the gates leading to them on a shortest path.
+///
+/// Destinations are identified by their [`ModuleId`], or by the address
+/// used in [`Header::dst`]. Since [`Header::receiver_module_id`] is
+/// overwritten on each hop, messages are routed by address only. By default,
+/// the address of a module is derived from its id, see [`module_address`].
+#[derive(Debug, Clone)]
+pub struct ForwardingTable {
+    local_addr: Option<[u8; 6]>,
+    modules: FxHashMap<ModuleId, GateRef>,
+    addresses: FxHashMap<[u8; 6], GateRef>,
+}
+
+/// The routing decision of a [`ForwardingTable`] for a message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    /// The message is addressed to the local module.
+    Local,
+    /// The message should be forwarded using the given gate.
+    Forward(GateRef),
+    /// The message is addressed to a module, that cannot be reached.
+    Unreachable,
+}
+
+impl ForwardingTable {
+    /// Computes the forwarding table of the module `src`, using the shortest
+    /// paths defined by the given edge weights.
+    ///
+    /// See [`Topology::shortest_path`] for more information on edge weights.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with the given object path exists
+    /// in the topology object, or if a weight is negative.
+    pub fn from_topology<N, C, F>(
+        topo: &Topology<N, C>,
+        src: impl Into<ObjectPath>,
+        weight: F,
+    ) -> Self
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        Self::from_topology_with_addresses(topo, src, weight, |module| {
+            Some(module_address(module.id()))
+        })
+    }
+
+    /// Computes the forwarding table of the module `src`, like
+    /// [`ForwardingTable::from_topology`], but using `address` to assign
+    /// addresses to modules. Modules without an address can only be
+    /// reached using [`ForwardingTable::gate_for`].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with the given object path exists
+    /// in the topology object, or if a weight is negative.
+    pub fn from_topology_with_addresses<N, C, F, A>(
+        topo: &Topology<N, C>,
+        src: impl Into<ObjectPath>,
+        weight: F,
+        mut address: A,
+    ) -> Self
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+        A: FnMut(&ModuleRef) -> Option<[u8; 6]>,
+    {
+        let src = src.into();
+        let local = topo
+            .nodes()
+            .iter()
+            .map(Node::module)
+            .find(|module| module.path() == src)
+            .unwrap_or_else(|| panic!("unknown node '{src}'"));
+
+        let mut table = Self {
+            local_addr: address(&local),
+            modules: FxHashMap::default(),
+            addresses: FxHashMap::default(),
+        };
+
+        for path in topo.shortest_paths(src, weight).into_values() {
+            let (Some(first), Some(last)) = (path.edges.first(), path.edges.last()) else {
+                continue;
+            };
+            let dst = last.to.module();
+            if let Some(addr) = address(&dst) {
+                table.addresses.insert(addr, first.from.gate());
+            }
+            table.modules.insert(dst.id(), first.from.gate());
+        }
+        table
+    }
+
+    /// The number of modules reachable from the local module.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Indicates whether no other module is reachable from the local module.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// The gate leading to the module with the given id.
+    #[must_use]
+    pub fn gate_for(&self, id: ModuleId) -> Option<GateRef> {
+        self.modules.get(&id).cloned()
+    }
+
+    /// The gate leading to the module with the given address.
+    #[must_use]
+    pub fn gate_for_address(&self, addr: [u8; 6]) -> Option<GateRef> {
+        self.addresses.get(&addr).cloned()
+    }
+
+    /// Decides how a message with the given header should be routed,
+    /// based on its [`dst`](Header::dst) address.
+    ///
+    /// Messages without a destination address, as well as broadcasts,
+    /// are considered local.
+    #[must_use]
+    pub fn route(&self, header: &Header) -> Route {
+        if header.dst == [0; 6] || header.dst == BROADCAST || Some(header.dst) == self.local_addr {
+            return Route::Local;
+        }
+        self.gate_for_address(header.dst)
+            .map_or(Route::Unreachable, Route::Forward)
+    }
+}
+
+/// The default address of a module, used by [`Router`] and
+/// [`ForwardingTable::from_topology`].
+///
+/// The address contains the module id in its last two bytes, in network byte order.
+#[must_use]
+pub fn module_address(id: ModuleId) -> [u8; 6] {
+    let [hi, lo] = id.0.to_be_bytes();
+    [0, 0, 0, 0, hi, lo]
+}
+
+/// A processing element, that forwards all messages not addressed
+/// to the local module along the shortest path to their destination.
+///
+/// The forwarding table is computed from the current [`Topology`]
+/// at sim start, and recomputed whenever the
+/// [`topology_version`](Globals::topology_version) changes, e.g. when links
+/// go down or modules are shut down. By default, all links that are
+/// [`alive`](EdgeCostAttachment::alive) have the same weight, thus
+/// messages are forwarded on the path with the fewest hops.
+///
+/// Messages are routed by their [`dst`](Header::dst) address, which defaults
+/// to the [`module_address`] of the destination. Messages addressed to the
+/// local module are passed up the processing stack, while messages without
+/// any route are dropped. Modules can send routed messages by scheduling
+/// them to themselves.
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::net::processing::{module_address, ProcessingStack, Router};
+/// struct Host {
+///     server: ModuleId,
+/// }
+///
+/// impl Module for Host {
+///     fn stack(&self, _: ProcessingStack) -> ProcessingStack {
+///         Router::new().into()
+///     }
+///
+///     fn at_sim_start(&mut self, _: usize) {
+///         let msg = Message::default().dst(module_address(self.server));
+///         schedule_in(msg, Duration::ZERO);
+///     }
+/// }
+/// ```
+pub struct Router {
+    table: Option<ForwardingTable>,
+    version: usize,
+    weight: WeightFn,
+    address: Option<AddressFn>,
+}
+
+impl Router {
+    /// Creates a new router, forwarding messages on the paths with
+    /// the fewest hops.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            table: None,
+            version: 0,
+            weight: Box::new(|edge| {
+                if edge.attachment.alive {
+                    1.0
+                } else {
+                    f64::INFINITY
+                }
+            }),
+            address: None,
+        }
+    }
+
+    /// Uses custom edge weights to select the shortest paths.
+    ///
+    /// Weights that are not finite mark edges as unusable.
+    #[must_use]
+    pub fn with_weight(
+        mut self,
+        weight: impl FnMut(Edge<'_, (), EdgeCostAttachment>) -> f64 + 'static,
+    ) -> Self {
+        self.weight = Box::new(weight);
+        self
+    }
+
+    /// Uses the given function to assign addresses to modules,
+    /// instead of the [`module_address`].
+    #[must_use]
+    pub fn with_addresses(
+        mut self,
+        address: impl FnMut(&ModuleRef) -> Option<[u8; 6]> + 'static,
+    ) -> Self {
+        self.address = Some(Box::new(address));
+        self
+    }
+
+    /// The current forwarding table, if the sim has allready started.
+    #[must_use]
+    pub fn table(&self) -> Option<&ForwardingTable> {
+        self.table.as_ref()
+    }
+
+    fn recompute(&mut self, globals: &Globals) {
+        let topo = globals.topology().with_edge_cost_attachment();
+        let src = current().path();
+        let table = if let Some(address) = &mut self.address {
+            ForwardingTable::from_topology_with_addresses(&topo, src, &mut self.weight, address)
+        } else {
+            ForwardingTable::from_topology(&topo, src, &mut self.weight)
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            "Recomputed forwarding table with {} reachable modules",
+            table.len()
+        );
+        self.table = Some(table);
+    }
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessingElement for Router {
+    fn sim_start(&mut self, stage: usize) {
+        if stage == 0 {
+            let globals = Globals::current();
+            self.version = globals.topology_version();
+            self.recompute(&globals);
+        }
+    }
+
+    fn event_start(&mut self) {
+        let globals = Globals::current();
+        let version = globals.topology_version();
+        // Restored simulations skip the sim start.
+        if self.table.is_none() || self.version != version {
+            self.recompute(&globals);
+            self.version = version;
+        }
+    }
+
+    fn incoming(&mut self, msg: Message) -> Option<Message> {
+        let Some(table) = &self.table else {
+            return Some(msg);
+        };
+        match table.route(msg.header()) {
+            Route::Local => Some(msg),
+            Route::Forward(gate) => {
+                #[cfg(feature = "tracing")]
+                tracing::trace!("Forwarding message [{}] on '{}'", msg, gate.path());
+                send(msg, &gate);
+                None
+            }
+            Route::Unreachable => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Dropping message [{}] without route", msg);
+                None
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Router {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Router")
+            .field("table", &self.table)
+            .field("version", &self.version)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,340 @@
+use serde::{Deserialize, Serialize};
+
+use super::{config::Configured, ProcessingElement};
+use crate::net::{
+    gate::GateRef,
+    message::{send_in, Message},
+};
+use crate::runtime::random;
+use crate::time::{Duration, SimTime};
+
+/// The configuration of a [`TokenBucket`].
+///
+/// By default, no rate limit is applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct TokenBucketConfig {
+    /// The rate in bit/s at which tokens are refilled.
+    pub rate: Option<f64>,
+    /// The capacity of the bucket in bytes.
+    pub burst: usize,
+    /// Whether messages exceeding the rate are delayed instead of dropped.
+    pub queue: bool,
+}
+
+/// A processing element, that limits the rate of outgoing messages
+/// using a token bucket.
+///
+/// The bucket holds up to [`burst`](TokenBucketConfig::burst) bytes and is
+/// refilled with [`rate`](TokenBucketConfig::rate) bit/s. A message is sent,
+/// if the bucket contains enough tokens for its length. Otherwise, the message
+/// is dropped, or delayed until enough tokens are available, if
+/// [`queue`](TokenBucketConfig::queue) is set. Delayed messages keep their order.
+///
+/// The configuration is read from the prop `token-bucket`. The rate must not be
+/// negative, and must be positive if messages are queued. If the configuration
+/// is invalid, all outgoing messages are dropped.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    config: Configured<TokenBucketConfig>,
+    tokens: Option<f64>,
+    last: SimTime,
+}
+
+impl TokenBucket {
+    /// Creates a new token bucket, that is initially full.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            config: Configured::new("token-bucket"),
+            tokens: None,
+            last: SimTime::ZERO,
+        }
+    }
+
+    /// Reads the configuration from the prop `key`, instead of `token-bucket`.
+    #[must_use]
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.config.set_key(key);
+        self
+    }
+
+    /// Uses the given configuration, instead of reading it from the props.
+    #[must_use]
+    pub fn with_config(mut self, config: TokenBucketConfig) -> Self {
+        self.config.set(config);
+        self
+    }
+}
+
+impl Default for TokenBucket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenBucketConfig {
+    fn validate(&self) -> Result<(), String> {
+        match self.rate {
+            Some(rate) if !(rate.is_finite() && rate >= 0.0) => {
+                Err(format!("rate must be a non-negative number, got {rate}"))
+            }
+            Some(rate) if rate == 0.0 && self.queue => {
+                Err("cannot queue messages with a rate of zero".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ProcessingElement for TokenBucket {
+    fn event_start(&mut self) {
+        self.config.get_valid(TokenBucketConfig::validate);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn outgoing(&mut self, msg: Message, gate: &GateRef) -> Option<Message> {
+        let Some(config) = self.config.get_valid(TokenBucketConfig::validate) else {
+            return drop_invalid(&msg);
+        };
+        let Some(rate) = config.rate else {
+            return Some(msg);
+        };
+        let bytes_per_sec = rate / 8.0;
+        let burst = config.burst as f64;
+
+        let now = SimTime::now();
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        let tokens = self.tokens.map_or(burst, |tokens| {
+            (tokens + elapsed * bytes_per_sec).min(burst)
+        });
+        self.last = now;
+
+        let len = msg.length() as f64;
+        if tokens >= len {
+            self.tokens = Some(tokens - len);
+            Some(msg)
+        } else if config.queue {
+            // Messages are delayed until the bucket has paid off their debt,
+            // so later messages are delayed at least as long as earlier ones.
+            let Ok(delay) = Duration::try_from_secs_f64((len - tokens) / bytes_per_sec) else {
+                return drop_invalid(&msg);
+            };
+            self.tokens = Some(tokens - len);
+            send_in(msg, gate, delay);
+            None
+        } else {
+            self.tokens = Some(tokens);
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Dropping message [{}] exceeding the rate limit", msg);
+            None
+        }
+    }
+}
+
+/// Drops a message, that cannot be processed due to an invalid configuration.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn drop_invalid(msg: &Message) -> Option<Message> {
+    #[cfg(feature = "tracing")]
+    tracing::warn!("Dropping message [{}] due to an invalid configuration", msg);
+    None
+}
+
+/// The configuration of a [`RandomDrop`].
+///
+/// By default, no messages are dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct RandomDropConfig {
+    /// The probability that a message is dropped.
+    pub probability: f64,
+    /// Whether incoming messages may be dropped.
+    pub incoming: bool,
+    /// Whether outgoing messages may be dropped.
+    pub outgoing: bool,
+}
+
+impl Default for RandomDropConfig {
+    fn default() -> Self {
+        Self {
+            probability: 0.0,
+            incoming: true,
+            outgoing: true,
+        }
+    }
+}
+
+/// A processing element, that drops messages with a fixed probability.
+///
+/// Drop decisions use the random number generator of the simulation,
+/// so they are reproducible for a given seed. The configuration is read
+/// from the prop `random-drop`.
+#[derive(Debug, Clone)]
+pub struct RandomDrop {
+    config: Configured<RandomDropConfig>,
+}
+
+impl RandomDrop {
+    /// Creates a new element, configured using props.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            config: Configured::new("random-drop"),
+        }
+    }
+
+    /// Creates a new element, that drops messages in both directions
+    /// with the given probability.
+    #[must_use]
+    pub fn with_probability(probability: f64) -> Self {
+        Self::new().with_config(RandomDropConfig {
+            probability,
+            ..RandomDropConfig::default()
+        })
+    }
+
+    /// Reads the configuration from the prop `key`, instead of `random-drop`.
+    #[must_use]
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.config.set_key(key);
+        self
+    }
+
+    /// Uses the given configuration, instead of reading it from the props.
+    #[must_use]
+    pub fn with_config(mut self, config: RandomDropConfig) -> Self {
+        self.config.set(config);
+        self
+    }
+
+    fn filter(&mut self, msg: Message, enabled: bool) -> Option<Message> {
+        let probability = self.config.get().probability;
+        if enabled && probability > 0.0 && random::<f64>() < probability {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Randomly dropping message [{}]", msg);
+            None
+        } else {
+            Some(msg)
+        }
+    }
+}
+
+impl Default for RandomDrop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessingElement for RandomDrop {
+    fn event_start(&mut self) {
+        self.config.get();
+    }
+
+    fn incoming(&mut self, msg: Message) -> Option<Message> {
+        let enabled = self.config.get().incoming;
+        self.filter(msg, enabled)
+    }
+
+    fn outgoing(&mut self, msg: Message, _gate: &GateRef) -> Option<Message> {
+        let enabled = self.config.get().outgoing;
+        self.filter(msg, enabled)
+    }
+}
+
+/// The configuration of a [`Delay`].
+///
+/// Durations are defined in seconds. By default, messages are not delayed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct DelayConfig {
+    /// The fixed delay applied to all messages.
+    pub delay: f64,
+    /// The upper bound of an additional, uniformly distributed delay.
+    pub jitter: f64,
+}
+
+/// A processing element, that delays outgoing messages.
+///
+/// Each message is delayed by the fixed [`delay`](DelayConfig::delay), plus
+/// a random delay up to [`jitter`](DelayConfig::jitter). Note that messages may
+/// be reordered, if a jitter is configured. The configuration is read from the
+/// prop `delay`. Both durations must be finite and not negative, otherwise
+/// all outgoing messages are dropped.
+#[derive(Debug, Clone)]
+pub struct Delay {
+    config: Configured<DelayConfig>,
+}
+
+impl Delay {
+    /// Creates a new element, configured using props.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            config: Configured::new("delay"),
+        }
+    }
+
+    /// Creates a new element, that delays all messages by a fixed duration.
+    #[must_use]
+    pub fn fixed(delay: Duration) -> Self {
+        Self::new().with_config(DelayConfig {
+            delay: delay.as_secs_f64(),
+            jitter: 0.0,
+        })
+    }
+
+    /// Reads the configuration from the prop `key`, instead of `delay`.
+    #[must_use]
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.config.set_key(key);
+        self
+    }
+
+    /// Uses the given configuration, instead of reading it from the props.
+    #[must_use]
+    pub fn with_config(mut self, config: DelayConfig) -> Self {
+        self.config.set(config);
+        self
+    }
+}
+
+impl Default for Delay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DelayConfig {
+    fn validate(&self) -> Result<(), String> {
+        for (name, value) in [("delay", self.delay), ("jitter", self.jitter)] {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(format!("{name} must be a non-negative number, got {value}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ProcessingElement for Delay {
+    fn event_start(&mut self) {
+        self.config.get_valid(DelayConfig::validate);
+    }
+
+    fn outgoing(&mut self, msg: Message, gate: &GateRef) -> Option<Message> {
+        let Some(config) = self.config.get_valid(DelayConfig::validate) else {
+            return drop_invalid(&msg);
+        };
+        let mut delay = config.delay;
+        if config.jitter > 0.0 {
+            delay += random::<f64>() * config.jitter;
+        }
+        if delay <= 0.0 {
+            return Some(msg);
+        }
+        let Ok(delay) = Duration::try_from_secs_f64(delay) else {
+            return drop_invalid(&msg);
+        };
+        send_in(msg, gate, delay);
+        None
+    }
+}
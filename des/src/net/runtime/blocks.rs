@@ -53,7 +53,8 @@ pub enum FailabilityPolicy {
     Continue,
     /// This option triggers a node restart as a result of an error.
     ///
-    /// If set on a node without restart semantics this is equivalent to `Continue`
+    /// The restart is subject to the [`RestartPolicy`](crate::net::module::RestartPolicy)
+    /// of the node, which may delay the restart, or keep the node shut down.
     Restart,
 }
 
@@ -116,9 +117,13 @@ where
                         "node '{}' failed to process message, handler fn failed with: {e} ",
                         current().path
                     ),
-                    FailabilityPolicy::Continue | FailabilityPolicy::Restart => {
+                    FailabilityPolicy::Continue => {
                         tracing::error!("failed to process message, handler fn failed with: {e}");
                     }
+                    FailabilityPolicy::Restart => {
+                        tracing::error!("failed to process message, handler fn failed with: {e}");
+                        current().shutdow_and_restart_in(Duration::ZERO);
+                    }
                 },
             },
         }
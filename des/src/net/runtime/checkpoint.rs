@@ -0,0 +1,724 @@
+use std::{
+    any::{type_name, Any},
+    fmt::Debug,
+    panic::set_hook,
+    sync::{atomic::Ordering::SeqCst, Arc},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_yml::Value;
+
+use super::{
+    fault::{FaultEvent, Restore},
+    panic_hook, ChannelUnbusyNotif, HandleMessageEvent, LinkChange, LinkStateChange,
+    MessageExitingConnection, ModuleRestartEvent, NetEvents, Sim,
+};
+use crate::{
+    net::{
+        channel::{ChannelMetrics, ChannelRef, DelayModel, LossModel, Packet},
+        gate::{Connection, GateRef},
+        message::{Body, Header, Message, MessageId, MessageKind},
+        module::{ModuleId, ModuleRef},
+        ObjectPath,
+    },
+    runtime::{Checkpoint, CheckpointError, EventCheckpoint, ExactTime},
+    stats::Statistics,
+};
+
+/// Type-erased (de)serialization functions for a message body type.
+pub(super) struct BodyCodec {
+    name: &'static str,
+    encode: fn(&Body) -> Option<Result<Value, CheckpointError>>,
+    decode: fn(Value, usize) -> Result<Body, CheckpointError>,
+}
+
+fn encode<T: Any + Serialize>(body: &Body) -> Option<Result<Value, CheckpointError>> {
+    let value = body.try_content::<T>()?;
+    Some(serde_yml::to_value(value).map_err(serde_error))
+}
+
+fn decode<T>(value: Value, length: usize) -> Result<Body, CheckpointError>
+where
+    T: Any + Clone + Debug + DeserializeOwned,
+{
+    let value = serde_yml::from_value::<T>(value).map_err(serde_error)?;
+    Ok(Body::new_with_len(value, length))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn serde_error(e: serde_yml::Error) -> CheckpointError {
+    CheckpointError::Serde(e.to_string())
+}
+
+impl<A> Sim<A> {
+    /// Registers a message body type, so that messages carrying values of
+    /// type `T` can be captured by [`Runtime::checkpoint`](crate::runtime::Runtime::checkpoint).
+    ///
+    /// Checkpointing a simulation fails if any pending message contains a body
+    /// of a type that was not registered. Bodies are restored with their original
+    /// length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use des::prelude::*;
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Debug, Clone, Serialize, Deserialize)]
+    /// struct Ping {
+    ///     seq: usize,
+    /// }
+    ///
+    /// let mut sim = Sim::new(());
+    /// sim.register_body::<Ping>();
+    /// ```
+    pub fn register_body<T>(&mut self)
+    where
+        T: Any + Clone + Debug + Serialize + DeserializeOwned,
+    {
+        if self
+            .bodies
+            .iter()
+            .any(|codec| codec.name == type_name::<T>())
+        {
+            return;
+        }
+        self.bodies.push(BodyCodec {
+            name: type_name::<T>(),
+            encode: encode::<T>,
+            decode: decode::<T>,
+        });
+    }
+
+    fn encode_body(&self, body: &Body) -> Result<BodySnapshot, CheckpointError> {
+        for codec in &self.bodies {
+            if let Some(value) = (codec.encode)(body) {
+                return Ok(BodySnapshot {
+                    ty: codec.name.to_string(),
+                    length: body.length(),
+                    value: value?,
+                });
+            }
+        }
+        Err(CheckpointError::Unsupported(format!(
+            "message body of type '{}' was not registered using Sim::register_body",
+            body.type_name()
+        )))
+    }
+
+    fn decode_body(&self, snapshot: BodySnapshot) -> Result<Body, CheckpointError> {
+        let Some(codec) = self.bodies.iter().find(|codec| codec.name == snapshot.ty) else {
+            return Err(CheckpointError::Unsupported(format!(
+                "message body of type '{}' was not registered using Sim::register_body",
+                snapshot.ty
+            )));
+        };
+        (codec.decode)(snapshot.value, snapshot.length)
+    }
+
+    fn module(&self, path: &str) -> Result<ModuleRef, CheckpointError> {
+        self.get(&ObjectPath::from(path))
+            .ok_or_else(|| CheckpointError::Missing(format!("module '{path}'")))
+    }
+
+    fn gate(&self, snapshot: &GateSnapshot) -> Result<GateRef, CheckpointError> {
+        self.module(&snapshot.module)?
+            .gate(&snapshot.name, snapshot.pos)
+            .ok_or_else(|| {
+                CheckpointError::Missing(format!(
+                    "gate '{}[{}]' on module '{}'",
+                    snapshot.name, snapshot.pos, snapshot.module
+                ))
+            })
+    }
+
+    fn locate_channel(&self, channel: &ChannelRef) -> Result<ChannelSlot, CheckpointError> {
+        self.with_modules(|mods| {
+            for module in mods.iter() {
+                for gate in module.gates() {
+                    for slot in 0..2 {
+                        let Some(con) = gate.connection_slot(slot) else {
+                            continue;
+                        };
+                        if con.channel.is_some_and(|ch| Arc::ptr_eq(&ch, channel)) {
+                            return Ok(ChannelSlot {
+                                gate: GateSnapshot::from(&gate),
+                                slot,
+                            });
+                        }
+                    }
+                }
+            }
+            Err(CheckpointError::Missing(
+                "channel not attached to any gate".to_string(),
+            ))
+        })
+    }
+
+    fn channel(&self, snapshot: &ChannelSlot) -> Result<ChannelRef, CheckpointError> {
+        self.gate(&snapshot.gate)?
+            .connection_slot(snapshot.slot)
+            .and_then(|con| con.channel)
+            .ok_or_else(|| {
+                CheckpointError::Missing(format!(
+                    "channel at slot {} of gate '{}[{}]' on module '{}'",
+                    snapshot.slot, snapshot.gate.name, snapshot.gate.pos, snapshot.gate.module
+                ))
+            })
+    }
+
+    fn checkpoint_connection(
+        &self,
+        con: &Connection,
+    ) -> Result<ConnectionSnapshot, CheckpointError> {
+        Ok(ConnectionSnapshot {
+            endpoint: GateSnapshot::from(&con.endpoint),
+            endpoint_id: con.endpoint_id,
+            channel: con
+                .channel
+                .as_ref()
+                .map(|ch| self.locate_channel(ch))
+                .transpose()?,
+        })
+    }
+
+    fn restore_connection(
+        &self,
+        snapshot: &ConnectionSnapshot,
+    ) -> Result<Connection, CheckpointError> {
+        Ok(Connection {
+            endpoint: self.gate(&snapshot.endpoint)?,
+            endpoint_id: snapshot.endpoint_id,
+            channel: snapshot
+                .channel
+                .as_ref()
+                .map(|ch| self.channel(ch))
+                .transpose()?,
+        })
+    }
+
+    fn checkpoint_fault(
+        &self,
+        event: &FaultEvent,
+    ) -> Result<NetEventsSnapshotKind, CheckpointError> {
+        let Some(fault) = self
+            .faults
+            .iter()
+            .position(|fault| Arc::ptr_eq(fault, &event.fault))
+        else {
+            return Err(CheckpointError::Missing(format!(
+                "scenario of the pending {}",
+                event.describe()
+            )));
+        };
+        let repair = match &event.repair {
+            None => None,
+            Some(Restore::Nothing) => Some(RestoreSnapshot::Nothing),
+            Some(Restore::Metrics(restore)) => Some(RestoreSnapshot::Metrics(
+                restore
+                    .iter()
+                    .map(|(channel, metrics)| Ok((self.locate_channel(channel)?, *metrics)))
+                    .collect::<Result<_, CheckpointError>>()?,
+            )),
+            Some(Restore::Loss(restore)) => Some(RestoreSnapshot::Loss(
+                restore
+                    .iter()
+                    .map(|(channel, loss)| Ok((self.locate_channel(channel)?, *loss)))
+                    .collect::<Result<_, CheckpointError>>()?,
+            )),
+            Some(Restore::Corruption(restore)) => Some(RestoreSnapshot::Corruption(
+                restore
+                    .iter()
+                    .map(|(channel, corruption)| Ok((self.locate_channel(channel)?, *corruption)))
+                    .collect::<Result<_, CheckpointError>>()?,
+            )),
+        };
+        Ok(NetEventsSnapshotKind::Fault { fault, repair })
+    }
+
+    fn restore_fault(
+        &self,
+        fault: usize,
+        repair: Option<RestoreSnapshot>,
+    ) -> Result<FaultEvent, CheckpointError> {
+        let Some(fault) = self.faults.get(fault) else {
+            return Err(CheckpointError::Missing(format!(
+                "fault #{fault} of the included scenarios"
+            )));
+        };
+        let repair = match repair {
+            None => None,
+            Some(RestoreSnapshot::Nothing) => Some(Restore::Nothing),
+            Some(RestoreSnapshot::Metrics(restore)) => Some(Restore::Metrics(
+                restore
+                    .into_iter()
+                    .map(|(slot, metrics)| Ok((self.channel(&slot)?, metrics)))
+                    .collect::<Result<_, CheckpointError>>()?,
+            )),
+            Some(RestoreSnapshot::Loss(restore)) => Some(Restore::Loss(
+                restore
+                    .into_iter()
+                    .map(|(slot, loss)| Ok((self.channel(&slot)?, loss)))
+                    .collect::<Result<_, CheckpointError>>()?,
+            )),
+            Some(RestoreSnapshot::Corruption(restore)) => Some(Restore::Corruption(
+                restore
+                    .into_iter()
+                    .map(|(slot, corruption)| Ok((self.channel(&slot)?, corruption)))
+                    .collect::<Result<_, CheckpointError>>()?,
+            )),
+        };
+        Ok(FaultEvent {
+            fault: fault.clone(),
+            repair,
+        })
+    }
+
+    fn checkpoint_message(&self, msg: &Message) -> Result<MessageSnapshot, CheckpointError> {
+        let header = &msg.header;
+        Ok(MessageSnapshot {
+            id: header.id,
+            kind: header.kind,
+            creation_time: header.creation_time.into(),
+            send_time: header.send_time.into(),
+            sender_module_id: header.sender_module_id.0,
+            receiver_module_id: header.receiver_module_id.0,
+            last_gate: header.last_gate.as_ref().map(GateSnapshot::from),
+            src: header.src,
+            dst: header.dst,
+            corrupted: header.corrupted,
+            body: msg
+                .content
+                .as_ref()
+                .map(|body| self.encode_body(body))
+                .transpose()?,
+        })
+    }
+
+    fn restore_message(&self, snapshot: MessageSnapshot) -> Result<Message, CheckpointError> {
+        let header = Header {
+            id: snapshot.id,
+            kind: snapshot.kind,
+            creation_time: snapshot.creation_time.into(),
+            send_time: snapshot.send_time.into(),
+            sender_module_id: ModuleId(snapshot.sender_module_id),
+            receiver_module_id: ModuleId(snapshot.receiver_module_id),
+            last_gate: snapshot
+                .last_gate
+                .as_ref()
+                .map(|gate| self.gate(gate))
+                .transpose()?,
+            src: snapshot.src,
+            dst: snapshot.dst,
+            corrupted: snapshot.corrupted,
+        };
+        let body = snapshot
+            .body
+            .map(|body| self.decode_body(body))
+            .transpose()?;
+        Ok(Message::from_raw_parts(Box::new(header), body))
+    }
+}
+
+/// A serializable snapshot of the state of a [`Sim`].
+///
+/// This snapshot contains the custom state of all modules (see [`Module::checkpoint`](crate::net::module::Module::checkpoint)),
+/// the transmission state of all channels, the recorded statistics and the inner application.
+/// Note that the state of processing elements and async tasks is not captured.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimSnapshot {
+    modules: Vec<ModuleSnapshot>,
+    channels: Vec<ChannelSnapshot>,
+    statistics: Statistics,
+    inner: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ModuleSnapshot {
+    path: String,
+    active: bool,
+    state: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
+struct ChannelSnapshot {
+    location: ChannelSlot,
+    busy: bool,
+    transmission_finish_time: ExactTime,
+    buffer: Vec<(MessageSnapshot, ConnectionSnapshot)>,
+    enqueued: Vec<ExactTime>,
+    burst: bool,
+    corruption_burst: bool,
+    last_arrival: ExactTime,
+    down: bool,
+    metrics: MetricsSnapshot,
+}
+
+/// The reconfigurable part of a channel.
+#[derive(Debug, Serialize, Deserialize)]
+struct MetricsSnapshot {
+    metrics: ChannelMetrics,
+    loss: LossModel,
+    corruption: LossModel,
+    delay_model: DelayModel,
+    fifo: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GateSnapshot {
+    module: String,
+    name: String,
+    pos: usize,
+}
+
+impl From<&GateRef> for GateSnapshot {
+    fn from(gate: &GateRef) -> Self {
+        GateSnapshot {
+            module: gate.owner().path().to_string(),
+            name: gate.name().to_string(),
+            pos: gate.pos(),
+        }
+    }
+}
+
+/// Channels are identified by the connection slot of the gate they are attached to.
+#[derive(Debug, Serialize, Deserialize)]
+struct ChannelSlot {
+    gate: GateSnapshot,
+    slot: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ConnectionSnapshot {
+    endpoint: GateSnapshot,
+    endpoint_id: usize,
+    channel: Option<ChannelSlot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MessageSnapshot {
+    id: MessageId,
+    kind: MessageKind,
+    creation_time: ExactTime,
+    send_time: ExactTime,
+    sender_module_id: u16,
+    receiver_module_id: u16,
+    last_gate: Option<GateSnapshot>,
+    src: [u8; 6],
+    dst: [u8; 6],
+    corrupted: bool,
+    body: Option<BodySnapshot>,
+}
+
+/// The state replaced by an injected fault, see [`Restore`].
+#[derive(Debug, Serialize, Deserialize)]
+enum RestoreSnapshot {
+    Nothing,
+    Metrics(Vec<(ChannelSlot, ChannelMetrics)>),
+    Loss(Vec<(ChannelSlot, LossModel)>),
+    Corruption(Vec<(ChannelSlot, LossModel)>),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BodySnapshot {
+    ty: String,
+    length: usize,
+    value: Value,
+}
+
+impl<A> Checkpoint for Sim<A>
+where
+    A: Serialize + DeserializeOwned,
+{
+    type Snapshot = SimSnapshot;
+
+    fn checkpoint(&self) -> Result<SimSnapshot, CheckpointError> {
+        let mods = self.with_modules(|mods| mods.iter().cloned().collect::<Vec<_>>());
+
+        let mut modules = Vec::with_capacity(mods.len());
+        let mut channels = Vec::new();
+        for module in mods {
+            modules.push(ModuleSnapshot {
+                path: module.path().to_string(),
+                active: module.is_active(),
+                state: module.processing.borrow().handler.checkpoint(),
+            });
+
+            for gate in module.gates() {
+                for slot in 0..2 {
+                    let Some(channel) = gate.connection_slot(slot).and_then(|con| con.channel)
+                    else {
+                        continue;
+                    };
+
+                    let burst = channel.in_burst();
+                    let corruption_burst = channel.in_corruption_burst();
+                    let last_arrival = channel.last_arrival();
+                    let down = !channel.is_up();
+                    let delay_model = channel.delay_model();
+                    if let DelayModel::Custom(_) = delay_model {
+                        return Err(CheckpointError::Unsupported(format!(
+                            "custom delay model of the channel at gate '{}'",
+                            gate.path()
+                        )));
+                    }
+                    let metrics = MetricsSnapshot {
+                        metrics: channel.metrics(),
+                        loss: channel.loss_model(),
+                        corruption: channel.corruption_model(),
+                        delay_model,
+                        fifo: channel.is_fifo(),
+                    };
+                    let snapshot = channel.with_transmission_state(|busy, finish, packets| {
+                        Ok::<_, CheckpointError>(ChannelSnapshot {
+                            location: ChannelSlot {
+                                gate: GateSnapshot::from(&gate),
+                                slot,
+                            },
+                            busy,
+                            transmission_finish_time: finish.into(),
+                            buffer: packets
+                                .packets()
+                                .map(|packet| {
+                                    Ok((
+                                        self.checkpoint_message(packet.message())?,
+                                        self.checkpoint_connection(&packet.con)?,
+                                    ))
+                                })
+                                .collect::<Result<_, CheckpointError>>()?,
+                            enqueued: packets
+                                .packets()
+                                .map(|packet| packet.enqueued().into())
+                                .collect(),
+                            burst,
+                            corruption_burst,
+                            last_arrival: last_arrival.into(),
+                            down,
+                            metrics,
+                        })
+                    })?;
+                    channels.push(snapshot);
+                }
+            }
+        }
+
+        Ok(SimSnapshot {
+            modules,
+            channels,
+            statistics: self.statistics(),
+            inner: serde_yml::to_value(&self.inner).map_err(serde_error)?,
+        })
+    }
+
+    fn restore(&mut self, snapshot: SimSnapshot) -> Result<(), CheckpointError> {
+        // The panic hook is usually set at sim start, which will
+        // be skipped for restored simulations.
+        set_hook(Box::new(panic_hook));
+
+        for module_snapshot in snapshot.modules {
+            let module = self.module(&module_snapshot.path)?;
+            module.ctx.active.store(module_snapshot.active, SeqCst);
+
+            // The async runtime of a module is usually created at sim start,
+            // drawing its seed from the simulation RNG. Create it now, so
+            // that no random values are drawn once the simulation resumes.
+            #[cfg(feature = "async")]
+            let _ = module.ctx.async_ext.write().rt.current();
+
+            if let Some(state) = module_snapshot.state {
+                module.activate();
+                module
+                    .processing
+                    .borrow_mut()
+                    .handler
+                    .restore_checkpoint(state);
+
+                let mut sink = Vec::new();
+                module.deactivate(&mut sink);
+                if !sink.is_empty() {
+                    return Err(CheckpointError::Unsupported(format!(
+                        "module '{}' scheduled events while restoring its state",
+                        module_snapshot.path
+                    )));
+                }
+            }
+        }
+
+        for channel_snapshot in snapshot.channels {
+            let channel = self.channel(&channel_snapshot.location)?;
+            if channel_snapshot.down {
+                channel.set_down();
+            } else {
+                channel.set_up();
+            }
+            let snapshot = channel_snapshot.metrics;
+            channel.set_metrics(snapshot.metrics);
+            channel.set_loss_model(snapshot.loss);
+            channel.set_corruption_model(snapshot.corruption);
+            channel.set_delay_model(snapshot.delay_model);
+            channel.set_fifo(snapshot.fifo);
+            if channel_snapshot.enqueued.len() != channel_snapshot.buffer.len() {
+                return Err(CheckpointError::Serde(format!(
+                    "channel at slot {} of gate '{}[{}]' on module '{}' has {} buffered messages, but {} enqueue times",
+                    channel_snapshot.location.slot,
+                    channel_snapshot.location.gate.name,
+                    channel_snapshot.location.gate.pos,
+                    channel_snapshot.location.gate.module,
+                    channel_snapshot.buffer.len(),
+                    channel_snapshot.enqueued.len()
+                )));
+            }
+            let packets = channel_snapshot
+                .buffer
+                .into_iter()
+                .zip(channel_snapshot.enqueued)
+                .map(|((msg, con), enqueued)| {
+                    Ok(Packet {
+                        msg: self.restore_message(msg)?,
+                        con: self.restore_connection(&con)?,
+                        enqueued: enqueued.into(),
+                    })
+                })
+                .collect::<Result<Vec<_>, CheckpointError>>()?;
+            channel.set_transmission_state(
+                channel_snapshot.busy,
+                channel_snapshot.transmission_finish_time.into(),
+                packets,
+            );
+            channel.set_in_burst(channel_snapshot.burst);
+            channel.set_in_corruption_burst(channel_snapshot.corruption_burst);
+            channel.set_last_arrival(channel_snapshot.last_arrival.into());
+        }
+
+        *self.stats.lock().expect("failed to lock statistics") = snapshot.statistics;
+        self.inner = serde_yml::from_value(snapshot.inner).map_err(serde_error)?;
+        Ok(())
+    }
+}
+
+/// A serializable snapshot of a pending event of a [`Sim`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NetEventsSnapshot(NetEventsSnapshotKind);
+
+#[derive(Debug, Serialize, Deserialize)]
+enum NetEventsSnapshotKind {
+    MessageExitingConnection {
+        con: ConnectionSnapshot,
+        msg: MessageSnapshot,
+    },
+    HandleMessageEvent {
+        module: String,
+        message: MessageSnapshot,
+    },
+    ChannelUnbusyNotif {
+        channel: ChannelSlot,
+    },
+    ModuleRestartEvent {
+        module: String,
+    },
+    LinkStateChange {
+        gate: GateSnapshot,
+        up: bool,
+    },
+    LinkReconfiguration {
+        gate: GateSnapshot,
+        metrics: ChannelMetrics,
+    },
+    /// Faults are identified by their index in the included scenarios.
+    Fault {
+        fault: usize,
+        repair: Option<RestoreSnapshot>,
+    },
+}
+
+impl<A> EventCheckpoint<Sim<A>> for NetEvents {
+    type Snapshot = NetEventsSnapshot;
+
+    fn checkpoint(&self, app: &Sim<A>) -> Result<NetEventsSnapshot, CheckpointError> {
+        let kind = match self {
+            Self::MessageExitingConnection(event) => {
+                NetEventsSnapshotKind::MessageExitingConnection {
+                    con: app.checkpoint_connection(&event.con)?,
+                    msg: app.checkpoint_message(&event.msg)?,
+                }
+            }
+            Self::HandleMessageEvent(event) => NetEventsSnapshotKind::HandleMessageEvent {
+                module: event.module.path().to_string(),
+                message: app.checkpoint_message(&event.message)?,
+            },
+            Self::ChannelUnbusyNotif(event) => NetEventsSnapshotKind::ChannelUnbusyNotif {
+                channel: app.locate_channel(&event.channel)?,
+            },
+            Self::ModuleRestartEvent(event) => NetEventsSnapshotKind::ModuleRestartEvent {
+                module: event.module.path().to_string(),
+            },
+            Self::LinkStateChange(event) => match event.change {
+                LinkChange::Down | LinkChange::Up => NetEventsSnapshotKind::LinkStateChange {
+                    gate: GateSnapshot::from(&event.gate),
+                    up: event.change == LinkChange::Up,
+                },
+                LinkChange::Metrics(metrics) => NetEventsSnapshotKind::LinkReconfiguration {
+                    gate: GateSnapshot::from(&event.gate),
+                    metrics,
+                },
+            },
+            Self::Fault(event) => app.checkpoint_fault(event)?,
+            Self::MediumTransmissionEnd(_) => {
+                return Err(CheckpointError::Unsupported(
+                    "pending transmission on a broadcast medium".to_string(),
+                ))
+            }
+            #[cfg(feature = "async")]
+            Self::AsyncWakeupEvent(event) => {
+                return Err(CheckpointError::Unsupported(format!(
+                    "pending async wakeup of module '{}'",
+                    event.module.path()
+                )))
+            }
+        };
+        Ok(NetEventsSnapshot(kind))
+    }
+
+    fn restore(snapshot: NetEventsSnapshot, app: &Sim<A>) -> Result<Self, CheckpointError> {
+        Ok(match snapshot.0 {
+            NetEventsSnapshotKind::MessageExitingConnection { con, msg } => {
+                Self::MessageExitingConnection(MessageExitingConnection {
+                    con: app.restore_connection(&con)?,
+                    msg: app.restore_message(msg)?,
+                })
+            }
+            NetEventsSnapshotKind::HandleMessageEvent { module, message } => {
+                Self::HandleMessageEvent(HandleMessageEvent {
+                    module: app.module(&module)?,
+                    message: app.restore_message(message)?,
+                })
+            }
+            NetEventsSnapshotKind::ChannelUnbusyNotif { channel } => {
+                Self::ChannelUnbusyNotif(ChannelUnbusyNotif {
+                    channel: app.channel(&channel)?,
+                })
+            }
+            NetEventsSnapshotKind::ModuleRestartEvent { module } => {
+                Self::ModuleRestartEvent(ModuleRestartEvent {
+                    module: app.module(&module)?,
+                })
+            }
+            NetEventsSnapshotKind::LinkStateChange { gate, up } => {
+                Self::LinkStateChange(LinkStateChange {
+                    gate: app.gate(&gate)?,
+                    change: if up { LinkChange::Up } else { LinkChange::Down },
+                })
+            }
+            NetEventsSnapshotKind::LinkReconfiguration { gate, metrics } => {
+                Self::LinkStateChange(LinkStateChange {
+                    gate: app.gate(&gate)?,
+                    change: LinkChange::Metrics(metrics),
+                })
+            }
+            NetEventsSnapshotKind::Fault { fault, repair } => {
+                Self::Fault(app.restore_fault(fault, repair)?)
+            }
+        })
+    }
+}
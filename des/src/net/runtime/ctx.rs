@@ -1,21 +1,43 @@
 #![allow(missing_docs)]
 
-use super::{Globals, HandleMessageEvent, MessageExitingConnection, Sim};
+use super::{supervise, Globals, HandleMessageEvent, MessageExitingConnection, Sim};
 use crate::net::gate::Connection;
-use crate::net::module::{current, with_mod_ctx, MOD_CTX};
+use crate::net::module::{current, with_mod_ctx, RestartOutcome, RestartRecord, MOD_CTX};
 use crate::net::ModuleRestartEvent;
 use crate::net::{gate::GateRef, message::Message, NetEvents};
 use crate::prelude::{EventLifecycle, ModuleRef};
 use crate::runtime::Runtime;
-use crate::sync::Mutex;
+use crate::sync::{Mutex, MutexGuard};
 use crate::time::SimTime;
+use crate::tracing::enter_scope;
+use std::mem;
 use std::sync::{Arc, Weak};
 
-static BUF_CTX: Mutex<BufferContext> = Mutex::new(BufferContext::new());
+thread_local! {
+    static BUF_CTX: Mutex<BufferContext> = const { Mutex::new(BufferContext::new()) };
+}
+
+/// Locks the buffer context of the simulation running on the current thread.
+fn buf_ctx() -> MutexGuard<'static, BufferContext> {
+    // SAFETY: The guard is internal to the event processing, so it
+    // never outlives the current thread.
+    let ctx: &'static Mutex<BufferContext> =
+        BUF_CTX.with(|ctx| unsafe { &*std::ptr::from_ref(ctx) });
+    ctx.lock()
+}
 
 struct BufferContext {
     // All new events that will be scheduled
     events: Vec<(NetEvents, SimTime)>,
+    // Modules spawned at runtime, that must be started
+    spawned: Vec<ModuleRef>,
+    // Modules terminated at runtime, that must be torn down
+    terminated: Vec<ModuleRef>,
+    // The number of processing elements, messages sent by the
+    // current origin must pass, before being emitted
+    intercept: usize,
+    // Messages intercepted for processing by the processing stack
+    outgoing: Vec<(Message, GateRef, SimTime)>,
     // globals
     globals: Option<Weak<Globals>>,
 }
@@ -24,6 +46,10 @@ impl BufferContext {
     const fn new() -> Self {
         Self {
             events: Vec::new(),
+            spawned: Vec::new(),
+            terminated: Vec::new(),
+            intercept: 0,
+            outgoing: Vec::new(),
             globals: None,
         }
     }
@@ -34,34 +60,58 @@ unsafe impl Sync for BufferContext {}
 
 impl Globals {
     pub(crate) fn current() -> Arc<Self> {
-        let ctx = BUF_CTX.lock();
+        let ctx = buf_ctx();
         ctx.globals
             .as_ref()
             .expect("no globals attached to this event")
             .upgrade()
             .expect("globals allready dropped: simulation shutting down")
     }
+
+    pub(crate) fn try_current() -> Option<Arc<Self>> {
+        buf_ctx().globals.as_ref()?.upgrade()
+    }
 }
 
 pub(crate) fn buf_init(globals: Weak<Globals>) {
-    let mut ctx = BUF_CTX.lock();
+    let mut ctx = buf_ctx();
     ctx.globals = Some(globals);
 
     // TODO: remove ?
     // SAFTEY:
-    // reseting the MOD_CTX is safe, since simulation lock is aquired.
-    unsafe {
-        MOD_CTX.reset(None);
-    }
+    // reseting the MOD_CTX is safe, since only one simulation exists per thread.
+    MOD_CTX.with(|ctx| unsafe { ctx.reset(None) });
 }
 
 pub(crate) fn buf_drop() {
-    let mut ctx = BUF_CTX.lock();
+    let mut ctx = buf_ctx();
     *ctx = BufferContext::new();
 }
 
-pub(crate) fn buf_send_at(mut msg: Message, gate: GateRef, send_time: SimTime) {
-    let mut ctx = BUF_CTX.lock();
+/// Sends a message, intercepting it if it must pass through processing elements first.
+pub(crate) fn buf_send_at(msg: Message, gate: GateRef, send_time: SimTime) {
+    let mut ctx = buf_ctx();
+    if ctx.intercept > 0 {
+        ctx.outgoing.push((msg, gate, send_time));
+        return;
+    }
+    drop(ctx);
+    buf_emit_at(msg, gate, send_time);
+}
+
+/// Sets the number of processing elements, messages sent from now on must pass.
+pub(crate) fn buf_intercept(depth: usize) {
+    buf_ctx().intercept = depth;
+}
+
+/// Takes all intercepted messages.
+pub(crate) fn buf_take_outgoing() -> Vec<(Message, GateRef, SimTime)> {
+    mem::take(&mut buf_ctx().outgoing)
+}
+
+/// Emits a message onto the gate chain, bypassing the processing stack.
+pub(crate) fn buf_emit_at(mut msg: Message, gate: GateRef, send_time: SimTime) {
+    let mut ctx = buf_ctx();
     msg.header.sender_module_id = current().id();
 
     crate::tracing::enter_scope(gate.owner().scope_token());
@@ -93,7 +143,7 @@ pub(crate) fn buf_schedule_at(msg: Message, arrival_time: SimTime) {
     // continue to delay the delivery of event, since non other components are
     // used, and we dont block any channels. additionally this ensures that
     // timeouts are allways ordered later than packets, which is good
-    let mut ctx = BUF_CTX.lock();
+    let mut ctx = buf_ctx();
     ctx.events.push((
         NetEvents::HandleMessageEvent(HandleMessageEvent {
             module: current().me(),
@@ -103,45 +153,213 @@ pub(crate) fn buf_schedule_at(msg: Message, arrival_time: SimTime) {
     ));
 }
 
+pub(crate) fn buf_spawn(module: ModuleRef) {
+    buf_ctx().spawned.push(module);
+}
+
+pub(crate) fn buf_terminate(module: ModuleRef) {
+    buf_ctx().terminated.push(module);
+}
+
 pub(crate) fn buf_process<A>(module: &ModuleRef, rt: &mut Runtime<Sim<A>>)
 where
     A: EventLifecycle<Sim<A>>,
 {
-    let mut ctx = BUF_CTX.lock();
+    let mut ctx = buf_ctx();
 
     // (0) Add delayed events from 'send'
     for (event, time) in ctx.events.drain(..) {
         rt.add_event(event, time);
     }
 
-    // (2) Handle shutdown if indicated
-    if let Some(restart) = module.shutdown_task.write().take() {
-        // Mark the modules state
-        #[cfg(feature = "tracing")]
-        tracing::debug!("Shuttind down module and restaring at {:?}", restart);
-        module
-            .ctx
-            .active
-            .store(false, std::sync::atomic::Ordering::SeqCst);
+    let terminated = mem::take(&mut ctx.terminated);
+    let spawned = mem::take(&mut ctx.spawned);
+    drop(ctx);
 
-        // drop the rt, to prevent all async activity from happening.
-        #[cfg(feature = "async")]
-        module.ctx.async_ext.write().rt.shutdown();
+    if !terminated.is_empty() || !spawned.is_empty() {
+        rt.app.globals.topology_changed();
+    }
+
+    // (1) Tear down terminated modules, before starting new ones, since
+    // modules may be terminated in the same event they were spawned in.
+    for module in terminated {
+        terminate(&module, rt);
+    }
+
+    // (1.1) Start spawned modules
+    for module in spawned.into_iter().filter(ModuleRef::is_active) {
+        enter_scope(module.scope_token());
+
+        #[cfg(feature = "tracing")]
+        tracing::info!("Starting spawned module");
 
-        // Reset the internal state
-        // Note that the module is not active, so it must be manually reactivated
         module.activate();
-        rt.app.error.extend(module.reset().err());
+        for stage in 0..module.num_sim_start_stages() {
+            rt.app.error.extend(module.at_sim_start(stage).err());
+        }
         module.deactivate(rt);
 
-        // Reschedule wakeup
-        if let Some(restart) = restart {
-            rt.add_event(
-                NetEvents::ModuleRestartEvent(ModuleRestartEvent {
-                    module: module.clone(),
-                }),
-                restart,
+        buf_process(&module, rt);
+    }
+
+    // (2) Handle shutdown if indicated
+    let shutdown = module.shutdown_task.write().take();
+    if let Some(restart) = shutdown {
+        shut_down(module, restart, rt);
+    }
+
+    // (3) Inform the supervisor of a caught panic
+    let failure = module.ctx.failure.write().take();
+    if let Some(err) = failure {
+        supervise(module, err, rt);
+    }
+}
+
+/// Shuts down a module and resets its state. If `restart` is set, the module
+/// is restarted at the given time, if permitted by its restart policy.
+///
+/// Returns `false` if the restart policy gave up.
+pub(super) fn shut_down<A>(
+    module: &ModuleRef,
+    restart: Option<SimTime>,
+    rt: &mut Runtime<Sim<A>>,
+) -> bool
+where
+    A: EventLifecycle<Sim<A>>,
+{
+    // Mark the modules state
+    #[cfg(feature = "tracing")]
+    tracing::debug!("Shuttind down module and restaring at {:?}", restart);
+    module
+        .ctx
+        .active
+        .store(false, std::sync::atomic::Ordering::SeqCst);
+    rt.app.globals.topology_changed();
+
+    // drop the rt, to prevent all async activity from happening.
+    #[cfg(feature = "async")]
+    module.ctx.async_ext.write().rt.shutdown();
+
+    // Reset the internal state
+    // Note that the module is not active, so it must be manually reactivated
+    module.activate();
+    rt.app.error.extend(module.reset().err());
+    module.deactivate(rt);
+
+    // Reschedule wakeup, if permitted by the restart policy
+    let Some(requested) = restart else {
+        return true;
+    };
+    let Some(restart) = schedule_restart(module, requested) else {
+        return false;
+    };
+    rt.add_event(
+        NetEvents::ModuleRestartEvent(ModuleRestartEvent {
+            module: module.clone(),
+        }),
+        restart,
+    );
+    true
+}
+
+/// Applies the restart policy of the module to a restart requested
+/// at `requested`, recording the decision in the restart history.
+fn schedule_restart(module: &ModuleRef, requested: SimTime) -> Option<SimTime> {
+    let policy = module.ctx.stereotyp.get().restart_policy;
+    let mut history = module.ctx.restarts.write();
+    let now = SimTime::now();
+    let restart = policy.schedule(&history, now, requested);
+    history.push(RestartRecord {
+        time: now,
+        outcome: restart.map_or(RestartOutcome::GaveUp, RestartOutcome::Scheduled),
+    });
+
+    #[cfg(feature = "tracing")]
+    if restart.is_none() {
+        tracing::warn!("Restart policy gave up, module stays shut down");
+    }
+    restart
+}
+
+/// Tears down a module terminated at runtime, including all its descendants.
+fn terminate<A>(module: &ModuleRef, rt: &mut Runtime<Sim<A>>)
+where
+    A: EventLifecycle<Sim<A>>,
+{
+    let children = mem::take(&mut *module.ctx.children.write());
+    for child in children.values() {
+        terminate(child, rt);
+    }
+
+    enter_scope(module.scope_token());
+
+    #[cfg(feature = "tracing")]
+    tracing::info!("Terminating module");
+
+    // (0) Shut down the module, discarding all messages sent while
+    // doing so, like at the end of the simulation.
+    if module.is_active() {
+        module.activate();
+        if let Err(e) = module.at_sim_end() {
+            rt.app.error.merge(e);
+        }
+        module.deactivate(&mut Vec::new());
+        buf_ctx().events.clear();
+    }
+
+    module
+        .ctx
+        .active
+        .store(false, std::sync::atomic::Ordering::SeqCst);
+
+    #[cfg(feature = "async")]
+    module.ctx.async_ext.write().rt.shutdown();
+
+    // (1) Disconnect all gates, taking down the channels of both directions
+    let mut channels = Vec::new();
+    for gate in module.gates() {
+        for slot in 0..2 {
+            let Some(con) = gate.connection_slot(slot) else {
+                continue;
+            };
+            channels.extend(con.channel.clone());
+            channels.extend(
+                con.endpoint
+                    .connection_slot(con.endpoint_id)
+                    .and_then(|back| back.channel),
             );
+            gate.disconnect(&con.endpoint);
         }
     }
+
+    // (2) Discard pending events, that refer to the module
+    rt.cancel_events_where(|event| match event {
+        NetEvents::HandleMessageEvent(event) => event.module == *module,
+        NetEvents::ModuleRestartEvent(event) => event.module == *module,
+        #[cfg(feature = "async")]
+        NetEvents::AsyncWakeupEvent(event) => event.module == *module,
+        NetEvents::LinkStateChange(event) => event.gate.owner() == *module,
+        NetEvents::Fault(event) => event.targets(module),
+        NetEvents::MessageExitingConnection(event) => {
+            if let Some(channel) = event
+                .con
+                .channel
+                .as_ref()
+                .filter(|ch| channels.iter().any(|other| Arc::ptr_eq(ch, other)))
+            {
+                channel.drop_in_flight(&event.msg);
+                return true;
+            }
+            event.con.endpoint.owner() == *module
+        }
+        _ => false,
+    });
+
+    // (3) Remove the module from the module tree
+    rt.app
+        .globals
+        .modules
+        .lock()
+        .expect("failed")
+        .remove(module);
 }
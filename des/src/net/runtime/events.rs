@@ -1,21 +1,29 @@
 use crate::{
     net::{
-        channel::ChannelRef, gate::Connection, message::Message, module::ModuleRef,
-        processing::ProcessingState, runtime::buf_process, Sim,
+        channel::{ChannelMetrics, ChannelRef, MediumRef},
+        gate::{Connection, GateRef},
+        message::Message,
+        module::{ModuleRef, Supervision},
+        processing::ProcessingState,
+        runtime::buf_process,
+        Globals, Sim,
     },
     prelude::RuntimeError,
     runtime::{Event, EventLifecycle, EventSink, Runtime},
     time::SimTime,
     tracing::enter_scope,
 };
-use std::{fmt::Debug, sync::atomic::Ordering::SeqCst};
+use std::{
+    fmt::Debug,
+    sync::{atomic::Ordering::SeqCst, Arc},
+};
 
 #[cfg(feature = "async")]
 use std::iter::once;
 #[cfg(feature = "async")]
 use tokio::task::{self, yield_now};
 
-use super::{Harness, PanicError};
+use super::{fault::FaultEvent, Harness, PanicError};
 
 ///
 /// The event set for a [`NetworkApplication`].
@@ -24,10 +32,21 @@ use super::{Harness, PanicError};
 #[cfg_attr(doc_cfg, doc(cfg(feature = "net")))]
 #[derive(Debug)]
 pub enum NetEvents {
+    /// A message leaves a gate, forwarding it along its connection.
     MessageExitingConnection(MessageExitingConnection),
+    /// A message is delivered to a module.
     HandleMessageEvent(HandleMessageEvent),
+    /// A channel finished the transmission of a message.
     ChannelUnbusyNotif(ChannelUnbusyNotif),
+    /// A broadcast medium finished the transmission of a message.
+    MediumTransmissionEnd(MediumTransmissionEnd),
+    /// A module is restarted after a shutdown.
     ModuleRestartEvent(ModuleRestartEvent),
+    /// A link is taken down, brought up or reconfigured.
+    LinkStateChange(LinkStateChange),
+    /// A fault of a scenario is injected or repaired.
+    Fault(FaultEvent),
+    /// An async module is woken up by a timer.
     #[cfg(feature = "async")]
     AsyncWakeupEvent(AsyncWakeupEvent),
 }
@@ -41,11 +60,62 @@ where
             Self::MessageExitingConnection(event) => event.handle(rt),
             Self::HandleMessageEvent(event) => event.handle(rt),
             Self::ChannelUnbusyNotif(event) => event.handle(rt),
+            Self::MediumTransmissionEnd(event) => event.handle(rt),
             Self::ModuleRestartEvent(event) => event.handle(rt),
+            Self::LinkStateChange(event) => event.handle(rt),
+            Self::Fault(event) => event.handle(rt),
             #[cfg(feature = "async")]
             Self::AsyncWakeupEvent(event) => event.handle(rt),
         }
     }
+
+    fn is_activity(&self) -> bool {
+        // Only the delivery of messages to modules counts as activity,
+        // channel and runtime internal events do not.
+        matches!(self, Self::HandleMessageEvent(_))
+    }
+
+    fn describe(&self) -> String {
+        NetEvents::describe(self)
+    }
+}
+
+impl NetEvents {
+    /// A short human-readable description of the event.
+    ///
+    /// See [`Event::describe`] for more information.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::MessageExitingConnection(event) => format!(
+                "MessageExitingConnection {{ gate: {}, id: {} }}",
+                event.con.endpoint.path(),
+                event.msg.header().id
+            ),
+            Self::HandleMessageEvent(event) => format!(
+                "HandleMessageEvent {{ module: {}, id: {} }}",
+                event.module.path(),
+                event.message.header().id
+            ),
+            Self::ChannelUnbusyNotif(_) => "ChannelUnbusyNotif".to_string(),
+            Self::MediumTransmissionEnd(event) => {
+                format!("MediumTransmissionEnd {{ id: {} }}", event.id)
+            }
+            Self::ModuleRestartEvent(event) => {
+                format!("ModuleRestartEvent {{ module: {} }}", event.module.path())
+            }
+            Self::LinkStateChange(event) => format!(
+                "LinkStateChange {{ gate: {}, change: {:?} }}",
+                event.gate.path(),
+                event.change
+            ),
+            Self::Fault(event) => event.describe(),
+            #[cfg(feature = "async")]
+            Self::AsyncWakeupEvent(event) => {
+                format!("AsyncWakeupEvent {{ module: {} }}", event.module.path())
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -62,6 +132,17 @@ impl MessageExitingConnection {
         let mut msg = self.msg;
         msg.header.last_gate = Some(self.con.endpoint.clone());
 
+        // Messages arriving on a link, that went down while they
+        // were on the medium, are lost. Otherwise the channel reports
+        // the delivery.
+        if let Some(channel) = &self.con.channel {
+            if !channel.is_up() {
+                channel.drop_in_flight(&msg);
+                return;
+            }
+            channel.record_delivery(&msg);
+        }
+
         // The connection that was exited.
         // Current packet position: `cur.endpoint`
         let mut cur = self.con;
@@ -109,6 +190,12 @@ impl MessageExitingConnection {
         // cur has not been checked for anything
         enter_scope(cur.endpoint.owner().scope_token());
 
+        // Gate chains ending at a medium continue as broadcast transmissions.
+        if let Some(medium) = cur.endpoint.medium() {
+            medium.transmit(msg, cur.endpoint, sink);
+            return;
+        }
+
         #[cfg(feature = "tracing")]
         tracing::info!(
             "Gate '{}' forwarding message [{}] to module #{}",
@@ -172,7 +259,7 @@ pub struct ModuleRestartEvent {
 }
 
 impl ModuleRestartEvent {
-    fn handle<A>(self, rt: &mut Runtime<Sim<A>>)
+    pub(super) fn handle<A>(self, rt: &mut Runtime<Sim<A>>)
     where
         A: EventLifecycle<Sim<A>>,
     {
@@ -230,6 +317,88 @@ impl ChannelUnbusyNotif {
     }
 }
 
+#[derive(Debug)]
+pub struct LinkStateChange {
+    pub(crate) gate: GateRef,
+    pub(crate) change: LinkChange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum LinkChange {
+    Down,
+    Up,
+    Metrics(ChannelMetrics),
+}
+
+impl LinkStateChange {
+    pub(super) fn handle<A>(self, rt: &mut Runtime<Sim<A>>)
+    where
+        A: EventLifecycle<Sim<A>>,
+    {
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            "Link at gate '{}' changing state: {:?}",
+            self.gate.path(),
+            self.change
+        );
+
+        let channels = self.gate.link_channels();
+        match self.change {
+            LinkChange::Down => {
+                for channel in &channels {
+                    channel.set_down();
+                }
+                rt.app.globals.topology_changed();
+
+                // Messages on the medium are lost, even if they would
+                // arrive after the link is up again.
+                rt.cancel_events_where(|event| {
+                    let NetEvents::MessageExitingConnection(event) = event else {
+                        return false;
+                    };
+                    let Some(channel) = event
+                        .con
+                        .channel
+                        .as_ref()
+                        .filter(|ch| channels.iter().any(|other| Arc::ptr_eq(ch, other)))
+                    else {
+                        return false;
+                    };
+                    channel.drop_in_flight(&event.msg);
+                    true
+                });
+            }
+            LinkChange::Up => {
+                for channel in &channels {
+                    channel.set_up();
+                }
+                rt.app.globals.topology_changed();
+            }
+            LinkChange::Metrics(metrics) => {
+                for channel in &channels {
+                    channel.set_metrics(metrics);
+                }
+                rt.app.globals.topology_changed();
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MediumTransmissionEnd {
+    pub(crate) medium: MediumRef,
+    pub(crate) id: u64,
+}
+
+impl MediumTransmissionEnd {
+    fn handle<A>(self, rt: &mut Runtime<Sim<A>>)
+    where
+        A: EventLifecycle<Sim<A>>,
+    {
+        self.medium.finish(self.id, rt);
+    }
+}
+
 impl ModuleRef {
     pub(crate) fn reset(&self) -> Result<(), PanicError> {
         let mut brw = self.processing.borrow_mut();
@@ -246,9 +415,13 @@ impl ModuleRef {
     #[cfg(feature = "async")]
     pub(crate) fn async_wakeup(&self) -> Result<(), PanicError> {
         if self.ctx.active.load(SeqCst) {
-            self.processing.borrow_mut().incoming_upstream(None);
-            Harness::new(&self.ctx).exec(|| {}).catch()?;
-            self.processing.borrow_mut().incoming_downstream();
+            let mut processing = self.processing.borrow_mut();
+            processing.incoming_upstream(None);
+            processing.handler_start();
+            let harness = Harness::new(&self.ctx).exec(|| {});
+            processing.handler_end();
+            harness.catch()?;
+            processing.incoming_downstream();
         } else {
             #[cfg(feature = "tracing")]
             tracing::debug!("Ignoring message since module is inactive");
@@ -261,6 +434,9 @@ impl ModuleRef {
         tracing::debug!("Restarting module");
         // restart the module itself.
         self.ctx.active.store(true, SeqCst);
+        if let Some(globals) = Globals::try_current() {
+            globals.topology_changed();
+        }
 
         // Do sim start procedure
         let stages = self.num_sim_start_stages();
@@ -279,16 +455,17 @@ impl ModuleRef {
 
             // Peek
             processing.state = ProcessingState::Peek;
-            if let Some(msg) = msg {
-                Harness::new(&self.ctx)
-                    .exec(|| {
-                        let msg = msg;
-                        processing.handler.handle_message(msg);
-                    })
-                    .catch()?;
+            processing.handler_start();
+            let harness = if let Some(msg) = msg {
+                Harness::new(&self.ctx).exec(|| {
+                    let msg = msg;
+                    processing.handler.handle_message(msg);
+                })
             } else {
-                Harness::new(&self.ctx).exec(|| {}).catch()?;
-            }
+                Harness::new(&self.ctx).exec(|| {})
+            };
+            processing.handler_end();
+            harness.catch()?;
 
             // Downstream
             processing.incoming_downstream();
@@ -302,14 +479,35 @@ impl ModuleRef {
     pub(crate) fn at_sim_start(&self, stage: usize) -> Result<(), PanicError> {
         let mut processing = self.processing.borrow_mut();
 
+        processing.sim_start_upstream(stage);
         processing.incoming_upstream(None);
-        Harness::new(&self.ctx)
-            .exec(|| processing.handler.at_sim_start(stage))
-            .catch()?;
+        processing.handler_start();
+        let harness = Harness::new(&self.ctx).exec(|| processing.handler.at_sim_start(stage));
+        processing.handler_end();
+        harness.catch()?;
         processing.incoming_downstream();
         Ok(())
     }
 
+    pub(crate) fn handle_child_failure(
+        &self,
+        child: ModuleRef,
+        err: &PanicError,
+    ) -> Result<Supervision, PanicError> {
+        let mut processing = self.processing.borrow_mut();
+
+        processing.incoming_upstream(None);
+        processing.handler_start();
+        let mut supervision = Supervision::Ignore;
+        let harness = Harness::new(&self.ctx).exec(|| {
+            supervision = processing.handler.handle_child_failure(child, err);
+        });
+        processing.handler_end();
+        harness.catch()?;
+        processing.incoming_downstream();
+        Ok(supervision)
+    }
+
     pub(crate) fn num_sim_start_stages(&self) -> usize {
         // No harness since this method bust be called before startin initalization to check the number of loops
         self.processing.borrow().handler.num_sim_start_stages()
@@ -321,9 +519,10 @@ impl ModuleRef {
         processing.incoming_upstream(None);
 
         let mut result = Ok(());
-        Harness::new(&self.ctx)
-            .exec(|| result = processing.handler.at_sim_end())
-            .catch()?;
+        processing.handler_start();
+        let harness = Harness::new(&self.ctx).exec(|| result = processing.handler.at_sim_end());
+        processing.handler_end();
+        harness.catch()?;
 
         #[cfg(feature = "async")]
         {
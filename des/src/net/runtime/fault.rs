@@ -0,0 +1,741 @@
+//! Declarative fault injection.
+//!
+//! A [`Scenario`] describes failures of modules and links, that are injected
+//! into a simulation at given simulation times, or as random processes of
+//! failures and repairs. Scenarios are usually loaded from YAML using
+//! [`SimBuilder::include_faults`](crate::net::SimBuilder::include_faults),
+//! alongside NDL files or configuration. All faults are executed as
+//! regular [`NetEvents`], drawing random times from the simulation RNG,
+//! so that runs using the same seed inject the same failures.
+//!
+//! # Scenario format
+//!
+//! A scenario is a list of faults, each consisting of an action and
+//! its timing. Times and durations are defined in seconds.
+//!
+//! ```yaml
+//! faults:
+//!   # crashes 'alice' at 10s, restarting it 2s later
+//!   - crash: alice
+//!     at: 10.0
+//!     mttr: 2.0
+//!   # restarts 'bob' at 12s
+//!   - restart: bob
+//!     at: 12.0
+//!   # takes the link at 'alice.port' down and up again, with an
+//!   # exponentially distributed time between failures
+//!   - link-down: alice.port
+//!     mtbf: { exponential: 60.0 }
+//!     mttr: { uniform: [1.0, 5.0] }
+//!     until: 600.0
+//!   # degrades the link at 'bob.port' for 5s
+//!   - degrade: { link: bob.port, latency: 0.2, bitrate: 1000 }
+//!     at: 20.0
+//!     mttr: 5.0
+//!   # loses packets on the link at 'bob.port' from 30s onwards
+//!   - loss: { link: bob.port, bit-error-rate: 1e-4 }
+//!     at: 30.0
+//!   # corrupts every tenth message on the link at 'alice.port' for 10s
+//!   - corrupt: { link: alice.port, probability: 0.1 }
+//!     at: 40.0
+//!     mttr: 10.0
+//! ```
+//!
+//! The actions are:
+//!
+//! - `crash: <module>`: shuts down a module. If its [`Stereotyp`] reports
+//!   caught panics to the parent, the failure is handed to the supervisor,
+//!   see [`Module::handle_child_failure`].
+//! - `restart: <module>`: shuts down a module, and starts it again immediately.
+//! - `link-down: <gate>` and `link-up: <gate>`: change the state of the link at a gate,
+//!   like [`Runtime::link_down_at`](crate::runtime::Runtime::link_down_at).
+//! - `degrade: { link: <gate>, ... }`: overrides the `bitrate`, `latency`
+//!   or `jitter` of the link at a gate.
+//! - `loss: { link: <gate>, ... }`: loses messages on the link at a gate, using
+//!   either a `bit-error-rate` or a loss `probability`, see [`LossModel`]. Lost
+//!   messages still occupy the link, but are never delivered.
+//! - `corrupt: { link: <gate>, ... }`: corrupts messages on the link at a gate,
+//!   with the same parameters as `loss`. Corrupted messages are delivered, but
+//!   their [`corrupted`](crate::net::message::Header::corrupted) flag is set,
+//!   so receivers can discard them like a failed checksum.
+//!
+//! A fault is injected `at` the given time. Faults with a `mttr` (time to
+//! repair, alias `duration`) are reverted afterwards: crashed modules are
+//! restarted, links brought up, and degraded, lossy or corrupting links restored.
+//! Faults with a `mtbf` (time between failures) are injected again after
+//! each repair, until the optional `until` time. Without `at`, the first
+//! failure occurs after one `mtbf`. Only `restart` and `link-up` faults
+//! cannot be repaired. Note that recurring faults without `until` keep the
+//! simulation running, unless it is limited otherwise.
+//!
+//! The modules and gates targeted by a scenario are resolved when the scenario
+//! is included, so they must exist at that point. Pending injections and repairs
+//! are captured by [`Runtime::checkpoint`](crate::runtime::Runtime::checkpoint),
+//! so restored simulations continue the scenario, as long as they include the
+//! same scenarios as the original simulation.
+//!
+//! Timings are either a fixed number of seconds, or a [`TimeDistribution`]
+//! like `{ exponential: <mean> }`, `{ uniform: [<min>, <max>] }` or
+//! `{ weibull: { shape: <k>, scale: <lambda> } }`.
+//!
+//! [`Stereotyp`]: crate::net::module::Stereotyp
+//! [`Module::handle_child_failure`]: crate::net::module::Module::handle_child_failure
+
+use std::{error::Error, fmt, sync::Arc};
+
+use serde::Deserialize;
+
+use super::{shut_down, supervise, LinkChange, LinkStateChange, ModuleRestartEvent, PanicError};
+use crate::{
+    net::{
+        channel::{ChannelMetrics, ChannelRef, LossModel},
+        gate::GateRef,
+        module::ModuleRef,
+        NetEvents, ObjectPath, Sim,
+    },
+    runtime::{random, EventLifecycle, Runtime},
+    time::{Duration, SimTime},
+    tracing::enter_scope,
+};
+
+/// An error that occured while loading a fault scenario.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenarioError {
+    /// The document is malformed.
+    Parse(String),
+    /// A fault is not well defined.
+    Invalid(String),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(s) => write!(f, "invalid scenario document: {s}"),
+            Self::Invalid(s) => write!(f, "invalid fault: {s}"),
+        }
+    }
+}
+
+impl Error for ScenarioError {}
+
+/// A set of faults, injected into a simulation.
+///
+/// See the [module level documentation](self) for more information.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// The faults of the scenario.
+    #[serde(default)]
+    pub faults: Vec<Fault>,
+}
+
+impl Scenario {
+    /// Parses a scenario from YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is malformed, or any
+    /// fault is not well defined.
+    pub fn from_yaml(raw: &str) -> Result<Self, ScenarioError> {
+        let scenario =
+            serde_yml::from_str::<Self>(raw).map_err(|e| ScenarioError::Parse(e.to_string()))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Checks that all faults are well defined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first invalid fault.
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        self.faults.iter().try_for_each(Fault::validate)
+    }
+}
+
+/// A fault, consisting of an action and its timing.
+///
+/// See the [module level documentation](self) for more information.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Fault {
+    /// The injected failure.
+    #[serde(flatten)]
+    pub action: FaultAction,
+    /// The time of the first injection.
+    pub at: Option<TimeDistribution>,
+    /// The time to repair, after which the fault is reverted.
+    #[serde(alias = "duration")]
+    pub mttr: Option<TimeDistribution>,
+    /// The time between a repair and the next injection.
+    pub mtbf: Option<TimeDistribution>,
+    /// The time after which no more injections take place, in seconds.
+    pub until: Option<f64>,
+}
+
+impl Fault {
+    /// Checks that the fault is well defined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timing is incomplete, the action cannot
+    /// be repaired, or any parameter is out of range.
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        let invalid = |msg: &str| Err(ScenarioError::Invalid(format!("{}: {msg}", self.action)));
+
+        if self.at.is_none() && self.mtbf.is_none() {
+            return invalid("either 'at' or 'mtbf' must be defined");
+        }
+        if self.mtbf.is_some() && self.mttr.is_none() {
+            return invalid("recurring faults require a 'mttr'");
+        }
+        if self.mttr.is_some() && !self.action.is_repairable() {
+            return invalid("fault cannot be repaired");
+        }
+        if self.until.is_some_and(|until| !(0.0..).contains(&until)) {
+            return invalid("'until' must be a non-negative time");
+        }
+        for dist in [&self.at, &self.mttr, &self.mtbf].into_iter().flatten() {
+            if let Err(e) = dist.validate() {
+                return invalid(&e);
+            }
+        }
+
+        match &self.action {
+            FaultAction::Degrade(degrade)
+                if [degrade.latency, degrade.jitter]
+                    .into_iter()
+                    .flatten()
+                    .any(|secs| !(0.0..f64::INFINITY).contains(&secs)) =>
+            {
+                invalid("'latency' and 'jitter' must be non-negative durations")
+            }
+            FaultAction::Loss(loss) => loss.loss_model().map(|_| ()).or_else(|e| invalid(&e)),
+            FaultAction::Corrupt(corrupt) => corrupt
+                .corruption_model()
+                .map(|_| ())
+                .or_else(|e| invalid(&e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The failure injected by a [`Fault`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FaultAction {
+    /// Shuts down a module, informing its supervisor if required
+    /// by its stereotyp. Repairs restart the module.
+    Crash(ObjectPath),
+    /// Shuts down a module, and starts it again immediately.
+    Restart(ObjectPath),
+    /// Takes the link at a gate down. Repairs bring the link up again.
+    LinkDown(ObjectPath),
+    /// Brings the link at a gate up.
+    LinkUp(ObjectPath),
+    /// Degrades the link at a gate. Repairs restore the previous metrics.
+    Degrade(LinkDegradation),
+    /// Loses messages on the link at a gate, by replacing its [`LossModel`].
+    /// Repairs restore the previous loss model.
+    Loss(LinkLoss),
+    /// Corrupts messages on the link at a gate, by replacing its corruption
+    /// model, see [`Channel::set_corruption_model`]. Repairs restore the
+    /// previous corruption model.
+    ///
+    /// [`Channel::set_corruption_model`]: crate::net::channel::Channel::set_corruption_model
+    Corrupt(LinkCorruption),
+}
+
+impl FaultAction {
+    fn is_repairable(&self) -> bool {
+        !matches!(self, Self::Restart(_) | Self::LinkUp(_))
+    }
+}
+
+impl fmt::Display for FaultAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Crash(module) => write!(f, "crash '{module}'"),
+            Self::Restart(module) => write!(f, "restart '{module}'"),
+            Self::LinkDown(gate) => write!(f, "link-down '{gate}'"),
+            Self::LinkUp(gate) => write!(f, "link-up '{gate}'"),
+            Self::Degrade(degrade) => write!(f, "degrade '{}'", degrade.link),
+            Self::Loss(loss) => write!(f, "loss '{}'", loss.link),
+            Self::Corrupt(corrupt) => write!(f, "corrupt '{}'", corrupt.link),
+        }
+    }
+}
+
+/// The parameters of a degraded link. Unset parameters keep their value.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LinkDegradation {
+    /// The gate, whose link is degraded.
+    pub link: ObjectPath,
+    /// The bitrate in bit/s.
+    pub bitrate: Option<usize>,
+    /// The latency in seconds.
+    pub latency: Option<f64>,
+    /// The jitter in seconds.
+    pub jitter: Option<f64>,
+}
+
+impl LinkDegradation {
+    fn apply(&self, mut metrics: ChannelMetrics) -> ChannelMetrics {
+        if let Some(bitrate) = self.bitrate {
+            metrics.bitrate = bitrate;
+        }
+        if let Some(latency) = self.latency {
+            metrics.latency = Duration::from_secs_f64(latency);
+        }
+        if let Some(jitter) = self.jitter {
+            metrics.jitter = Duration::from_secs_f64(jitter);
+        }
+        metrics
+    }
+}
+
+/// The parameters of a lossy link. Exactly one parameter must be defined.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LinkLoss {
+    /// The gate, whose link loses messages.
+    pub link: ObjectPath,
+    /// The probability that a bit is corrupted, losing the message,
+    /// see [`LossModel::BitError`].
+    pub bit_error_rate: Option<f64>,
+    /// The probability that a message is lost, see [`LossModel::Bernoulli`].
+    pub probability: Option<f64>,
+}
+
+impl LinkLoss {
+    fn loss_model(&self) -> Result<LossModel, String> {
+        error_model(self.bit_error_rate, self.probability)
+    }
+}
+
+/// The parameters of a corrupting link. Exactly one parameter must be defined.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LinkCorruption {
+    /// The gate, whose link corrupts messages.
+    pub link: ObjectPath,
+    /// The probability that a bit is corrupted, corrupting the message,
+    /// see [`LossModel::BitError`].
+    pub bit_error_rate: Option<f64>,
+    /// The probability that a message is corrupted, see [`LossModel::Bernoulli`].
+    pub probability: Option<f64>,
+}
+
+impl LinkCorruption {
+    fn corruption_model(&self) -> Result<LossModel, String> {
+        error_model(self.bit_error_rate, self.probability)
+    }
+}
+
+fn error_model(bit_error_rate: Option<f64>, probability: Option<f64>) -> Result<LossModel, String> {
+    let (model, p) = match (bit_error_rate, probability) {
+        (Some(p), None) => (LossModel::BitError(p), p),
+        (None, Some(p)) => (LossModel::Bernoulli(p), p),
+        _ => {
+            return Err(
+                "exactly one of 'bit-error-rate' or 'probability' must be defined".to_string(),
+            )
+        }
+    };
+    if !(0.0..=1.0).contains(&p) {
+        return Err(format!("probability {p} is not within [0, 1]"));
+    }
+    Ok(model)
+}
+
+/// A distribution of durations, used for the timing of faults.
+///
+/// In YAML, fixed durations are defined as a plain number of seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(from = "TimeDistributionDef")]
+pub enum TimeDistribution {
+    /// A fixed number of seconds.
+    Fixed(f64),
+    /// An exponential distribution with the given mean in seconds.
+    Exponential(f64),
+    /// A uniform distribution between `min` and `max` seconds.
+    Uniform {
+        /// The lower bound in seconds.
+        min: f64,
+        /// The upper bound in seconds.
+        max: f64,
+    },
+    /// A Weibull distribution, commonly used to model the lifetime of components.
+    Weibull {
+        /// The shape `k`. Shapes below one model early failures,
+        /// shapes above one model wear-out.
+        shape: f64,
+        /// The scale `lambda` in seconds.
+        scale: f64,
+    },
+}
+
+impl TimeDistribution {
+    /// Draws a duration from the simulation RNG.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the RNG has not been initalized.
+    #[must_use]
+    pub fn sample(&self) -> Duration {
+        let secs = match *self {
+            Self::Fixed(secs) => secs,
+            Self::Exponential(mean) => -(1.0 - random::<f64>()).ln() * mean,
+            Self::Uniform { min, max } => min + random::<f64>() * (max - min),
+            Self::Weibull { shape, scale } => {
+                scale * (-(1.0 - random::<f64>()).ln()).powf(1.0 / shape)
+            }
+        };
+        Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let valid = match *self {
+            Self::Fixed(secs) => secs >= 0.0 && secs.is_finite(),
+            Self::Exponential(mean) => mean > 0.0 && mean.is_finite(),
+            Self::Uniform { min, max } => min >= 0.0 && min <= max && max.is_finite(),
+            Self::Weibull { shape, scale } => {
+                shape > 0.0 && scale > 0.0 && shape.is_finite() && scale.is_finite()
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("invalid distribution {self:?}"))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TimeDistributionDef {
+    Fixed(f64),
+    Tagged(TaggedTimeDistribution),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum TaggedTimeDistribution {
+    Fixed(f64),
+    Exponential(f64),
+    Uniform([f64; 2]),
+    Weibull { shape: f64, scale: f64 },
+}
+
+impl From<TimeDistributionDef> for TimeDistribution {
+    fn from(def: TimeDistributionDef) -> Self {
+        match def {
+            TimeDistributionDef::Fixed(secs)
+            | TimeDistributionDef::Tagged(TaggedTimeDistribution::Fixed(secs)) => Self::Fixed(secs),
+            TimeDistributionDef::Tagged(TaggedTimeDistribution::Exponential(mean)) => {
+                Self::Exponential(mean)
+            }
+            TimeDistributionDef::Tagged(TaggedTimeDistribution::Uniform([min, max])) => {
+                Self::Uniform { min, max }
+            }
+            TimeDistributionDef::Tagged(TaggedTimeDistribution::Weibull { shape, scale }) => {
+                Self::Weibull { shape, scale }
+            }
+        }
+    }
+}
+
+/// A fault bound to the modules and gates of a simulation.
+#[derive(Debug)]
+pub(crate) struct BoundFault {
+    fault: Fault,
+    target: Target,
+}
+
+impl BoundFault {
+    /// Resolves the module or gate targeted by a fault.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fault refers to a module or gate,
+    /// that does not exist.
+    pub(crate) fn bind<A>(fault: Fault, sim: &Sim<A>) -> Result<Self, ScenarioError> {
+        let target = match &fault.action {
+            FaultAction::Crash(path) | FaultAction::Restart(path) => {
+                let Some(module) = sim.get(path) else {
+                    return Err(ScenarioError::Invalid(format!(
+                        "{}: module '{path}' does not exist",
+                        fault.action
+                    )));
+                };
+                Target::Module(module)
+            }
+            FaultAction::LinkDown(path)
+            | FaultAction::LinkUp(path)
+            | FaultAction::Degrade(LinkDegradation { link: path, .. })
+            | FaultAction::Loss(LinkLoss { link: path, .. })
+            | FaultAction::Corrupt(LinkCorruption { link: path, .. }) => {
+                let Some(gate) = sim.find_gate(path) else {
+                    return Err(ScenarioError::Invalid(format!(
+                        "{}: gate '{path}' does not exist",
+                        fault.action
+                    )));
+                };
+                Target::Gate(gate)
+            }
+        };
+        Ok(Self { fault, target })
+    }
+}
+
+#[derive(Debug)]
+enum Target {
+    Module(ModuleRef),
+    Gate(GateRef),
+}
+
+/// The state replaced by an injected fault, restored by its repair.
+#[derive(Debug)]
+pub(crate) enum Restore {
+    Nothing,
+    Metrics(Vec<(ChannelRef, ChannelMetrics)>),
+    Loss(Vec<(ChannelRef, LossModel)>),
+    Corruption(Vec<(ChannelRef, LossModel)>),
+}
+
+/// The injection or repair of a fault.
+#[derive(Debug)]
+pub struct FaultEvent {
+    pub(crate) fault: Arc<BoundFault>,
+    pub(crate) repair: Option<Restore>,
+}
+
+/// Schedules the first injection of all faults.
+pub(super) fn schedule<A>(faults: &[Arc<BoundFault>], rt: &mut Runtime<Sim<A>>)
+where
+    A: EventLifecycle<Sim<A>>,
+{
+    for fault in faults {
+        let delay = fault
+            .fault
+            .at
+            .as_ref()
+            .or(fault.fault.mtbf.as_ref())
+            .map_or(Duration::ZERO, TimeDistribution::sample);
+        fault.clone().schedule_injection(SimTime::now() + delay, rt);
+    }
+}
+
+impl BoundFault {
+    fn schedule_injection<A>(self: Arc<Self>, time: SimTime, rt: &mut Runtime<Sim<A>>)
+    where
+        A: EventLifecycle<Sim<A>>,
+    {
+        if self
+            .fault
+            .until
+            .is_some_and(|until| time > SimTime::from(until))
+        {
+            return;
+        }
+        rt.add_event(
+            NetEvents::Fault(FaultEvent {
+                fault: self,
+                repair: None,
+            }),
+            time,
+        );
+    }
+
+    fn module(&self) -> &ModuleRef {
+        let Target::Module(ref module) = self.target else {
+            unreachable!("fault '{}' does not target a module", self.fault.action)
+        };
+        module
+    }
+
+    fn gate(&self) -> &GateRef {
+        let Target::Gate(ref gate) = self.target else {
+            unreachable!("fault '{}' does not target a gate", self.fault.action)
+        };
+        gate
+    }
+
+    fn inject<A>(&self, rt: &mut Runtime<Sim<A>>) -> Restore
+    where
+        A: EventLifecycle<Sim<A>>,
+    {
+        match &self.fault.action {
+            FaultAction::Crash(_) => {
+                let module = self.module();
+                enter_scope(module.scope_token());
+                if !module.is_active() {
+                    return Restore::Nothing;
+                }
+                shut_down(module, None, rt);
+                if module.stereotyp().on_panic_inform_parent && module.ctx.parent.is_some() {
+                    let err = PanicError {
+                        path: module.path(),
+                        payload: Box::new("injected crash"),
+                    };
+                    supervise(module, err, rt);
+                }
+                Restore::Nothing
+            }
+            FaultAction::Restart(_) => {
+                let module = self.module();
+                enter_scope(module.scope_token());
+                if module.is_active() {
+                    shut_down(module, None, rt);
+                }
+                restart(module, rt);
+                Restore::Nothing
+            }
+            FaultAction::LinkDown(_) => {
+                self.change_link(LinkChange::Down, rt);
+                Restore::Nothing
+            }
+            FaultAction::LinkUp(_) => {
+                self.change_link(LinkChange::Up, rt);
+                Restore::Nothing
+            }
+            FaultAction::Degrade(degrade) => {
+                let channels = self.gate().link_channels();
+                let mut restore = Vec::with_capacity(channels.len());
+                for channel in channels {
+                    let metrics = channel.metrics();
+                    channel.set_metrics(degrade.apply(metrics));
+                    restore.push((channel, metrics));
+                }
+                Restore::Metrics(restore)
+            }
+            FaultAction::Loss(loss) => {
+                let loss = loss.loss_model().expect("fault was validated");
+                let channels = self.gate().link_channels();
+                let mut restore = Vec::with_capacity(channels.len());
+                for channel in channels {
+                    restore.push((channel.clone(), channel.loss_model()));
+                    channel.set_loss_model(loss);
+                }
+                Restore::Loss(restore)
+            }
+            FaultAction::Corrupt(corrupt) => {
+                let corruption = corrupt.corruption_model().expect("fault was validated");
+                let channels = self.gate().link_channels();
+                let mut restore = Vec::with_capacity(channels.len());
+                for channel in channels {
+                    restore.push((channel.clone(), channel.corruption_model()));
+                    channel.set_corruption_model(corruption);
+                }
+                Restore::Corruption(restore)
+            }
+        }
+    }
+
+    fn repair<A>(&self, restore: Restore, rt: &mut Runtime<Sim<A>>)
+    where
+        A: EventLifecycle<Sim<A>>,
+    {
+        match (&self.fault.action, restore) {
+            (FaultAction::Crash(_), _) => {
+                // The supervisor may have restarted the module already.
+                let module = self.module();
+                enter_scope(module.scope_token());
+                if !module.is_active() {
+                    restart(module, rt);
+                }
+            }
+            (FaultAction::LinkDown(_), _) => self.change_link(LinkChange::Up, rt),
+            (_, Restore::Metrics(restore)) => {
+                for (channel, metrics) in restore {
+                    channel.set_metrics(metrics);
+                }
+            }
+            (_, Restore::Loss(restore)) => {
+                for (channel, loss) in restore {
+                    channel.set_loss_model(loss);
+                }
+            }
+            (_, Restore::Corruption(restore)) => {
+                for (channel, corruption) in restore {
+                    channel.set_corruption_model(corruption);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn change_link<A>(&self, change: LinkChange, rt: &mut Runtime<Sim<A>>)
+    where
+        A: EventLifecycle<Sim<A>>,
+    {
+        LinkStateChange {
+            gate: self.gate().clone(),
+            change,
+        }
+        .handle(rt);
+    }
+}
+
+fn restart<A>(module: &ModuleRef, rt: &mut Runtime<Sim<A>>)
+where
+    A: EventLifecycle<Sim<A>>,
+{
+    ModuleRestartEvent {
+        module: module.clone(),
+    }
+    .handle(rt);
+}
+
+impl FaultEvent {
+    pub(super) fn handle<A>(self, rt: &mut Runtime<Sim<A>>)
+    where
+        A: EventLifecycle<Sim<A>>,
+    {
+        let fault = &self.fault.fault;
+        match self.repair {
+            None => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("Injecting fault: {}", fault.action);
+
+                let restore = self.fault.inject(rt);
+                if let Some(ref mttr) = fault.mttr {
+                    let time = SimTime::now() + mttr.sample();
+                    rt.add_event(
+                        NetEvents::Fault(FaultEvent {
+                            fault: self.fault.clone(),
+                            repair: Some(restore),
+                        }),
+                        time,
+                    );
+                }
+            }
+            Some(restore) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("Repairing fault: {}", fault.action);
+
+                self.fault.repair(restore, rt);
+                if let Some(ref mtbf) = fault.mtbf {
+                    let time = SimTime::now() + mtbf.sample();
+                    self.fault.clone().schedule_injection(time, rt);
+                }
+            }
+        }
+    }
+
+    /// Whether the fault affects the given module, or one of its gates.
+    pub(crate) fn targets(&self, module: &ModuleRef) -> bool {
+        match self.fault.target {
+            Target::Module(ref target) => target == module,
+            Target::Gate(ref gate) => gate.owner() == *module,
+        }
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        format!(
+            "FaultEvent {{ fault: {}, repair: {} }}",
+            self.fault.fault.action,
+            self.repair.is_some()
+        )
+    }
+}
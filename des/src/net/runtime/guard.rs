@@ -1,33 +1,28 @@
 use crate::net::{buf_drop, buf_init, module::module_ctx_drop, Globals};
-use std::sync::{Mutex, MutexGuard, TryLockError, Weak};
+use std::{cell::Cell, marker::PhantomData, sync::Weak};
 
-static GUARD: Mutex<()> = Mutex::new(());
+thread_local! {
+    static ACTIVE: Cell<bool> = const { Cell::new(false) };
+}
 
 #[derive(Debug)]
 pub(super) struct SimStaticsGuard {
-    #[allow(unused)]
-    guard: MutexGuard<'static, ()>,
+    // The statics are bound to the current thread, so the
+    // guard must not be moved to another thread.
+    _not_send: PhantomData<*const ()>,
 }
 
 impl SimStaticsGuard {
     pub(super) fn new(globals: Weak<Globals>) -> Self {
-        let guard = GUARD.try_lock();
-        let guard = match guard {
-            Ok(guard) => guard,
-            Err(e) => match e {
-                TryLockError::WouldBlock => GUARD.lock().unwrap_or_else(|e| {
-                    eprintln!("net-sim lock poisnoed: rebuilding lock");
-                    e.into_inner()
-                }),
-                TryLockError::Poisoned(poisoned) => {
-                    eprintln!("net-sim lock poisoned: rebuilding lock");
-                    poisoned.into_inner()
-                }
-            },
-        };
+        assert!(
+            !ACTIVE.with(|active| active.replace(true)),
+            "another net-sim allready exists on this thread"
+        );
 
         buf_init(globals);
-        Self { guard }
+        Self {
+            _not_send: PhantomData,
+        }
     }
 }
 
@@ -35,5 +30,6 @@ impl Drop for SimStaticsGuard {
     fn drop(&mut self) {
         buf_drop();
         module_ctx_drop();
+        ACTIVE.with(|active| active.set(false));
     }
 }
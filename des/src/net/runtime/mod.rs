@@ -1,5 +1,6 @@
 use blocks::ModuleBlock;
 use des_net_utils::props::Cfg;
+use fxhash::FxHashSet;
 use serde_yml::{from_str, Value};
 
 use crate::{
@@ -10,6 +11,7 @@ use crate::{
     },
     prelude::{Application, EventLifecycle, GateRef, Module, ModuleRef, ObjectPath, Runtime},
     runtime::RuntimeError,
+    stats::Statistics,
     time::SimTime,
     tracing::{enter_scope, leave_scope},
 };
@@ -19,13 +21,17 @@ use std::{
     ops::{self, Deref, DerefMut},
     panic::{set_hook, take_hook, PanicHookInfo},
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc, Mutex,
+    },
 };
 
 mod api;
 pub use self::api::*;
 
 mod events;
+pub use self::events::NetEvents;
 pub(crate) use self::events::*;
 
 #[cfg(feature = "async")]
@@ -37,7 +43,15 @@ pub(crate) use self::ctx::*;
 mod guard;
 use guard::SimStaticsGuard;
 
+mod checkpoint;
+pub use self::checkpoint::*;
+
 pub mod blocks;
+pub mod fault;
+use self::fault::{BoundFault, Scenario, ScenarioError};
+
+mod supervisor;
+use self::supervisor::supervise;
 
 mod unwind;
 use self::unwind::Harness;
@@ -86,6 +100,9 @@ pub struct Sim<A> {
     /// custom lifetime handlers to a simulation
     pub inner: A,
 
+    bodies: Vec<BodyCodec>,
+    faults: Vec<Arc<BoundFault>>,
+
     #[allow(unused)]
     guard: SimStaticsGuard,
 }
@@ -171,6 +188,8 @@ impl<A> Sim<A> {
             modules: globals.modules.clone(),
             guard,
             inner,
+            bodies: Vec::new(),
+            faults: Vec::new(),
             globals,
         }
         .into_builder(ProcessingStack::default)
@@ -220,11 +239,9 @@ impl<A: Debug> Debug for Sim<A> {
 
 impl<A> Drop for Sim<A> {
     fn drop(&mut self) {
-        // SAFETY: Remove ctxs, since the next use of a `Sim` may occur on
-        // a different thread
-        unsafe {
-            MOD_CTX.reset(None);
-        }
+        // SAFETY: Remove ctxs, since the module contexts of this `Sim`
+        // should not outlive the simulation.
+        MOD_CTX.with(|ctx| unsafe { ctx.reset(None) });
     }
 }
 
@@ -318,6 +335,65 @@ impl<A> SimBuilder<A> {
         Ok(())
     }
 
+    /// Includes a fault scenario in the simulation, defined in YAML.
+    ///
+    /// The faults are scheduled once the simulation starts. All modules and
+    /// gates they refer to must exist, when the scenario is included. See
+    /// [`fault`] for the scenario format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scenario is malformed, any fault is not
+    /// well defined, or refers to a module or gate that does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use des::prelude::*;
+    /// # use des::net::blocks::HandlerFn;
+    /// let mut sim = Sim::new(());
+    /// sim.node("alice", HandlerFn::new(|_| {}));
+    /// sim.include_faults("faults:\n  - crash: alice\n    at: 1.0\n    mttr: 0.5")
+    ///     .expect("invalid scenario");
+    ///
+    /// let _ = Builder::new().build(sim.freeze()).run();
+    /// ```
+    pub fn include_faults(&mut self, raw: &str) -> Result<(), ScenarioError> {
+        self.include_scenario(Scenario::from_yaml(raw)?)
+    }
+
+    /// Includes an already parsed fault scenario in the simulation.
+    ///
+    /// See [`SimBuilder::include_faults`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any fault is not well defined, or refers to
+    /// a module or gate that does not exist.
+    pub fn include_scenario(&mut self, scenario: Scenario) -> Result<(), ScenarioError> {
+        scenario.validate()?;
+        let faults = scenario
+            .faults
+            .into_iter()
+            .map(|fault| BoundFault::bind(fault, self).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.faults.extend(faults);
+        Ok(())
+    }
+
+    /// Tries to read and include a fault scenario from a file into the simulation.
+    ///
+    /// See [`SimBuilder::include_faults`] for more infomation.
+    ///
+    /// # Errors
+    ///
+    /// This function may fail if the reading from a file fails, or the
+    /// scenario is invalid.
+    pub fn include_faults_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.include_faults(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
     /// Creates a gate on a allready created module.
     ///
     /// The module will be defined `path` and the gate will be named `gate`.
@@ -589,6 +665,13 @@ where
             .iter()
             .fold(1, |acc, module| acc.max(module.num_sim_start_stages()));
 
+        // Modules spawned while starting run all their stages once spawned,
+        // so only the modules existing beforehand are started here.
+        let initial = mods
+            .iter()
+            .map(|module| module.id())
+            .collect::<FxHashSet<_>>();
+
         drop(mods);
 
         // (2.1) Call the stages in order, parallel over all modules
@@ -600,6 +683,7 @@ where
                 .lock()
                 .expect("failed")
                 .iter()
+                .filter(|module| initial.contains(&module.id()))
                 .cloned()
                 .collect::<Vec<_>>();
             for module in mods {
@@ -620,9 +704,24 @@ where
 
         leave_scope();
 
+        // (3) Schedule the fault scenario
+        let faults = rt.app.faults.clone();
+        fault::schedule(&faults, rt);
+
         A::at_sim_start(rt);
     }
 
+    fn at_warmup_end(rt: &mut Runtime<Sim<A>>) {
+        rt.app
+            .globals
+            .stats
+            .lock()
+            .expect("failed to lock statistics")
+            .reset();
+
+        A::at_warmup_end(rt);
+    }
+
     fn at_sim_end(rt: &mut Runtime<Sim<A>>) -> Result<(), RuntimeError> {
         A::at_sim_end(rt)?;
 
@@ -653,6 +752,8 @@ where
             // NOTE: no buf_process since no furthe events will be processed.
         }
 
+        rt.profiler.statistics = rt.app.globals.statistics();
+
         let _ = take_hook();
         leave_scope();
         if error.is_empty() {
@@ -699,6 +800,8 @@ fn panic_hook(info: &PanicHookInfo) {
 #[derive(Debug, Default)]
 pub struct Globals {
     pub(crate) modules: Arc<Mutex<ModuleTree>>,
+    pub(crate) stats: Mutex<Statistics>,
+    topology_version: AtomicUsize,
 }
 
 impl Globals {
@@ -712,12 +815,49 @@ impl Globals {
         self.with(|mods| Topology::from_modules(mods))
     }
 
+    /// A counter, that is incremented whenever the topology changes at runtime.
+    ///
+    /// Changes include links going up or down or being reconfigured, gates
+    /// being connected or disconnected and modules being shut down, restarted,
+    /// spawned or terminated. Components that cache information derived from the
+    /// [`topology`](Self::topology), can compare versions to detect
+    /// outdated information.
+    #[must_use]
+    pub fn topology_version(&self) -> usize {
+        self.topology_version.load(atomic::Ordering::SeqCst)
+    }
+
+    pub(crate) fn topology_changed(&self) {
+        self.topology_version.fetch_add(1, atomic::Ordering::SeqCst);
+    }
+
     /// Returns a handle to a module from the global scope.
     /// This can be used to access arbitrary modules, independent of the current execution context.
     #[must_use]
     pub fn get(&self, path: &ObjectPath) -> Option<ModuleRef> {
         self.with(|mods| mods.get(path))
     }
+
+    /// Returns a handle to a gate, where the last segment of `path` is the gate name.
+    pub(crate) fn find_gate(&self, path: &ObjectPath) -> Option<GateRef> {
+        let module = self.get(&path.parent()?)?;
+        module
+            .gates()
+            .into_iter()
+            .find(|gate| gate.str() == path.name())
+    }
+
+    /// Returns the statistics recorded by the modules so far.
+    ///
+    /// See [`stats`](crate::stats) for more information.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn statistics(&self) -> Statistics {
+        self.stats
+            .lock()
+            .expect("failed to lock statistics")
+            .clone()
+    }
 }
 
 #[derive(Debug, Default)]
@@ -758,6 +898,10 @@ impl ModuleTree {
             self.modules.push(module);
         }
     }
+
+    pub(crate) fn remove(&mut self, module: &ModuleRef) {
+        self.modules.retain(|other| other != module);
+    }
 }
 
 impl ops::Deref for ModuleTree {
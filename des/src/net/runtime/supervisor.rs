@@ -0,0 +1,111 @@
+use super::{buf_process, shut_down, PanicError, Sim};
+use crate::{
+    net::module::{ModuleRef, ModuleRefWeak, Supervision},
+    runtime::{EventLifecycle, Runtime},
+    time::SimTime,
+    tracing::enter_scope,
+};
+
+/// Informs the supervisor of a failed module, and applies its decision.
+///
+/// Should the supervisor give up, it is shut down and the failure is escalated
+/// to its own supervisor. Failures escalated beyond the root of the module tree
+/// fail the simulation.
+pub(crate) fn supervise<A>(child: &ModuleRef, err: PanicError, rt: &mut Runtime<Sim<A>>)
+where
+    A: EventLifecycle<Sim<A>>,
+{
+    let Some(parent) = child.ctx.parent.as_ref().and_then(ModuleRefWeak::upgrade) else {
+        rt.app.error.extend(Some(err));
+        return;
+    };
+
+    enter_scope(parent.scope_token());
+    if !parent.is_active() {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            "Ignoring failure of child '{}' since supervisor is inactive",
+            child.path()
+        );
+        return;
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::info!("Handling failure of child '{}': {}", child.path(), err);
+
+    parent.activate();
+    let supervision = parent.handle_child_failure(child.clone(), &err);
+    parent.deactivate(rt);
+    let supervision = match supervision {
+        Ok(supervision) => supervision,
+        Err(e) => {
+            rt.app.error.extend(Some(e));
+            return;
+        }
+    };
+
+    let restarted = match supervision {
+        Supervision::Ignore => true,
+        Supervision::Escalate => false,
+        Supervision::OneForOne => restart(std::slice::from_ref(child), rt),
+        Supervision::OneForAll => restart(&children(&parent), rt),
+        Supervision::RestForOne => {
+            let mut rest = children(&parent);
+            rest.retain(|sibling| sibling.id() >= child.id());
+            restart(&rest, rt)
+        }
+    };
+
+    buf_process(&parent, rt);
+
+    if !restarted {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Supervisor '{}' gave up, escalating failure", parent.path());
+
+        shut_down_tree(&parent, rt);
+        supervise(&parent, err, rt);
+    }
+}
+
+/// All children of a module, in the order they were created.
+fn children(module: &ModuleRef) -> Vec<ModuleRef> {
+    let mut children = module
+        .ctx
+        .children
+        .read()
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
+    children.sort_by_key(|child| child.id());
+    children
+}
+
+/// Restarts all given modules, returning `false` if any restart policy gave up.
+fn restart<A>(modules: &[ModuleRef], rt: &mut Runtime<Sim<A>>) -> bool
+where
+    A: EventLifecycle<Sim<A>>,
+{
+    let mut restarted = true;
+    for module in modules {
+        enter_scope(module.scope_token());
+        restarted &= shut_down(module, Some(SimTime::now()), rt);
+    }
+    restarted
+}
+
+/// Shuts down a supervisor that gave up, including its descendants, if
+/// required by its stereotyp.
+fn shut_down_tree<A>(module: &ModuleRef, rt: &mut Runtime<Sim<A>>)
+where
+    A: EventLifecycle<Sim<A>>,
+{
+    if module.stereotyp().on_panic_drop_submodules {
+        for child in children(module) {
+            if child.is_active() {
+                shut_down_tree(&child, rt);
+            }
+        }
+    }
+    enter_scope(module.scope_token());
+    shut_down(module, None, rt);
+}
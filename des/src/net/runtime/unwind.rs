@@ -1,4 +1,4 @@
-use crate::net::{module::ModuleContext, ObjectPath};
+use crate::net::{module::ModuleContext, Globals, ObjectPath};
 use std::{
     any::Any,
     error::Error as StdError,
@@ -45,11 +45,19 @@ impl<'a> Harness<'a> {
             // display_panic(&unwind);
 
             self.ctx.active.store(false, Ordering::SeqCst);
-            if !self.ctx.stereotyp.get().on_panic_catch {
-                return Err(PanicError {
-                    path: self.ctx.path(),
-                    payload: unwind,
-                });
+            if let Some(globals) = Globals::try_current() {
+                globals.topology_changed();
+            }
+            let error = PanicError {
+                path: self.ctx.path(),
+                payload: unwind,
+            };
+            let stereotyp = self.ctx.stereotyp.get();
+            if !stereotyp.on_panic_catch {
+                return Err(error);
+            }
+            if stereotyp.on_panic_inform_parent && self.ctx.parent.is_some() {
+                *self.ctx.failure.write() = Some(error);
             }
         }
         Ok(())
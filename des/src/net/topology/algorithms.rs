@@ -0,0 +1,879 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use fxhash::{FxBuildHasher, FxHashMap, FxHashSet};
+
+use super::{Edge, EdgeEndpoint, NodeID, Topology};
+use crate::{
+    net::{channel::ChannelRef, ObjectPath},
+    time::Duration,
+};
+
+/// A path through a [`Topology`], as a sequence of edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<'a, N, C> {
+    /// The edges of the path, in order.
+    pub edges: Vec<Edge<'a, N, C>>,
+    /// The total weight of all edges on the path.
+    pub cost: f64,
+}
+
+/// The result of a maximum flow computation, see [`Topology::max_flow`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow<'a, N, C> {
+    /// The value of the maximum flow.
+    pub value: f64,
+    /// The flow assigned to each edge, omitting edges without any flow.
+    pub flows: Vec<(Edge<'a, N, C>, f64)>,
+    /// The edges of a minimum cut, separating the source from the sink.
+    pub cut: Vec<Edge<'a, N, C>>,
+}
+
+/// An edge identified by its source node and its index in the source's bundle.
+type EdgeID = (NodeID, usize);
+
+/// The result of a single-source shortest path search.
+struct ShortestPathTree {
+    dist: Vec<f64>,
+    pred: Vec<Option<EdgeID>>,
+}
+
+#[derive(PartialEq)]
+struct QueueElement {
+    cost: f64,
+    idx: NodeID,
+}
+
+impl Eq for QueueElement {}
+
+impl Ord for QueueElement {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, since the binary heap is a max-heap
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.idx.cmp(&self.idx))
+    }
+}
+
+impl PartialOrd for QueueElement {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C> Path<'_, N, C> {
+    /// The number of hops on the path.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Indicates whether the path contains no edges, thus leading from
+    /// a node to itself.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// The module paths of all nodes on the path, including both ends.
+    #[must_use]
+    pub fn nodes(&self) -> Vec<ObjectPath> {
+        self.edges
+            .first()
+            .map(|edge| edge.from.module.path())
+            .into_iter()
+            .chain(self.edges.iter().map(|edge| edge.to.module.path()))
+            .collect()
+    }
+}
+
+impl<N, C> Edge<'_, N, C> {
+    /// The total latency of all channels on the gate chain of the edge.
+    #[must_use]
+    pub fn latency(&self) -> Duration {
+        self.channels().map(|ch| ch.metrics().latency).sum()
+    }
+
+    /// The smallest bitrate of all channels on the gate chain of the edge.
+    ///
+    /// Returns `None` if the edge is not limited by any channel.
+    #[must_use]
+    pub fn bitrate(&self) -> Option<usize> {
+        self.channels()
+            .map(|ch| ch.metrics().bitrate)
+            .filter(|bitrate| *bitrate != 0)
+            .min()
+    }
+
+    fn channels(&self) -> impl Iterator<Item = ChannelRef> {
+        self.from
+            .gate
+            .path_iter()
+            .into_iter()
+            .flatten()
+            .take(16)
+            .filter_map(|con| con.channel())
+    }
+}
+
+impl<N, C> Topology<N, C> {
+    /// Computes the shortest path between two nodes, using the given edge weights.
+    ///
+    /// Weights must not be negative. Edges with a non-finite weight, like
+    /// `f64::INFINITY`, are not used. Returns `None` if no path exists.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with the given object paths
+    /// exist in the topology object, or if a weight is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use des::prelude::*;
+    /// # fn f(topo: Topology<(), ()>) {
+    /// let topo = topo.with_edge_cost_attachment();
+    /// let path = topo.shortest_path("a", "b", |edge| {
+    ///     if edge.attachment.alive {
+    ///         edge.attachment.cost
+    ///     } else {
+    ///         f64::INFINITY
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn shortest_path<F>(
+        &self,
+        src: impl Into<ObjectPath>,
+        dst: impl Into<ObjectPath>,
+        weight: F,
+    ) -> Option<Path<'_, N, C>>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        let src = self.node_id(&src.into());
+        let dst = self.node_id(&dst.into());
+        let weights = self.weights(weight);
+        let tree = self.shortest_path_tree(src, &weights, &[], &FxHashSet::default());
+        self.path_to(&tree, dst, &weights)
+    }
+
+    /// Computes the shortest paths from a node to all reachable nodes,
+    /// using the given edge weights.
+    ///
+    /// See [`Topology::shortest_path`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with the given object path exists
+    /// in the topology object, or if a weight is negative.
+    pub fn shortest_paths<F>(
+        &self,
+        src: impl Into<ObjectPath>,
+        weight: F,
+    ) -> FxHashMap<ObjectPath, Path<'_, N, C>>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        let src = self.node_id(&src.into());
+        let weights = self.weights(weight);
+        self.shortest_paths_from(src, &weights)
+    }
+
+    /// Computes the shortest paths between all pairs of nodes, using the
+    /// given edge weights. The result maps a source node to the
+    /// shortest paths to all nodes reachable from the source.
+    ///
+    /// See [`Topology::shortest_path`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if a weight is negative.
+    pub fn all_pairs_shortest_paths<F>(
+        &self,
+        weight: F,
+    ) -> FxHashMap<ObjectPath, FxHashMap<ObjectPath, Path<'_, N, C>>>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        let weights = self.weights(weight);
+        (0..self.nodes.len())
+            .map(|src| {
+                (
+                    self.nodes[src].module.path(),
+                    self.shortest_paths_from(src, &weights),
+                )
+            })
+            .collect()
+    }
+
+    /// Computes up to `k` loopless shortest paths between two nodes, in
+    /// ascending order of their cost, using Yen's algorithm.
+    ///
+    /// See [`Topology::shortest_path`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with the given object paths
+    /// exist in the topology object, or if a weight is negative.
+    pub fn k_shortest_paths<F>(
+        &self,
+        src: impl Into<ObjectPath>,
+        dst: impl Into<ObjectPath>,
+        k: usize,
+        weight: F,
+    ) -> Vec<Path<'_, N, C>>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        let src = self.node_id(&src.into());
+        let dst = self.node_id(&dst.into());
+        let weights = self.weights(weight);
+        self.k_shortest_edge_ids(src, dst, k, &weights)
+            .into_iter()
+            .map(|path| self.path_from_ids(&path, &weights))
+            .collect()
+    }
+
+    /// Computes a minimum spanning tree, using the given edge weights.
+    ///
+    /// Edges are considered undirected, so for each link of the tree only one
+    /// directed edge is returned. If the topology is not connected, a minimum
+    /// spanning forest is returned. Edges with a non-finite weight are not used.
+    pub fn minimum_spanning_tree<F>(&self, weight: F) -> Vec<Edge<'_, N, C>>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        fn find(parent: &mut [NodeID], i: NodeID) -> NodeID {
+            if parent[i] != i {
+                parent[i] = find(parent, parent[i]);
+            }
+            parent[i]
+        }
+
+        let weights = self.weights(weight);
+        let mut edges = self
+            .edge_ids()
+            .filter(|&(i, k)| weights[i][k].is_finite())
+            .collect::<Vec<_>>();
+        edges.sort_by(|&(i, k), &(j, l)| weights[i][k].total_cmp(&weights[j][l]));
+
+        // Kruskal using a union-find structure
+        let mut parent = (0..self.nodes.len()).collect::<Vec<_>>();
+        let mut tree = Vec::new();
+        for (i, k) in edges {
+            let from = find(&mut parent, i);
+            let to = find(&mut parent, self.edges[i][k].dst);
+            if from != to {
+                parent[from] = to;
+                tree.push(self.edge((i, k)));
+            }
+        }
+        tree
+    }
+
+    /// Computes the maximum flow between two nodes, as well as a minimum cut,
+    /// using the Edmonds-Karp algorithm.
+    ///
+    /// The capacity of each edge is defined by the given closure, for example
+    /// using the [`bitrate`](Edge::bitrate) of the edge. Edges with an infinite
+    /// capacity are allowed. If a path of such edges exists between both nodes,
+    /// the flow is infinite.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with the given object paths
+    /// exist in the topology object, or if a capacity is negative.
+    pub fn max_flow<F>(
+        &self,
+        src: impl Into<ObjectPath>,
+        dst: impl Into<ObjectPath>,
+        capacity: F,
+    ) -> MaxFlow<'_, N, C>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        let src = self.node_id(&src.into());
+        let dst = self.node_id(&dst.into());
+        let capacities = self.weights(capacity);
+        self.max_flow_by_id(src, dst, &capacities)
+    }
+
+    /// Computes the closeness centrality of all nodes, using the given edge weights.
+    ///
+    /// The closeness of a node is the inverse of the average distance to all nodes
+    /// reachable from it. To compare nodes in disconnected topologies, the
+    /// closeness is scaled by the fraction of nodes reachable (Wasserman and Faust).
+    /// Nodes that cannot reach any other node have a closeness of zero.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if a weight is negative.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn closeness_centrality<F>(&self, weight: F) -> FxHashMap<ObjectPath, f64>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        let n = self.nodes.len();
+        let weights = self.weights(weight);
+        (0..n)
+            .map(|src| {
+                let tree = self.shortest_path_tree(src, &weights, &[], &FxHashSet::default());
+                let reachable = tree.dist.iter().filter(|d| d.is_finite()).count() - 1;
+                let total = tree.dist.iter().filter(|d| d.is_finite()).sum::<f64>();
+
+                let closeness = if reachable == 0 || total == 0.0 {
+                    0.0
+                } else {
+                    (reachable as f64 / total) * (reachable as f64 / (n - 1) as f64)
+                };
+                (self.nodes[src].module.path(), closeness)
+            })
+            .collect()
+    }
+
+    /// Computes the betweenness centrality of all nodes, using the given
+    /// edge weights and Brandes' algorithm.
+    ///
+    /// The betweenness of a node is the sum of the fractions of shortest paths
+    /// between all other (ordered) pairs of nodes, that pass through the node.
+    /// Note that in bidirectional topologies, each unordered pair of nodes is
+    /// thus counted twice.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if a weight is negative.
+    pub fn betweenness_centrality<F>(&self, weight: F) -> FxHashMap<ObjectPath, f64>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        let weights = self.weights(weight);
+        self.nodes
+            .iter()
+            .zip(self.betweenness_by_id(&weights))
+            .map(|(node, betweenness)| (node.module.path(), betweenness))
+            .collect()
+    }
+
+    // ==== internals ====
+
+    /// Yen's algorithm, returning the paths as edge identifiers.
+    fn k_shortest_edge_ids(
+        &self,
+        src: NodeID,
+        dst: NodeID,
+        k: usize,
+        weights: &[Vec<f64>],
+    ) -> Vec<Vec<EdgeID>> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let cost = |path: &[EdgeID]| path.iter().map(|&(i, k)| weights[i][k]).sum::<f64>();
+
+        let tree = self.shortest_path_tree(src, weights, &[], &FxHashSet::default());
+        let Some(first) = Self::edge_ids_to(&tree, dst) else {
+            return Vec::new();
+        };
+
+        let mut found = vec![first];
+        let mut candidates = Vec::<Vec<EdgeID>>::new();
+        while found.len() < k {
+            let prev = found.last().expect("at least one path");
+            for i in 0..prev.len() {
+                let root = &prev[..i];
+                let spur = prev[i].0;
+
+                // Remove all edges, that would continue a known path with the same root
+                let banned_edges = found
+                    .iter()
+                    .filter(|path| path.len() > i && path[..i] == *root)
+                    .map(|path| path[i])
+                    .collect::<FxHashSet<_>>();
+
+                // Remove all nodes on the root, to prevent loops
+                let mut banned_nodes = vec![false; self.nodes.len()];
+                for &(node, _) in root {
+                    banned_nodes[node] = true;
+                }
+
+                let tree = self.shortest_path_tree(spur, weights, &banned_nodes, &banned_edges);
+                if let Some(spur_path) = Self::edge_ids_to(&tree, dst) {
+                    let mut candidate = root.to_vec();
+                    candidate.extend(spur_path);
+                    if !candidates.contains(&candidate) && !found.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+
+            // Select the cheapest candidate, preferring older candidates on ties
+            let Some(best) = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, lhs), (_, rhs)| cost(lhs).total_cmp(&cost(rhs)))
+                .map(|(idx, _)| idx)
+            else {
+                break;
+            };
+            found.push(candidates.remove(best));
+        }
+
+        found
+    }
+
+    /// Edmonds-Karp, using node identifiers.
+    fn max_flow_by_id(
+        &self,
+        src: NodeID,
+        dst: NodeID,
+        capacities: &[Vec<f64>],
+    ) -> MaxFlow<'_, N, C> {
+        let mut flow = capacities
+            .iter()
+            .map(|bundle| vec![0.0; bundle.len()])
+            .collect::<Vec<_>>();
+
+        let mut incoming = vec![Vec::new(); self.nodes.len()];
+        for (i, k) in self.edge_ids() {
+            incoming[self.edges[i][k].dst].push((i, k));
+        }
+
+        let mut value = 0.0;
+        if src != dst {
+            loop {
+                // Search the shortest augmenting path in the residual graph.
+                // Each step is an edge, and whether it is used backwards.
+                let mut pred = vec![None::<(EdgeID, bool)>; self.nodes.len()];
+                let mut queue = VecDeque::from([src]);
+                while let Some(cur) = queue.pop_front() {
+                    let forward = (0..self.edges[cur].len())
+                        .filter(|&k| capacities[cur][k] - flow[cur][k] > 0.0)
+                        .map(|k| ((cur, k), self.edges[cur][k].dst, false));
+                    let backward = incoming[cur]
+                        .iter()
+                        .filter(|&&(i, k)| flow[i][k] > 0.0)
+                        .map(|&(i, k)| ((i, k), i, true));
+
+                    for (edge, next, reverse) in forward.chain(backward) {
+                        if next != src && pred[next].is_none() {
+                            pred[next] = Some((edge, reverse));
+                            queue.push_back(next);
+                        }
+                    }
+                }
+
+                let mut steps = Vec::new();
+                let mut cur = dst;
+                while let Some(((i, k), reverse)) = pred[cur] {
+                    steps.push(((i, k), reverse));
+                    cur = if reverse { self.edges[i][k].dst } else { i };
+                }
+                if cur != src || steps.is_empty() {
+                    break;
+                }
+
+                let bottleneck = steps
+                    .iter()
+                    .map(|&((i, k), reverse)| {
+                        if reverse {
+                            flow[i][k]
+                        } else {
+                            capacities[i][k] - flow[i][k]
+                        }
+                    })
+                    .fold(f64::INFINITY, f64::min);
+
+                if bottleneck.is_infinite() {
+                    value = f64::INFINITY;
+                    break;
+                }
+
+                for ((i, k), reverse) in steps {
+                    if reverse {
+                        flow[i][k] -= bottleneck;
+                    } else {
+                        flow[i][k] += bottleneck;
+                    }
+                }
+                value += bottleneck;
+            }
+        }
+
+        // The source side of the cut is reachable in the residual graph
+        let mut reachable = vec![false; self.nodes.len()];
+        let mut stack = vec![src];
+        while let Some(cur) = stack.pop() {
+            if std::mem::replace(&mut reachable[cur], true) {
+                continue;
+            }
+            for k in 0..self.edges[cur].len() {
+                if capacities[cur][k] - flow[cur][k] > 0.0 {
+                    stack.push(self.edges[cur][k].dst);
+                }
+            }
+            for &(i, k) in &incoming[cur] {
+                if flow[i][k] > 0.0 {
+                    stack.push(i);
+                }
+            }
+        }
+
+        MaxFlow {
+            value,
+            flows: self
+                .edge_ids()
+                .filter(|&(i, k)| flow[i][k] > 0.0)
+                .map(|(i, k)| (self.edge((i, k)), flow[i][k]))
+                .collect(),
+            cut: if value.is_infinite() {
+                Vec::new()
+            } else {
+                self.edge_ids()
+                    .filter(|&(i, k)| reachable[i] && !reachable[self.edges[i][k].dst])
+                    .map(|id| self.edge(id))
+                    .collect()
+            },
+        }
+    }
+
+    /// Brandes' algorithm, using node identifiers.
+    fn betweenness_by_id(&self, weights: &[Vec<f64>]) -> Vec<f64> {
+        const EPSILON: f64 = 1e-9;
+
+        let n = self.nodes.len();
+        let mut betweenness = vec![0.0; n];
+
+        for src in 0..n {
+            let mut order = Vec::with_capacity(n);
+            let mut preds = vec![Vec::new(); n];
+            let mut sigma = vec![0.0f64; n];
+            let mut dist = vec![f64::INFINITY; n];
+            let mut done = vec![false; n];
+
+            sigma[src] = 1.0;
+            dist[src] = 0.0;
+            let mut queue = BinaryHeap::from([QueueElement {
+                cost: 0.0,
+                idx: src,
+            }]);
+            while let Some(QueueElement { cost, idx }) = queue.pop() {
+                if std::mem::replace(&mut done[idx], true) {
+                    continue;
+                }
+                order.push(idx);
+
+                for (k, raw) in self.edges[idx].iter().enumerate() {
+                    let next = cost + weights[idx][k];
+                    if !next.is_finite() || done[raw.dst] {
+                        continue;
+                    }
+                    if next < dist[raw.dst] - EPSILON * next.abs().max(1.0) {
+                        dist[raw.dst] = next;
+                        sigma[raw.dst] = 0.0;
+                        preds[raw.dst].clear();
+                        queue.push(QueueElement {
+                            cost: next,
+                            idx: raw.dst,
+                        });
+                    }
+                    if (next - dist[raw.dst]).abs() <= EPSILON * next.abs().max(1.0) {
+                        sigma[raw.dst] += sigma[idx];
+                        preds[raw.dst].push(idx);
+                    }
+                }
+            }
+
+            let mut delta = vec![0.0; n];
+            for &w in order.iter().rev() {
+                for &v in &preds[w] {
+                    delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+                }
+                if w != src {
+                    betweenness[w] += delta[w];
+                }
+            }
+        }
+
+        betweenness
+    }
+
+    fn node_id(&self, path: &ObjectPath) -> NodeID {
+        self.nodes
+            .iter()
+            .position(|node| node.module.path == *path)
+            .unwrap_or_else(|| panic!("unknown node '{path}'"))
+    }
+
+    fn edge_ids(&self) -> impl Iterator<Item = EdgeID> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(i, bundle)| (0..bundle.len()).map(move |k| (i, k)))
+    }
+
+    fn edge(&self, (src, k): EdgeID) -> Edge<'_, N, C> {
+        let raw = &self.edges[src][k];
+        Edge {
+            from: EdgeEndpoint {
+                node: &self.nodes[src],
+                gate: &raw.start,
+                id: src,
+            },
+            to: EdgeEndpoint {
+                node: &self.nodes[raw.dst],
+                gate: &raw.end,
+                id: raw.dst,
+            },
+            attachment: &raw.data,
+        }
+    }
+
+    fn weights<F>(&self, mut weight: F) -> Vec<Vec<f64>>
+    where
+        F: FnMut(Edge<'_, N, C>) -> f64,
+    {
+        (0..self.nodes.len())
+            .map(|src| {
+                self.edges_by_id(src)
+                    .map(|edge| {
+                        let (from, to) = (edge.from.id, edge.to.id);
+                        let w = weight(edge);
+                        assert!(
+                            w >= 0.0 || w.is_nan(),
+                            "edge weights must not be negative, found {w} for edge {} -> {}",
+                            self.nodes[from].module.path(),
+                            self.nodes[to].module.path()
+                        );
+                        w
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Dijkstra's algorithm, ignoring banned nodes and edges.
+    fn shortest_path_tree(
+        &self,
+        src: NodeID,
+        weights: &[Vec<f64>],
+        banned_nodes: &[bool],
+        banned_edges: &FxHashSet<EdgeID>,
+    ) -> ShortestPathTree {
+        let n = self.nodes.len();
+        let banned = |idx: NodeID| banned_nodes.get(idx).copied().unwrap_or(false);
+
+        let mut tree = ShortestPathTree {
+            dist: vec![f64::INFINITY; n],
+            pred: vec![None; n],
+        };
+        tree.dist[src] = 0.0;
+
+        let mut done = vec![false; n];
+        let mut queue = BinaryHeap::from([QueueElement {
+            cost: 0.0,
+            idx: src,
+        }]);
+        while let Some(QueueElement { cost, idx }) = queue.pop() {
+            if std::mem::replace(&mut done[idx], true) {
+                continue;
+            }
+
+            for (k, raw) in self.edges[idx].iter().enumerate() {
+                let next = cost + weights[idx][k];
+                if !next.is_finite()
+                    || banned(raw.dst)
+                    || banned_edges.contains(&(idx, k))
+                    || next >= tree.dist[raw.dst]
+                {
+                    continue;
+                }
+                tree.dist[raw.dst] = next;
+                tree.pred[raw.dst] = Some((idx, k));
+                queue.push(QueueElement {
+                    cost: next,
+                    idx: raw.dst,
+                });
+            }
+        }
+        tree
+    }
+
+    fn edge_ids_to(tree: &ShortestPathTree, dst: NodeID) -> Option<Vec<EdgeID>> {
+        if !tree.dist[dst].is_finite() {
+            return None;
+        }
+        let mut path = Vec::new();
+        let mut cur = dst;
+        while let Some((src, k)) = tree.pred[cur] {
+            path.push((src, k));
+            cur = src;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    fn path_to(
+        &self,
+        tree: &ShortestPathTree,
+        dst: NodeID,
+        weights: &[Vec<f64>],
+    ) -> Option<Path<'_, N, C>> {
+        Self::edge_ids_to(tree, dst).map(|ids| self.path_from_ids(&ids, weights))
+    }
+
+    fn path_from_ids(&self, ids: &[EdgeID], weights: &[Vec<f64>]) -> Path<'_, N, C> {
+        Path {
+            edges: ids.iter().map(|&id| self.edge(id)).collect(),
+            cost: ids.iter().map(|&(i, k)| weights[i][k]).sum(),
+        }
+    }
+
+    fn shortest_paths_from(
+        &self,
+        src: NodeID,
+        weights: &[Vec<f64>],
+    ) -> FxHashMap<ObjectPath, Path<'_, N, C>> {
+        let tree = self.shortest_path_tree(src, weights, &[], &FxHashSet::default());
+        let mut paths = FxHashMap::with_hasher(FxBuildHasher::default());
+        for dst in 0..self.nodes.len() {
+            if let Some(path) = self.path_to(&tree, dst, weights) {
+                paths.insert(self.nodes[dst].module.path(), path);
+            }
+        }
+        paths
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    // 0 --1-- 1 --6-- 3
+    //  \      |      /
+    //   4     2     1
+    //    \    |    /
+    //     \-- 2 --/
+    fn diamond() -> (Topology<usize, ()>, Vec<Vec<f64>>) {
+        let topo = Topology::raw(&[&[1, 2], &[0, 2, 3], &[0, 1, 3], &[1, 2]]);
+        let weights = topo.weights(diamond_weight);
+        (topo, weights)
+    }
+
+    fn diamond_weight(edge: Edge<'_, usize, ()>) -> f64 {
+        match (edge.from.id.min(edge.to.id), edge.from.id.max(edge.to.id)) {
+            (0, 1) | (2, 3) => 1.0,
+            (1, 2) => 2.0,
+            (0, 2) => 4.0,
+            (1, 3) => 6.0,
+            _ => unreachable!(),
+        }
+    }
+
+    fn hops(topo: &Topology<usize, ()>, ids: &[EdgeID]) -> Vec<NodeID> {
+        let mut hops = vec![ids[0].0];
+        hops.extend(ids.iter().map(|&(i, k)| topo.edges[i][k].dst));
+        hops
+    }
+
+    #[test]
+    fn weighted_shortest_paths() {
+        let (topo, weights) = diamond();
+        let tree = topo.shortest_path_tree(0, &weights, &[], &FxHashSet::default());
+        assert_eq!(tree.dist, [0.0, 1.0, 3.0, 4.0]);
+
+        let ids = Topology::<usize, ()>::edge_ids_to(&tree, 3).unwrap();
+        assert_eq!(hops(&topo, &ids), [0, 1, 2, 3]);
+        assert_eq!(topo.path_from_ids(&ids, &weights).cost, 4.0);
+    }
+
+    #[test]
+    fn unusable_edges_are_skipped() {
+        let (topo, mut weights) = diamond();
+        weights[1][2] = f64::INFINITY; // 1 -> 3
+        weights[2][2] = f64::INFINITY; // 2 -> 3
+        let tree = topo.shortest_path_tree(0, &weights, &[], &FxHashSet::default());
+        assert!(Topology::<usize, ()>::edge_ids_to(&tree, 3).is_none());
+    }
+
+    #[test]
+    #[should_panic = "edge weights must not be negative"]
+    fn negative_weights_panic() {
+        let topo = Topology::raw(&[&[1], &[0]]);
+        let _ = topo.weights(|_| -1.0);
+    }
+
+    #[test]
+    fn yen_k_shortest_paths() {
+        let (topo, weights) = diamond();
+        let paths = topo
+            .k_shortest_edge_ids(0, 3, 10, &weights)
+            .into_iter()
+            .map(|ids| (hops(&topo, &ids), topo.path_from_ids(&ids, &weights).cost))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                (vec![0, 1, 2, 3], 4.0),
+                (vec![0, 2, 3], 5.0),
+                (vec![0, 1, 3], 7.0),
+                (vec![0, 2, 1, 3], 12.0),
+            ]
+        );
+
+        assert_eq!(topo.k_shortest_edge_ids(0, 3, 2, &weights).len(), 2);
+        assert!(topo.k_shortest_edge_ids(0, 3, 0, &weights).is_empty());
+    }
+
+    #[test]
+    fn spanning_tree() {
+        let (topo, _) = diamond();
+        let mut tree = topo
+            .minimum_spanning_tree(diamond_weight)
+            .into_iter()
+            .map(|edge| (edge.from.id.min(edge.to.id), edge.from.id.max(edge.to.id)))
+            .collect::<Vec<_>>();
+        tree.sort_unstable();
+        assert_eq!(tree, [(0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn max_flow_and_min_cut() {
+        let (topo, capacities) = diamond();
+        let flow = topo.max_flow_by_id(0, 3, &capacities);
+        // 0 -> 1 -> 3, 0 -> 2 -> 3 and 0 -> 2 -> 1 -> 3
+        assert_eq!(flow.value, 4.0);
+
+        let mut cut = flow
+            .cut
+            .iter()
+            .map(|edge| (edge.from.id, edge.to.id))
+            .collect::<Vec<_>>();
+        cut.sort_unstable();
+        assert_eq!(cut, [(0, 1), (2, 1), (2, 3)]);
+
+        let outflow = flow
+            .flows
+            .iter()
+            .filter(|(edge, _)| edge.from.id == 0)
+            .map(|(_, flow)| flow)
+            .sum::<f64>();
+        assert_eq!(outflow, 4.0);
+    }
+
+    #[test]
+    fn betweenness() {
+        // 0 -- 1 -- 2
+        let topo = Topology::raw(&[&[1], &[0, 2], &[1]]);
+        let weights = topo.weights(|_| 1.0);
+        assert_eq!(topo.betweenness_by_id(&weights), [0.0, 2.0, 0.0]);
+
+        // two equal paths 0 -> 3, via 1 and 2
+        let topo = Topology::raw(&[&[1, 2], &[3], &[3], &[]]);
+        let weights = topo.weights(|_| 1.0);
+        assert_eq!(topo.betweenness_by_id(&weights), [0.0, 0.5, 0.5, 0.0]);
+    }
+}
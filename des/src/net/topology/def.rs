@@ -0,0 +1,412 @@
+use std::{error::Error, fmt};
+
+use des_net_utils::ndl::error::ErrorKind;
+use fxhash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    net::{
+        channel::{Channel, ChannelDropBehaviour, ChannelMetrics, ChannelRef, DelayModel},
+        gate::GateKind,
+        ndl::{Layer, Registry},
+        ObjectPath, SimBuilder,
+    },
+    time::Duration,
+};
+
+/// The registry symbol used for nodes, that do not define a `type` attribute.
+const DEFAULT_SYMBOL: &str = "Node";
+
+/// The name of gates created for links, that do not define the gates they use.
+const DEFAULT_GATE: &str = "port";
+
+/// An error that occured while importing a topology.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    /// The document is malformed, or does not describe a valid network.
+    Parse(String),
+    /// The document refers to a node that does not exist.
+    Missing(String),
+    /// The registry failed to provide software for a node.
+    Registry(String),
+    /// A gate is invalid, or used by multiple links.
+    Gate(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(s) => write!(f, "invalid topology document: {s}"),
+            Self::Missing(s) => write!(f, "topology refers to missing node: {s}"),
+            Self::Registry(s) => write!(f, "registry failed to provide software: {s}"),
+            Self::Gate(s) => write!(f, "invalid gate: {s}"),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+/// A format-independent description of a network.
+#[derive(Debug)]
+pub(super) struct GraphDef {
+    pub(super) directed: bool,
+    pub(super) nodes: Vec<NodeDef>,
+    pub(super) edges: Vec<EdgeDef>,
+}
+
+#[derive(Debug)]
+pub(super) struct NodeDef {
+    pub(super) id: String,
+    pub(super) typ: Option<String>,
+}
+
+#[derive(Debug)]
+pub(super) struct EdgeDef {
+    pub(super) source: String,
+    pub(super) target: String,
+    pub(super) source_gate: Option<String>,
+    pub(super) target_gate: Option<String>,
+    pub(super) channel: Option<ChannelDef>,
+}
+
+/// The exchange format of a [`Channel`], using the field names of NDL links.
+///
+/// Durations are defined in seconds. The drop behaviour is one of `drop`,
+/// `queue` or `queue(<size>)`. Custom delay models cannot be represented,
+/// so channels using one are exported without a `delay-model`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub(super) struct ChannelDef {
+    bitrate: usize,
+    latency: f64,
+    jitter: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delay_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pareto_shape: Option<f64>,
+    fifo: bool,
+    drop_behaviour: String,
+}
+
+impl Default for ChannelDef {
+    fn default() -> Self {
+        Self::from(ChannelMetrics::new(
+            0,
+            Duration::ZERO,
+            Duration::ZERO,
+            ChannelDropBehaviour::Drop,
+        ))
+    }
+}
+
+impl ChannelDef {
+    const KEYS: [&'static str; 7] = [
+        "bitrate",
+        "latency",
+        "jitter",
+        "delay-model",
+        "pareto-shape",
+        "fifo",
+        "drop-behaviour",
+    ];
+
+    /// Extracts a channel from the attributes of an edge.
+    ///
+    /// Edges without any channel attributes are not backed by a channel,
+    /// unless they define the bitrate using `LinkSpeedRaw`, like
+    /// the graphs of the Internet Topology Zoo.
+    pub(super) fn from_attributes(attrs: &Map<String, Value>) -> Result<Option<Self>, ImportError> {
+        if Self::KEYS.iter().any(|key| attrs.contains_key(*key)) {
+            serde_json::from_value(Value::Object(attrs.clone()))
+                .map(Some)
+                .map_err(|e| ImportError::Parse(format!("invalid channel: {e}")))
+        } else if let Some(speed) = attrs.get("LinkSpeedRaw").and_then(Value::as_f64) {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Ok(Some(Self {
+                bitrate: speed as usize,
+                ..Self::default()
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl From<&Channel> for ChannelDef {
+    fn from(channel: &Channel) -> Self {
+        let (delay_model, pareto_shape) = match channel.delay_model() {
+            DelayModel::Uniform => (Some("uniform"), None),
+            DelayModel::Normal => (Some("normal"), None),
+            DelayModel::Exponential => (Some("exponential"), None),
+            DelayModel::Pareto(shape) => (Some("pareto"), Some(shape)),
+            DelayModel::Custom(_) => (None, None),
+        };
+        Self {
+            delay_model: delay_model.map(str::to_string),
+            pareto_shape,
+            fifo: channel.is_fifo(),
+            ..Self::from(channel.metrics())
+        }
+    }
+}
+
+impl From<ChannelMetrics> for ChannelDef {
+    fn from(metrics: ChannelMetrics) -> Self {
+        Self {
+            bitrate: metrics.bitrate,
+            latency: metrics.latency.as_secs_f64(),
+            jitter: metrics.jitter.as_secs_f64(),
+            delay_model: Some("uniform".to_string()),
+            pareto_shape: None,
+            fifo: false,
+            drop_behaviour: match metrics.drop_behaviour {
+                ChannelDropBehaviour::Drop => "drop".to_string(),
+                ChannelDropBehaviour::Queue(None) => "queue".to_string(),
+                ChannelDropBehaviour::Queue(Some(size)) => format!("queue({size})"),
+            },
+        }
+    }
+}
+
+impl TryFrom<&ChannelDef> for ChannelMetrics {
+    type Error = ImportError;
+    fn try_from(def: &ChannelDef) -> Result<Self, Self::Error> {
+        let duration = |secs: f64| {
+            Duration::try_from_secs_f64(secs)
+                .map_err(|e| ImportError::Parse(format!("invalid channel duration: {e}")))
+        };
+        let drop_behaviour = match def.drop_behaviour.as_str() {
+            "drop" => ChannelDropBehaviour::Drop,
+            "queue" => ChannelDropBehaviour::Queue(None),
+            other => ChannelDropBehaviour::Queue(Some(
+                other
+                    .strip_prefix("queue(")
+                    .and_then(|s| s.strip_suffix(')'))
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| {
+                        ImportError::Parse(format!("unsupported drop behaviour '{other}'"))
+                    })?,
+            )),
+        };
+
+        Ok(ChannelMetrics::new(
+            def.bitrate,
+            duration(def.latency)?,
+            duration(def.jitter)?,
+            drop_behaviour,
+        ))
+    }
+}
+
+impl TryFrom<&ChannelDef> for ChannelRef {
+    type Error = ImportError;
+    fn try_from(def: &ChannelDef) -> Result<Self, Self::Error> {
+        let delay_model = match def.delay_model.as_deref() {
+            None | Some("uniform") => DelayModel::Uniform,
+            Some("normal") => DelayModel::Normal,
+            Some("exponential") => DelayModel::Exponential,
+            Some("pareto") => DelayModel::Pareto(def.pareto_shape.unwrap_or(2.0)),
+            Some(other) => {
+                return Err(ImportError::Parse(format!(
+                    "unsupported delay model '{other}'"
+                )))
+            }
+        };
+
+        let channel = Channel::new(ChannelMetrics::try_from(def)?);
+        channel.set_delay_model(delay_model);
+        channel.set_fifo(def.fifo);
+        Ok(channel)
+    }
+}
+
+/// Splits a gate identifier like `port[2]` into its name and position.
+fn parse_gate(s: &str) -> Result<(&str, Option<usize>), ImportError> {
+    let Some((name, pos)) = s.strip_suffix(']').and_then(|s| s.split_once('[')) else {
+        return Ok((s, None));
+    };
+    let pos = pos
+        .parse()
+        .map_err(|_| ImportError::Gate(format!("invalid gate identifier '{s}'")))?;
+    Ok((name, Some(pos)))
+}
+
+impl GraphDef {
+    /// Returns the links of the network. In directed graphs, two edges
+    /// describing both directions of the same link are merged.
+    fn links(&self) -> Vec<&EdgeDef> {
+        if !self.directed {
+            return self.edges.iter().collect();
+        }
+
+        let mut merged = vec![false; self.edges.len()];
+        let mut links = Vec::new();
+        for (i, edge) in self.edges.iter().enumerate() {
+            if merged[i] {
+                continue;
+            }
+            let reverse = self
+                .edges
+                .iter()
+                .enumerate()
+                .skip(i + 1)
+                .find(|(j, other)| {
+                    !merged[*j]
+                        && other.source == edge.target
+                        && other.target == edge.source
+                        && other.source_gate == edge.target_gate
+                        && other.target_gate == edge.source_gate
+                });
+            if let Some((j, _)) = reverse {
+                merged[j] = true;
+            }
+            links.push(edge);
+        }
+        links
+    }
+}
+
+impl<A> SimBuilder<A> {
+    /// Creates nodes, gates and connections from a graph, using `registry`
+    /// to attach software to the nodes.
+    ///
+    /// The whole graph is validated before the simulation is modified,
+    /// so that no partial network remains, if the import fails.
+    pub(super) fn nodes_from_graph<L: Layer>(
+        &mut self,
+        def: &GraphDef,
+        registry: &mut Registry<L>,
+    ) -> Result<(), ImportError> {
+        let nodes = self.validate_nodes(def, registry)?;
+
+        // (1) Assign gates to all link endpoints, using the next free
+        // default gate, if the link does not define the gate
+        let links = def.links();
+        let mut free = FxHashMap::<&str, usize>::default();
+        let mut used = FxHashSet::<(&str, &str, usize)>::default();
+        let mut endpoints = Vec::with_capacity(links.len());
+        for link in &links {
+            let mut pair = Vec::with_capacity(2);
+            for (node, gate) in [
+                (&link.source, &link.source_gate),
+                (&link.target, &link.target_gate),
+            ] {
+                let path = ObjectPath::from(node);
+                let module = self.get(&path);
+                if module.is_none() && !nodes.contains(&path) {
+                    return Err(ImportError::Missing(format!("'{node}' used by link")));
+                }
+                let (name, pos) = if let Some(gate) = gate {
+                    parse_gate(gate)?
+                } else {
+                    let slot = free.entry(node.as_str()).or_default();
+                    *slot += 1;
+                    (DEFAULT_GATE, Some(*slot - 1))
+                };
+
+                if !used.insert((node.as_str(), name, pos.unwrap_or(0))) {
+                    return Err(ImportError::Gate(format!(
+                        "'{node}.{name}[{}]' is used by multiple links",
+                        pos.unwrap_or(0)
+                    )));
+                }
+                if let Some(module) = module.filter(|module| module.gate(name, 0).is_some()) {
+                    let gate = module.gate(name, pos.unwrap_or(0)).ok_or_else(|| {
+                        ImportError::Gate(format!("'{node}.{name}' does not exist"))
+                    })?;
+                    if gate.kind() != GateKind::Standalone || gate.medium().is_some() {
+                        return Err(ImportError::Gate(format!(
+                            "'{}' is used by multiple links",
+                            gate.path()
+                        )));
+                    }
+                }
+                pair.push((node.as_str(), name, pos));
+            }
+            endpoints.push([pair[0], pair[1]]);
+        }
+
+        // (2) Create the channels of all links
+        let channels = links
+            .iter()
+            .map(|link| link.channel.as_ref().map(ChannelRef::try_from).transpose())
+            .collect::<Result<Vec<_>, ImportError>>()?;
+
+        // (3) Create all nodes
+        for node in &def.nodes {
+            let path = ObjectPath::from(node.id.as_str());
+            let symbol = node.typ.as_deref().unwrap_or(DEFAULT_SYMBOL);
+            self.raw_ndl(&path, symbol, registry)
+                .map_err(|e| ImportError::Registry(e.to_string()))?;
+        }
+
+        // (4) Create gate clusters large enough for all used positions
+        let mut sizes = FxHashMap::<(&str, &str), Option<usize>>::default();
+        for (node, name, pos) in endpoints.iter().flatten() {
+            let size = sizes.entry((node, name)).or_default();
+            *size = (*size).max(pos.map(|pos| pos + 1));
+        }
+        for ((node, name), size) in sizes {
+            let module = self
+                .get(&ObjectPath::from(node))
+                .expect("node was created previously");
+            if module.gate(name, 0).is_some() {
+                continue;
+            }
+            match size {
+                Some(size) => {
+                    let _ = module.create_gate_cluster(name, size);
+                }
+                None => {
+                    let _ = module.create_gate(name);
+                }
+            }
+        }
+
+        // (5) Connect the gates
+        for ([from, to], channel) in endpoints.into_iter().zip(channels) {
+            let [from, to] = [from, to].map(|(node, name, pos)| {
+                self.get(&ObjectPath::from(node))
+                    .and_then(|module| module.gate(name, pos.unwrap_or(0)))
+                    .expect("gate was validated previously")
+            });
+            from.connect(to, channel);
+        }
+
+        Ok(())
+    }
+
+    /// Validates the nodes of a graph, returning the paths of all nodes.
+    /// Parents must be defined before their children.
+    fn validate_nodes<L: Layer>(
+        &self,
+        def: &GraphDef,
+        registry: &Registry<L>,
+    ) -> Result<FxHashSet<ObjectPath>, ImportError> {
+        let mut nodes = FxHashSet::<ObjectPath>::default();
+        for node in &def.nodes {
+            let path = ObjectPath::from(node.id.as_str());
+            if self.get(&path).is_some() || nodes.contains(&path) {
+                return Err(ImportError::Parse(format!("duplicate node '{path}'")));
+            }
+            if let Some(parent) = path.nonzero_parent() {
+                if self.get(&parent).is_none() && !nodes.contains(&parent) {
+                    return Err(ImportError::Missing(format!(
+                        "parent '{parent}' of node '{path}'"
+                    )));
+                }
+            }
+
+            let symbol = node.typ.as_deref().unwrap_or(DEFAULT_SYMBOL);
+            if !registry.contains(symbol) {
+                return Err(ImportError::Registry(
+                    ErrorKind::MissingRegistrySymbol(path.to_string(), symbol.to_string())
+                        .to_string(),
+                ));
+            }
+            nodes.insert(path);
+        }
+        Ok(nodes)
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt::Write;
+
+use fxhash::FxHashMap;
+use roxmltree::{Document, Node, ParsingOptions};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::{
+    def::{ChannelDef, EdgeDef, GraphDef, ImportError, NodeDef},
+    Topology,
+};
+use crate::net::{
+    ndl::{Layer, Registry},
+    SimBuilder,
+};
+
+impl<N: Serialize, C: Serialize> Topology<N, C> {
+    /// Exports the current topology object as a `GraphML` document.
+    ///
+    /// Nodes are identified by their module path. Each edge refers to the
+    /// gates it is attached to as `sourceport` and `targetport`. Node and
+    /// edge attachments are exported as data attributes, using the field
+    /// names of the attachment, or `data` if the attachment is not a struct.
+    /// The metrics of the channel of an edge are exported using the
+    /// field names of NDL links, like `bitrate` or `latency`. Custom
+    /// delay models are not exported.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the attachments cannot be represented as JSON.
+    #[must_use]
+    pub fn as_graphml(&self) -> String {
+        let mut keys = Keys::default();
+
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(id, node)| {
+                let mut ports = self
+                    .edges_by_id(id)
+                    .map(|edge| edge.from.gate.str())
+                    .chain(
+                        self.edges()
+                            .filter(|edge| edge.to.id == id)
+                            .map(|edge| edge.to.gate.str()),
+                    )
+                    .collect::<Vec<_>>();
+                ports.sort();
+                ports.dedup();
+                (node, ports, keys.data("node", &node.data))
+            })
+            .collect::<Vec<_>>();
+
+        let edges = self
+            .edges()
+            .map(|edge| {
+                let mut data = keys.data("edge", edge.attachment);
+                if let Some(channel) = edge.from.gate.channel() {
+                    data.extend(keys.data("edge", &ChannelDef::from(&*channel)));
+                }
+                (edge, data)
+            })
+            .collect::<Vec<_>>();
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (id, (domain, name, ty)) in keys.keys.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "  <key id=\"d{id}\" for=\"{domain}\" attr.name=\"{}\" attr.type=\"{ty}\"/>",
+                escape(name)
+            );
+        }
+
+        out.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+        for (node, ports, data) in nodes {
+            let _ = writeln!(
+                out,
+                "    <node id=\"{}\">",
+                escape(node.module.path.as_str())
+            );
+            for port in ports {
+                let _ = writeln!(out, "      <port name=\"{}\"/>", escape(&port));
+            }
+            write_data(&mut out, &data);
+            out.push_str("    </node>\n");
+        }
+        for (edge, data) in edges {
+            let _ = writeln!(
+                out,
+                "    <edge source=\"{}\" target=\"{}\" sourceport=\"{}\" targetport=\"{}\">",
+                escape(edge.from.module.path.as_str()),
+                escape(edge.to.module.path.as_str()),
+                escape(&edge.from.gate.str()),
+                escape(&edge.to.gate.str()),
+            );
+            write_data(&mut out, &data);
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+/// The data keys of a `GraphML` document, identified by their index.
+#[derive(Default)]
+struct Keys {
+    keys: Vec<(&'static str, String, &'static str)>,
+}
+
+impl Keys {
+    /// Converts an attachment into data attributes, registering
+    /// new keys as required.
+    fn data(&mut self, domain: &'static str, value: &impl Serialize) -> Vec<(usize, String)> {
+        let attrs = match serde_json::to_value(value).expect("failed to serialize attachment") {
+            Value::Null => Vec::new(),
+            Value::Object(map) => map.into_iter().filter(|(_, v)| !v.is_null()).collect(),
+            value => vec![("data".to_string(), value)],
+        };
+
+        attrs
+            .into_iter()
+            .map(|(name, value)| {
+                let (ty, text) = match value {
+                    Value::Bool(b) => ("boolean", b.to_string()),
+                    Value::Number(n) if n.is_f64() => ("double", n.to_string()),
+                    Value::Number(n) => ("long", n.to_string()),
+                    Value::String(s) => ("string", s),
+                    other => ("string", other.to_string()),
+                };
+                let id = self
+                    .keys
+                    .iter()
+                    .position(|key| key.0 == domain && key.1 == name)
+                    .unwrap_or_else(|| {
+                        self.keys.push((domain, name, ty));
+                        self.keys.len() - 1
+                    });
+                (id, text)
+            })
+            .collect()
+    }
+}
+
+fn write_data(out: &mut String, data: &[(usize, String)]) {
+    for (key, text) in data {
+        let _ = writeln!(out, "      <data key=\"d{key}\">{}</data>", escape(text));
+    }
+}
+
+impl<A> SimBuilder<A> {
+    /// Creates nodes, gates and connections from a `GraphML` document, like
+    /// the ones created by [`Topology::as_graphml`], or the ones provided
+    /// by the Internet Topology Zoo.
+    ///
+    /// Each node is created at the module path defined by its `id`, using
+    /// the registry symbol defined by its `type` attribute, or `Node` by
+    /// default. Parents must be defined before their children. Edges connect
+    /// the gates defined as `sourceport` and `targetport`. If an edge does not
+    /// define its gates, the next free gate of the cluster `port` is used.
+    /// Edges are backed by a channel, if they define channel attributes like
+    /// `bitrate` or `latency`, or a `LinkSpeedRaw` attribute.
+    ///
+    /// Since connections are always bidirectional, the two edges describing
+    /// both directions of a link in a directed graph are merged.
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the document is malformed, or the registry
+    /// fails to provide software for some node.
+    pub fn nodes_from_graphml<L: Layer>(
+        &mut self,
+        graphml: &str,
+        mut registry: impl AsMut<Registry<L>>,
+    ) -> Result<(), ImportError> {
+        let def = parse(graphml)?;
+        self.nodes_from_graph(&def, registry.as_mut())
+    }
+}
+
+fn parse(graphml: &str) -> Result<GraphDef, ImportError> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = Document::parse_with_options(graphml, options)
+        .map_err(|e| ImportError::Parse(e.to_string()))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "graphml" {
+        return Err(ImportError::Parse(format!(
+            "expected root element 'graphml', found '{}'",
+            root.tag_name().name()
+        )));
+    }
+
+    let keys = children(root, "key")
+        .filter_map(|key| {
+            let id = key.attribute("id")?;
+            let name = key.attribute("attr.name").unwrap_or(id);
+            let ty = key.attribute("attr.type").unwrap_or("string");
+            Some((id, (name, ty)))
+        })
+        .collect::<FxHashMap<_, _>>();
+
+    let graph = children(root, "graph")
+        .next()
+        .ok_or_else(|| ImportError::Parse("missing element 'graph'".to_string()))?;
+
+    let attr = |element: Node, name: &str| {
+        element.attribute(name).map(String::from).ok_or_else(|| {
+            ImportError::Parse(format!(
+                "missing attribute '{name}' on '{}'",
+                element.tag_name().name()
+            ))
+        })
+    };
+
+    let nodes = children(graph, "node")
+        .map(|node| {
+            Ok(NodeDef {
+                id: attr(node, "id")?,
+                typ: data(node, &keys)
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+            })
+        })
+        .collect::<Result<_, ImportError>>()?;
+
+    let edges = children(graph, "edge")
+        .map(|edge| {
+            Ok(EdgeDef {
+                source: attr(edge, "source")?,
+                target: attr(edge, "target")?,
+                source_gate: edge.attribute("sourceport").map(String::from),
+                target_gate: edge.attribute("targetport").map(String::from),
+                channel: ChannelDef::from_attributes(&data(edge, &keys))?,
+            })
+        })
+        .collect::<Result<_, ImportError>>()?;
+
+    Ok(GraphDef {
+        directed: graph.attribute("edgedefault") != Some("undirected"),
+        nodes,
+        edges,
+    })
+}
+
+/// The child elements with the given name, ignoring their namespace.
+fn children<'a, 'input>(
+    element: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    element
+        .children()
+        .filter(move |child| child.has_tag_name(name))
+}
+
+/// Collects the data attributes of an element, typed by their keys.
+fn data(element: Node, keys: &FxHashMap<&str, (&str, &str)>) -> Map<String, Value> {
+    children(element, "data")
+        .filter_map(|data| {
+            let key = data.attribute("key")?;
+            let (name, ty) = keys.get(key).copied().unwrap_or((key, "string"));
+            let text = data
+                .descendants()
+                .filter(Node::is_text)
+                .filter_map(|node| node.text())
+                .collect::<String>();
+            let text = text.trim();
+            let value = match ty {
+                "boolean" => Some(Value::Bool(text == "true" || text == "1")),
+                "int" | "long" => text.parse::<i64>().ok().map(Value::from),
+                "float" | "double" => text.parse::<f64>().ok().map(Value::from),
+                _ => None,
+            };
+            Some((
+                name.to_string(),
+                value.unwrap_or_else(|| Value::String(text.to_string())),
+            ))
+        })
+        .collect()
+}
+
+/// Escapes a string, so that it can be used as text or attribute value.
+fn escape(input: &str) -> String {
+    input.chars().fold(String::new(), |mut acc, c| {
+        match c {
+            '<' => acc.push_str("&lt;"),
+            '>' => acc.push_str("&gt;"),
+            '&' => acc.push_str("&amp;"),
+            '"' => acc.push_str("&quot;"),
+            '\'' => acc.push_str("&apos;"),
+            c if c.is_control() && !c.is_whitespace() => {
+                let _ = write!(acc, "&#{};", c as u32);
+            }
+            c => acc.push(c),
+        }
+        acc
+    })
+}
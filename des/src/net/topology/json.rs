@@ -0,0 +1,159 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::{
+    def::{ChannelDef, EdgeDef, GraphDef, ImportError, NodeDef},
+    Topology,
+};
+use crate::net::{
+    ndl::{Layer, Registry},
+    SimBuilder,
+};
+
+impl<N: Serialize, C: Serialize> Topology<N, C> {
+    /// Exports the current topology object as JSON.
+    ///
+    /// The document contains a list of `nodes`, identified by their module
+    /// path, and a list of directed `edges`, including the gates they
+    /// are attached to. Node and edge attachments are included as `data`,
+    /// the metrics of the channel of an edge as `channel`.
+    ///
+    /// ```json
+    /// {
+    ///   "directed": true,
+    ///   "nodes": [{ "id": "a", "data": null }, { "id": "b", "data": null }],
+    ///   "edges": [{
+    ///     "source": "a", "target": "b",
+    ///     "source_gate": "port", "target_gate": "port",
+    ///     "channel": { "bitrate": 1000, "latency": 0.01, ... },
+    ///     "data": null
+    ///   }, ...]
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the attachments cannot be represented as JSON.
+    #[must_use]
+    pub fn as_json(&self) -> String {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| json!({ "id": node.module.path.as_str(), "data": node.data }))
+            .collect::<Vec<_>>();
+
+        let edges = self
+            .edges()
+            .map(|edge| {
+                json!({
+                    "source": edge.from.module.path.as_str(),
+                    "target": edge.to.module.path.as_str(),
+                    "source_gate": edge.from.gate.str(),
+                    "target_gate": edge.to.gate.str(),
+                    "channel": edge.from.gate.channel().map(|ch| ChannelDef::from(&*ch)),
+                    "data": edge.attachment,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_string_pretty(&json!({
+            "directed": true,
+            "nodes": nodes,
+            "edges": edges,
+        }))
+        .expect("failed to serialize topology")
+    }
+}
+
+impl<A> SimBuilder<A> {
+    /// Creates nodes, gates and connections from a JSON topology, like
+    /// the ones created by [`Topology::as_json`].
+    ///
+    /// Each node is created at the module path defined by its `id`, using
+    /// the registry symbol defined by its `type`, or `Node` by default.
+    /// Parents must be defined before their children. Edges (or `links`)
+    /// connect the gates `source_gate` and `target_gate`. If an edge does
+    /// not define its gates, the next free gate of the cluster `port` is used.
+    /// Edges are backed by a channel defined by `channel`, or by a
+    /// `LinkSpeedRaw` attribute.
+    ///
+    /// Since connections are always bidirectional, the two edges describing
+    /// both directions of a link in a `directed` graph are merged.
+    /// This makes this function compatible to the node-link format of `networkx`.
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the document is malformed, or the registry
+    /// fails to provide software for some node.
+    pub fn nodes_from_json<L: Layer>(
+        &mut self,
+        json: &str,
+        mut registry: impl AsMut<Registry<L>>,
+    ) -> Result<(), ImportError> {
+        let def = parse(json)?;
+        self.nodes_from_graph(&def, registry.as_mut())
+    }
+}
+
+fn parse(json: &str) -> Result<GraphDef, ImportError> {
+    let value: Value = serde_json::from_str(json).map_err(|e| ImportError::Parse(e.to_string()))?;
+
+    let nodes = value
+        .get("nodes")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ImportError::Parse("missing list of nodes".to_string()))?
+        .iter()
+        .map(|node| {
+            Ok(NodeDef {
+                id: ident(node, "id")?,
+                typ: node.get("type").and_then(Value::as_str).map(String::from),
+            })
+        })
+        .collect::<Result<_, ImportError>>()?;
+
+    let edges = value
+        .get("edges")
+        .or_else(|| value.get("links"))
+        .and_then(Value::as_array)
+        .map_or(&[][..], Vec::as_slice)
+        .iter()
+        .map(|edge| {
+            let attrs = edge
+                .as_object()
+                .ok_or_else(|| ImportError::Parse("edges must be objects".to_string()))?;
+            let gate = |key: &str| edge.get(key).and_then(Value::as_str).map(String::from);
+            let channel = match edge.get("channel") {
+                None | Some(Value::Null) => ChannelDef::from_attributes(attrs)?,
+                Some(channel) => Some(
+                    serde_json::from_value(channel.clone())
+                        .map_err(|e| ImportError::Parse(format!("invalid channel: {e}")))?,
+                ),
+            };
+            Ok(EdgeDef {
+                source: ident(edge, "source")?,
+                target: ident(edge, "target")?,
+                source_gate: gate("source_gate"),
+                target_gate: gate("target_gate"),
+                channel,
+            })
+        })
+        .collect::<Result<_, ImportError>>()?;
+
+    Ok(GraphDef {
+        directed: value
+            .get("directed")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        nodes,
+        edges,
+    })
+}
+
+/// Reads an identifier, that may either be a string or a number.
+fn ident(value: &Value, key: &str) -> Result<String, ImportError> {
+    match value.get(key) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(Value::Number(n)) => Ok(n.to_string()),
+        _ => Err(ImportError::Parse(format!("missing or invalid '{key}'"))),
+    }
+}
@@ -1,5 +1,13 @@
 //! Graph-based tooling for exploring simulation topology.
+//!
+//! Topologies can be exported as DOT, `GraphML` or JSON. Networks described
+//! in `GraphML` or JSON can be imported using [`SimBuilder::nodes_from_graphml`]
+//! and [`SimBuilder::nodes_from_json`].
+//!
+//! [`SimBuilder::nodes_from_graphml`]: crate::net::SimBuilder::nodes_from_graphml
+//! [`SimBuilder::nodes_from_json`]: crate::net::SimBuilder::nodes_from_json
 use fxhash::{FxBuildHasher, FxHashMap};
+use serde::Serialize;
 
 use super::{
     gate::{GateKind, GateRef},
@@ -11,7 +19,15 @@ use std::{
     io::{Result, Write},
     process::Command,
 };
-use std::{ops::Deref, process::Stdio};
+use std::{ops::Deref, process::Stdio, sync::Arc};
+
+mod algorithms;
+mod def;
+mod graphml;
+mod json;
+
+pub use self::algorithms::{MaxFlow, Path};
+pub use self::def::ImportError;
 
 /// A graph-based representation of the simulations topology.
 #[derive(Debug, Default, Clone)]
@@ -317,7 +333,7 @@ impl Topology<(), ()> {
 
             let src_idx = this.nodes.len() - 1;
             for gate in gates {
-                if gate.kind() == GateKind::Endpoint {
+                if gate.kind() == GateKind::Endpoint || gate.medium().is_some() {
                     let iter = gate
                         .path_iter()
                         .expect("path_iter should exist on gates of kind: endpoint");
@@ -328,32 +344,34 @@ impl Topology<(), ()> {
                         end = con.endpoint;
                     }
 
-                    let end_id = end.owner().id();
-                    let end_idx = this
-                        .nodes
-                        .iter()
-                        .position(|node| node.module.id() == end_id)
-                        .unwrap_or_else(|| {
-                            // Node is not yet in the spanned set
-                            // but maybe allready in queue
-                            if let Some(offset) =
-                                modules.iter().position(|module| module.id() == end_id)
-                            {
-                                src_idx + 1 + offset
-                            } else {
-                                modules.push(end.owner());
-                                src_idx + modules.len()
-                            }
-                        });
-
-                    let raw = EdgeRaw {
-                        dst: end_idx,
-                        data: (),
-                        start: gate,
-                        end,
-                    };
+                    for end in broadcast_ends(end) {
+                        let end_id = end.owner().id();
+                        let end_idx = this
+                            .nodes
+                            .iter()
+                            .position(|node| node.module.id() == end_id)
+                            .unwrap_or_else(|| {
+                                // Node is not yet in the spanned set
+                                // but maybe allready in queue
+                                if let Some(offset) =
+                                    modules.iter().position(|module| module.id() == end_id)
+                                {
+                                    src_idx + 1 + offset
+                                } else {
+                                    modules.push(end.owner());
+                                    src_idx + modules.len()
+                                }
+                            });
+
+                        let raw = EdgeRaw {
+                            dst: end_idx,
+                            data: (),
+                            start: gate.clone(),
+                            end,
+                        };
 
-                    this.edges[src_idx].push(raw);
+                        this.edges[src_idx].push(raw);
+                    }
                 }
             }
         }
@@ -378,7 +396,7 @@ impl Topology<(), ()> {
         for (src_id, module) in modules.iter().enumerate() {
             let gates = module.gates();
             for gate in gates {
-                if gate.kind() == GateKind::Endpoint {
+                if gate.kind() == GateKind::Endpoint || gate.medium().is_some() {
                     let iter = gate
                         .path_iter()
                         .expect("path_iter should exist on gates of kind: endpoint");
@@ -388,24 +406,26 @@ impl Topology<(), ()> {
                         end = con.endpoint;
                     }
 
-                    let end_id = end.owner().id();
-                    let Some(dst) = this
-                        .nodes
-                        .iter()
-                        .position(|node| node.module.id() == end_id)
-                    else {
-                        // no spanning tree, ignore external links
-                        continue;
-                    };
-
-                    let raw = EdgeRaw {
-                        dst,
-                        data: (),
-                        start: gate,
-                        end,
-                    };
-
-                    this.edges[src_id].push(raw);
+                    for end in broadcast_ends(end) {
+                        let end_id = end.owner().id();
+                        let Some(dst) = this
+                            .nodes
+                            .iter()
+                            .position(|node| node.module.id() == end_id)
+                        else {
+                            // no spanning tree, ignore external links
+                            continue;
+                        };
+
+                        let raw = EdgeRaw {
+                            dst,
+                            data: (),
+                            start: gate.clone(),
+                            end,
+                        };
+
+                        this.edges[src_id].push(raw);
+                    }
                 }
             }
         }
@@ -414,6 +434,22 @@ impl Topology<(), ()> {
     }
 }
 
+/// Resolves the end of a gate chain to the gates reachable through it.
+///
+/// If the chain ends at a broadcast medium, these are the ends of the gate
+/// chains of all other attachees, otherwise it is the end itself.
+fn broadcast_ends(end: GateRef) -> Vec<GateRef> {
+    match end.medium() {
+        Some(medium) => medium
+            .attachees()
+            .into_iter()
+            .filter(|gate| !Arc::ptr_eq(gate, &end))
+            .map(|gate| gate.path_end().unwrap_or(gate))
+            .collect(),
+        None => vec![end],
+    }
+}
+
 #[cfg(test)]
 impl Topology<NodeID, ()> {
     fn raw(edges: &[&[usize]]) -> Self {
@@ -507,7 +543,7 @@ impl<N, C> Topology<N, C> {
 /// connections.
 ///
 /// > Note that attachments do NOT automatically updated when the topology object is later changed.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub struct NodeConnectivityAttachment {
     /// The number of outoing edges.
     pub degree: usize,
@@ -535,13 +571,14 @@ impl<N, C> Topology<N, C> {
 /// to transmit messages.
 ///
 /// > Note that attachments do NOT automatically updated when the topology object is later changed.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct EdgeCostAttachment {
     /// A cost definining how expensive a certain link would be.
     pub cost: f64,
     /// A flag whether a link is currently able to transmit messages.
     ///
-    /// This may be false if, e.g. a transit node on the link is shut down.
+    /// This may be false if, e.g. a transit node on the link is shut down,
+    /// or a channel on the link is down.
     pub alive: bool,
 }
 
@@ -556,6 +593,7 @@ impl<N, C> Topology<N, C> {
         self.with_edge_attachments(|edge| {
             let mut cost = 0.0;
             let mut alive = edge.from.gate.owner().is_active();
+            let mut up = true;
 
             let iter = edge
                 .from
@@ -564,13 +602,17 @@ impl<N, C> Topology<N, C> {
                 .expect("all repesented edges SHOULD exist only on endpoint gates");
 
             for con in iter.take(16) {
-                if con.channel().is_some() {
+                if let Some(channel) = con.channel() {
                     cost += 1.0;
+                    up &= channel.is_up();
                 }
                 alive |= con.endpoint.owner().is_active();
             }
 
-            EdgeCostAttachment { cost, alive }
+            EdgeCostAttachment {
+                cost,
+                alive: alive && up,
+            }
         })
     }
 }
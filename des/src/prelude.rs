@@ -15,6 +15,7 @@ pub use crate::time::SimTime;
 
 pub use crate::runtime::Application;
 pub use crate::runtime::Event;
+pub use crate::runtime::EventHandle;
 pub use crate::runtime::EventLifecycle;
 
 pub use crate::runtime::random;
@@ -41,6 +42,8 @@ cfg_net! {
     pub use crate::net::channel::ChannelMetrics;
     pub use crate::net::channel::ChannelRef;
     pub use crate::net::channel::ChannelDropBehaviour;
+    pub use crate::net::channel::DelayModel;
+    pub use crate::net::channel::LossModel;
 
     pub use crate::net::gate::Gate;
     pub use crate::net::gate::GateRef;
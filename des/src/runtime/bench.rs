@@ -5,7 +5,10 @@ use std::{
 
 use crate::time::SimTime;
 
-use super::{FT_ASYNC, FT_CQUEUE, FT_NET};
+#[cfg(feature = "net")]
+use crate::stats::Statistics;
+
+use super::{RuntimeLimit, FT_ASYNC, FT_CQUEUE, FT_NET};
 
 /// A run profiler
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -31,8 +34,15 @@ pub struct Profiler<E> {
 
     /// The number of events that where executed.
     pub event_count: usize,
+    /// The limit that terminated the simulation, if any.
+    pub limit: Option<RuntimeLimit>,
     /// The active features.
     pub features: Vec<String>,
+
+    /// The statistics collected by the modules of a [`Sim`](crate::net::Sim).
+    /// Empty for other applications.
+    #[cfg(feature = "net")]
+    pub statistics: Statistics,
 }
 
 impl<E> Profiler<E> {
@@ -42,8 +52,9 @@ impl<E> Profiler<E> {
     }
 
     /// Finishes the profile.
-    pub(super) fn finish(&mut self, event_count: usize) {
+    pub(super) fn finish(&mut self, event_count: usize, limit: Option<RuntimeLimit>) {
         self.event_count = event_count;
+        self.limit = limit;
         let now = Instant::now();
         self.duration = now - self.time_start;
     }
@@ -105,7 +116,11 @@ impl<E> Default for Profiler<E> {
             remaining: Vec::new(),
 
             event_count: 0,
+            limit: None,
             features,
+
+            #[cfg(feature = "net")]
+            statistics: Statistics::default(),
         }
     }
 }
@@ -1,35 +1,91 @@
 use std::{
+    cell::Cell,
     fmt::Debug,
-    sync::{Mutex, TryLockError},
+    marker::PhantomData,
+    sync::{Mutex, MutexGuard, TryLockError},
 };
 
 #[cfg(feature = "cqueue")]
 use std::time::Duration;
 
-use rand::{
-    rngs::{StdRng, ThreadRng},
-    RngCore, SeedableRng,
-};
+use rand::{rngs::ThreadRng, SeedableRng};
 
 use crate::prelude::SimTime;
 
-use super::{Application, FutureEventSet, Profiler, Runtime, RuntimeLimit, State, RNG};
+use super::{
+    rng_slot, Application, EventQueue, EventQueueKind, FutureEventSet, Profiler, Runtime,
+    RuntimeLimit, RuntimeObserver, SimRng, State,
+};
 
-/// A lock the ensures only one runtime exits at a time.
+/// A lock the ensures only one exclusive runtime exits at a time.
 static SIMULATION_LOCK: Mutex<()> = Mutex::new(());
 
+thread_local! {
+    /// Indicates whether a runtime exists on the current thread.
+    static RUNTIME_ACTIVE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A permit to run a simulation on the current thread.
+///
+/// Simulation globals like the [`SimTime`] or the RNG are bound to the
+/// thread that created the runtime, so only one runtime may exist per
+/// thread, and the runtime must not be moved to another thread.
+pub(super) struct Permit {
+    #[allow(unused)]
+    lock: Option<MutexGuard<'static, ()>>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Permit {
+    fn acquire<A: Application>(exclusive: bool) -> Permit {
+        assert!(
+            !RUNTIME_ACTIVE.with(|active| active.replace(true)),
+            "another runtime allready exists on this thread"
+        );
+
+        let lock = exclusive.then(|| match SIMULATION_LOCK.try_lock() {
+            Ok(permit) => permit,
+            Err(TryLockError::WouldBlock) => {
+                eprintln!(
+                    "des::warning ** another runtime allready exists ... waiting for simlock"
+                );
+                SIMULATION_LOCK.lock().unwrap_or_else(|p| {
+                    eprintln!("des::error ** another runtime poisoned the simlock ... cleaning up");
+                    Runtime::<A>::poison_cleanup();
+                    p.into_inner()
+                })
+            }
+            Err(TryLockError::Poisoned(p)) => {
+                eprintln!("des::error ** another runtime poisoned the simlock ... cleaning up");
+                Runtime::<A>::poison_cleanup();
+                p.into_inner()
+            }
+        });
+
+        Permit {
+            lock,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        RUNTIME_ACTIVE.with(|active| active.set(false));
+    }
+}
+
 /// A builder for a runtime instance.
 #[must_use]
 pub struct Builder {
     pub(super) quiet: bool,
-    pub(super) rng: Box<dyn RngCore>,
+    pub(super) rng: SimRng,
     pub(super) limit: RuntimeLimit,
     pub(super) start_time: SimTime,
+    pub(super) warmup: Option<SimTime>,
+    pub(super) exclusive: bool,
 
-    #[cfg(feature = "cqueue")]
-    pub(super) cqueue_num_buckets: usize,
-    #[cfg(feature = "cqueue")]
-    pub(super) cqueue_bucket_timespan: Duration,
+    pub(super) event_queue: EventQueueKind,
 }
 
 impl Builder {
@@ -41,16 +97,14 @@ impl Builder {
     pub fn new() -> Builder {
         Builder {
             quiet: false,
-            rng: Box::new(StdRng::from_rng(&mut ThreadRng::default())),
+            rng: SimRng::from_rng(&mut ThreadRng::default()),
             limit: RuntimeLimit::None,
 
             start_time: SimTime::MIN,
+            warmup: None,
+            exclusive: false,
 
-            #[cfg(feature = "cqueue")]
-            cqueue_num_buckets: 1028,
-
-            #[cfg(feature = "cqueue")]
-            cqueue_bucket_timespan: Duration::from_secs_f64(0.0025),
+            event_queue: EventQueueKind::default(),
         }
     }
 
@@ -58,28 +112,107 @@ impl Builder {
     pub fn seeded(seed: u64) -> Builder {
         Builder {
             quiet: false,
-            rng: Box::new(StdRng::seed_from_u64(seed)),
+            rng: SimRng::seed_from_u64(seed),
             limit: RuntimeLimit::None,
 
             start_time: SimTime::MIN,
+            warmup: None,
+            exclusive: false,
 
-            #[cfg(feature = "cqueue")]
-            cqueue_num_buckets: 1028,
-
-            #[cfg(feature = "cqueue")]
-            cqueue_bucket_timespan: Duration::from_secs_f64(0.0025),
+            event_queue: EventQueueKind::default(),
         }
     }
 
     ///
-    /// Sets the cqueue options if this runtime uses a cqueue.
-    /// NOP otherwise.
+    /// Selects a calendar queue with `n` buckets, each spanning
+    /// a timeslot of `t`, as the future event set.
     ///
     #[cfg(feature = "cqueue")]
-    pub fn cqueue_options(mut self, n: usize, t: Duration) -> Self {
-        self.cqueue_num_buckets = n;
-        self.cqueue_bucket_timespan = t;
+    pub fn cqueue_options(self, n: usize, t: Duration) -> Self {
+        #[cfg(not(feature = "miri"))]
+        let event_queue = EventQueueKind::CalendarQueue {
+            num_buckets: n,
+            bucket_timespan: t,
+        };
+        // Calendar queues are not available under miri
+        #[cfg(feature = "miri")]
+        let event_queue = {
+            let _ = (n, t);
+            self.event_queue
+        };
+        Self {
+            event_queue,
+            ..self
+        }
+    }
 
+    ///
+    /// Selects one of the built-in event queues as the future event set.
+    ///
+    /// Defaults to a calendar queue if the feature `cqueue` is active,
+    /// and to a binary heap otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use des::prelude::*;
+    /// use des::runtime::EventQueueKind;
+    ///
+    /// let builder = Builder::seeded(123).event_queue(EventQueueKind::BinaryHeap);
+    /// ```
+    pub fn event_queue(mut self, kind: EventQueueKind) -> Self {
+        self.event_queue = kind;
+        self
+    }
+
+    ///
+    /// Binds the builder to the application type `A`, to configure
+    /// options that depend on the event set of the application,
+    /// like a custom event queue or observers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use des::prelude::*;
+    /// use des::runtime::RuntimeObserver;
+    ///
+    /// # struct App;
+    /// # impl Application for App {
+    /// #     type EventSet = Events;
+    /// #     type Lifecycle = ();
+    /// # }
+    /// # enum Events {}
+    /// # impl Event<App> for Events {
+    /// #     fn handle(self, rt: &mut Runtime<App>) {}
+    /// # }
+    /// struct Progress;
+    /// impl RuntimeObserver<Events> for Progress {}
+    ///
+    /// let rt = Builder::seeded(123)
+    ///     .max_time(10.0.into())
+    ///     .with_app::<App>()
+    ///     .observer(Progress)
+    ///     .build(App);
+    /// ```
+    pub fn with_app<A: Application>(self) -> AppBuilder<A> {
+        AppBuilder {
+            builder: self,
+            custom_event_queue: None,
+            observers: Vec::new(),
+        }
+    }
+
+    ///
+    /// Acquires a process-wide lock when building the runtime, so that
+    /// no other exclusive runtime exists at the same time.
+    ///
+    /// By default, runtimes on different threads run in parallel, since
+    /// all simulation globals are bound to the thread that created the
+    /// runtime. Use this option, if your application shares state
+    /// between simulations on different threads.
+    ///
+    pub fn exclusive(mut self) -> Self {
+        self.exclusive = true;
         self
     }
 
@@ -99,6 +232,27 @@ impl Builder {
         self
     }
 
+    ///
+    /// Sets the end of the warm-up period of the simulation.
+    ///
+    /// Once the warm-up period ends, [`EventLifecycle::at_warmup_end`](super::EventLifecycle::at_warmup_end)
+    /// is called on the application. Network simulations use this hook to discard all
+    /// statistics recorded during the transient phase, see [`stats`](crate::stats).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use des::prelude::*;
+    ///
+    /// let builder = Builder::seeded(123)
+    ///     .warmup(100.0.into())
+    ///     .max_time(1000.0.into());
+    /// ```
+    pub fn warmup(mut self, warmup: SimTime) -> Self {
+        self.warmup = Some(warmup);
+        self
+    }
+
     ///
     /// Changes the maximum iteration number of a runtime.
     ///
@@ -148,61 +302,106 @@ impl Builder {
     /// let app = App(42, String::from("Hello there!"));
     /// let rt = Builder::new().build(app);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if another runtime exists on the current thread.
     pub fn build<A: Application>(self, app: A) -> Runtime<A> {
-        let permit = {
-            let lock = SIMULATION_LOCK.try_lock();
-            match lock {
-                Ok(permit) => permit,
-                Err(err) => {
-                    match err {
-                        TryLockError::WouldBlock => {
-                            eprintln!("des::warning ** another runtime allready exists ... waiting for simlock");
-                            let lock = SIMULATION_LOCK.lock();
-                            match lock {
-                                Ok(lock) => lock,
-                                Err(p) => {
-                                    eprintln!("des::error ** another runtime poisoned the simlock ... cleaning up");
-                                    Runtime::<A>::poison_cleanup();
-                                    p.into_inner()
-                                }
-                            }
-                        }
-                        TryLockError::Poisoned(p) => {
-                            eprintln!("des::error ** another runtime poisoned the simlock ... cleaning up");
-                            Runtime::<A>::poison_cleanup();
-                            p.into_inner()
-                        }
-                    }
-                }
-            }
-        };
+        self.with_app().build(app)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+impl Debug for Builder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder").finish()
+    }
+}
+
+/// A builder for a runtime of the application `A`, created
+/// using [`Builder::with_app`].
+#[must_use]
+pub struct AppBuilder<A: Application> {
+    pub(super) builder: Builder,
+    custom_event_queue: Option<Box<dyn EventQueue<A::EventSet>>>,
+    observers: Vec<Box<dyn RuntimeObserver<A::EventSet>>>,
+}
+
+impl<A: Application> AppBuilder<A> {
+    ///
+    /// Uses a custom [`EventQueue`] as the future event set, replacing
+    /// the queue selected by [`Builder::event_queue`].
+    ///
+    pub fn custom_event_queue(mut self, queue: impl EventQueue<A::EventSet> + 'static) -> Self {
+        self.custom_event_queue = Some(Box::new(queue));
+        self
+    }
+
+    ///
+    /// Registers a [`RuntimeObserver`], that will be notified about
+    /// all events scheduled and dispatched by the runtime.
+    ///
+    /// Observers are notified in the order they were registered.
+    ///
+    pub fn observer(mut self, observer: impl RuntimeObserver<A::EventSet> + 'static) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    ///
+    /// Builds a new [`Runtime`] instance, like [`Builder::build`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if another runtime exists on the current thread.
+    pub fn build(self, app: A) -> Runtime<A> {
+        let AppBuilder {
+            builder,
+            custom_event_queue,
+            observers,
+        } = self;
+        let permit = Permit::acquire::<A>(builder.exclusive);
 
         // Log prep
         // StandardLogger::setup().expect("Failed to create logger");
-        #[cfg(feature = "cqueue")]
-        if std::mem::size_of::<A::EventSet>() > 128 {
+        #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+        if matches!(builder.event_queue, EventQueueKind::CalendarQueue { .. })
+            && custom_event_queue.is_none()
+            && std::mem::size_of::<A::EventSet>() > 128
+        {
             eprintln!("des::warning ** creating runtime with event-set bigger that 128 bytes * this may lead to performance losses");
         }
 
-        let future_event_set = FutureEventSet::new_with(&self);
+        let future_event_set =
+            FutureEventSet::new_with(builder.event_queue, custom_event_queue, builder.start_time);
 
         // Set SimTime
-        SimTime::set_now(self.start_time);
+        SimTime::set_now(builder.start_time);
 
         // Set RNG
-        *unsafe { &mut *RNG.get() } = Some(self.rng);
+        *rng_slot() = Some(builder.rng);
 
         Runtime {
             future_event_set,
 
             event_id: 0,
             itr: 0,
+            restored: false,
             permit,
 
-            limit: self.limit,
+            limit: builder.limit,
+            limit_reached: None,
+            warmup: builder.warmup,
+            last_activity: builder.start_time,
 
-            quiet: self.quiet,
+            quiet: builder.quiet,
             profiler: Profiler::default(),
+            observers,
 
             state: State::Ready,
             app,
@@ -210,14 +409,8 @@ impl Builder {
     }
 }
 
-impl Default for Builder {
-    fn default() -> Self {
-        Builder::new()
-    }
-}
-
-impl Debug for Builder {
+impl<A: Application> Debug for AppBuilder<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Builder").finish()
+        f.debug_struct("AppBuilder").finish_non_exhaustive()
     }
 }
@@ -0,0 +1,280 @@
+use std::{error::Error, fmt};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{rng_slot, AppBuilder, Application, Builder, EventId, Runtime, SimRng, State};
+use crate::time::{Duration, SimTime};
+
+/// An application whose state can be captured in a [`Snapshot`], and
+/// restored later on.
+///
+/// Restoring is done in place, so the application must be constructed
+/// by the user before a snapshot can be applied. This allows applications
+/// to contain state that cannot be serialized (e.g. code), as long as this
+/// state can be recreated by the caller.
+pub trait Checkpoint {
+    /// The serializable representation of the applications state.
+    type Snapshot: Serialize + DeserializeOwned;
+
+    /// Captures the current state of the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if some part of the state cannot be captured.
+    fn checkpoint(&self) -> Result<Self::Snapshot, CheckpointError>;
+
+    /// Restores a previously captured state onto this application.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot does not match the application.
+    fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), CheckpointError>;
+}
+
+/// An event set, whose pending events can be captured in a [`Snapshot`].
+///
+/// Both directions have access to the application, so that events may
+/// refer to parts of the application by a serializable identifier,
+/// instead of by value.
+pub trait EventCheckpoint<App>: Sized {
+    /// The serializable representation of a single event.
+    type Snapshot: Serialize + DeserializeOwned;
+
+    /// Captures a pending event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be captured.
+    fn checkpoint(&self, app: &App) -> Result<Self::Snapshot, CheckpointError>;
+
+    /// Recreates a pending event from its snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be recreated in the context of `app`.
+    fn restore(snapshot: Self::Snapshot, app: &App) -> Result<Self, CheckpointError>;
+}
+
+/// A serializable snapshot of a running [`Runtime`].
+///
+/// A snapshot contains the current simulation time, the pending events,
+/// the event counters, the state of the RNG and the state of the application.
+/// It can be used to resume the simulation using [`Builder::restore`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Snapshot<A>
+where
+    A: Application + Checkpoint,
+    A::EventSet: EventCheckpoint<A>,
+{
+    time: ExactTime,
+    event_id: EventId,
+    itr: usize,
+    rng: SimRng,
+    events: Vec<(ExactTime, <A::EventSet as EventCheckpoint<A>>::Snapshot)>,
+    app: A::Snapshot,
+}
+
+impl<A> Snapshot<A>
+where
+    A: Application + Checkpoint,
+    A::EventSet: EventCheckpoint<A>,
+{
+    /// The simulation time at which the snapshot was taken.
+    pub fn time(&self) -> SimTime {
+        self.time.into()
+    }
+
+    /// The number of events that were dispatched before the snapshot was taken.
+    pub fn num_events_dispatched(&self) -> usize {
+        self.itr
+    }
+
+    /// The number of events pending at the time the snapshot was taken.
+    pub fn num_events_remaining(&self) -> usize {
+        self.events.len()
+    }
+}
+
+impl<A> fmt::Debug for Snapshot<A>
+where
+    A: Application + Checkpoint,
+    A::EventSet: EventCheckpoint<A>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("time", &self.time())
+            .field("event_id", &self.event_id)
+            .field("itr", &self.itr)
+            .field("events", &self.events.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A simulation time, that is serialized without loss of precision,
+/// independent of whether the format is human readable.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct ExactTime {
+    secs: u64,
+    nanos: u32,
+}
+
+impl From<SimTime> for ExactTime {
+    fn from(time: SimTime) -> Self {
+        ExactTime {
+            secs: time.as_secs(),
+            nanos: time.subsec_nanos(),
+        }
+    }
+}
+
+impl From<ExactTime> for SimTime {
+    fn from(time: ExactTime) -> Self {
+        SimTime::from_duration(Duration::new(time.secs, time.nanos))
+    }
+}
+
+/// An error that occured while creating or restoring a [`Snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// Some part of the simulation state does not support checkpointing.
+    Unsupported(String),
+    /// The snapshot refers to an object that does not exist in the restored application.
+    Missing(String),
+    /// Some state could not be serialized or deserialized.
+    Serde(String),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(s) => write!(f, "checkpointing not supported: {s}"),
+            Self::Missing(s) => write!(f, "snapshot refers to missing object: {s}"),
+            Self::Serde(s) => write!(f, "snapshot could not be (de)serialized: {s}"),
+        }
+    }
+}
+
+impl Error for CheckpointError {}
+
+impl<A> Runtime<A>
+where
+    A: Application + Checkpoint,
+    A::EventSet: EventCheckpoint<A>,
+{
+    /// Captures the state of a running simulation in a [`Snapshot`].
+    ///
+    /// The snapshot can be serialized and used to resume the simulation
+    /// later on using [`Builder::restore`]. Note that [`EventHandle`](super::EventHandle)s
+    /// are not preserved, so events scheduled before the snapshot cannot be
+    /// cancelled after the simulation was restored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the application or some pending event cannot
+    /// be captured.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the simulation has not been started.
+    pub fn checkpoint(&self) -> Result<Snapshot<A>, CheckpointError> {
+        assert_eq!(
+            self.state,
+            State::Running,
+            "only a running simulation can be checkpointed"
+        );
+
+        let mut pending = self.future_event_set.iter().collect::<Vec<_>>();
+        if pending.len() != self.future_event_set.len() {
+            return Err(CheckpointError::Unsupported(format!(
+                "event queue {} cannot enumerate pending events",
+                self.future_event_set.descriptor()
+            )));
+        }
+        pending.sort_by_key(|(_, handle)| (handle.time, handle.id));
+
+        let events = pending
+            .into_iter()
+            .map(|(event, handle)| Ok((handle.time.into(), event.checkpoint(&self.app)?)))
+            .collect::<Result<Vec<_>, CheckpointError>>()?;
+
+        let rng = rng_slot().as_ref().expect("RNG not yet initalized").clone();
+
+        Ok(Snapshot {
+            time: SimTime::now().into(),
+            event_id: self.event_id,
+            itr: self.itr,
+            rng,
+            events,
+            app: self.app.checkpoint()?,
+        })
+    }
+}
+
+impl Builder {
+    /// Builds a new [`Runtime`] that resumes the simulation captured in `snapshot`.
+    ///
+    /// The application `app` must be constructed in the same way as the original
+    /// application, since the snapshot is restored onto it in place. The simulation
+    /// time and RNG state of the builder are replaced by the values of the snapshot,
+    /// while all other options (e.g. limits) are applied as usual.
+    ///
+    /// When starting the returned runtime, [`EventLifecycle::at_sim_start`](super::EventLifecycle::at_sim_start)
+    /// will not be called again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be applied to `app`.
+    pub fn restore<A>(self, app: A, snapshot: Snapshot<A>) -> Result<Runtime<A>, CheckpointError>
+    where
+        A: Application + Checkpoint,
+        A::EventSet: EventCheckpoint<A>,
+    {
+        self.with_app().restore(app, snapshot)
+    }
+}
+
+impl<A> AppBuilder<A>
+where
+    A: Application + Checkpoint,
+    A::EventSet: EventCheckpoint<A>,
+{
+    /// Builds a new [`Runtime`] that resumes the simulation captured
+    /// in `snapshot`, like [`Builder::restore`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be applied to `app`.
+    pub fn restore(mut self, app: A, snapshot: Snapshot<A>) -> Result<Runtime<A>, CheckpointError> {
+        self.builder.start_time = snapshot.time.into();
+        self.builder.rng = snapshot.rng.clone();
+
+        // A warm-up period that ended before the snapshot was taken
+        // must not discard the restored statistics.
+        if self
+            .builder
+            .warmup
+            .is_some_and(|warmup| warmup < self.builder.start_time)
+        {
+            self.builder.warmup = None;
+        }
+
+        let mut rt = self.build(app);
+        rt.app.restore(snapshot.app)?;
+
+        for (time, event) in snapshot.events {
+            let event = A::EventSet::restore(event, &rt.app)?;
+            let _ = rt.add_event(event, time.into());
+        }
+
+        // Restoring the application may have consumed random values,
+        // so the RNG state is reapplied.
+        *rng_slot() = Some(snapshot.rng);
+
+        rt.event_id = snapshot.event_id;
+        rt.itr = snapshot.itr;
+        rt.restored = true;
+
+        Ok(rt)
+    }
+}
@@ -1,399 +1,118 @@
-cfg_not_miri! {
-
-    cfg_not_cqueue! {
-        mod default_impl {
-            use crate::{runtime::{Application, EventId}, time::SimTime};
-            use std::{collections::{BinaryHeap, VecDeque}, cmp, marker};
-            use crate::runtime::Builder;
-
-            #[derive(Debug)]
-            pub(crate) struct EventNode<A>
-            where
-                A: Application,
-            {
-                /// The deadline timestamp for the event.
-                pub(crate) time: SimTime,
-                /// A runtime-specific unique identifier.
-                pub(crate) id: EventId,
-                /// The actual event.
-                pub(crate) event: A::EventSet,
-
-                /// A marker to preserve the type information concerning the application
-                /// not only the Event set.
-                pub(crate) _phantom: marker::PhantomData<A>,
-            }
-
-            impl<A> cmp::PartialEq for EventNode<A>
-            where
-                A: Application,
-            {
-                fn eq(&self, other: &Self) -> bool {
-                    self.id == other.id
-                }
-            }
-
-            impl<A> cmp::Eq for EventNode<A> where A: Application {}
-
-            impl<A> cmp::PartialOrd for EventNode<A>
-            where
-                A: Application,
-            {
-                fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-                    Some(self.cmp(other))
-                }
-
-                fn lt(&self, other: &Self) -> bool {
-                    other.time < self.time
-                }
-
-                fn le(&self, other: &Self) -> bool {
-                    other.time <= self.time
-                }
-
-                fn gt(&self, other: &Self) -> bool {
-                    other.time > self.time
-                }
-
-                fn ge(&self, other: &Self) -> bool {
-                    other.time >= self.time
-                }
-            }
-
-            impl<A> cmp::Ord for EventNode<A>
-            where
-                A: Application,
-            {
-                fn cmp(&self, other: &Self) -> cmp::Ordering {
-                    // Inverted call should act as reverse
-                    other.time.cmp(&self.time)
-                }
-            }
-
-
-
-            pub(crate) struct FutureEventSet<A>
-            where
-                A: Application,
-            {
-                heap: BinaryHeap<EventNode<A>>,
-                zero_queue: VecDeque<EventNode<A>>,
-
-                last_event_simtime: SimTime,
-            }
-
-            impl<A> FutureEventSet<A>
-            where
-                A: Application,
-            {
-                #[allow(clippy::unused_self)]
-                pub(crate) fn descriptor(&self) -> String {
-                    "FutureEventSet::BinaryHeap()".to_string()
-                }
-
-                pub(crate) fn len(&self) -> usize {
-                    self.len_zero() + self.len_nonzero()
-                }
-
-                pub(crate) fn is_empty(&self) -> bool {
-                    self.heap.is_empty() && self.zero_queue.is_empty()
-                }
-
-                pub(crate) fn len_zero(&self) -> usize {
-                    self.zero_queue.len()
-                }
-
-                pub(crate) fn len_nonzero(&self) -> usize {
-                    self.heap.len()
-                }
-
-                pub(crate) fn new_with(options: &Builder) -> Self {
-                    Self {
-                        heap: BinaryHeap::with_capacity(64),
-                        zero_queue: VecDeque::with_capacity(32),
-
-                        last_event_simtime: options.start_time,
-                    }
-                }
-
-                //
-                // clippy::let_and_return occures on not(feature = "metrics")
-                // but would produce invalid code with feature "metrics"
-                //
-                #[allow(clippy::let_and_return)]
-                #[allow(clippy::needless_pass_by_value)]
-                #[allow(clippy::cast_precision_loss)]
-                pub(crate) fn fetch_next(
-                    &mut self,
-                ) -> (A::EventSet, SimTime) {
-
-                    let event = if let Some(event) = self.zero_queue.pop_front() {
-                        self.last_event_simtime = event.time;
-                        event
-                    } else {
-                        let event = self.heap.pop().expect("unreachable: fetch_next shall only be called with the guarantee that an event exists");
-                        self.last_event_simtime = event.time;
-                        event
-                    };
-
-                    (event.event, event.time)
-                }
-
-                #[allow(clippy::needless_pass_by_value)]
-                pub(crate) fn add(
-                    &mut self,
-                    time: SimTime,
-                    event: impl Into<A::EventSet>,
-                ) {
-                    assert!(
-                        time >= self.last_event_simtime,
-                        "Sorry we cannot timetravel yet"
-                    );
-
-                    let node = EventNode {
-                        id: 0,
-                        event: event.into(),
-                        time,
-
-                        _phantom: std::marker::PhantomData,
-                    };
+use super::{BinaryHeapQueue, EventHandle, EventQueue, EventQueueKind};
+use crate::{runtime::Application, time::SimTime};
+
+/// The event queue backing a [`FutureEventSet`].
+///
+/// Built-in queues are stored inline, so that the event set
+/// of the application is not required to be `'static`.
+enum Backend<E> {
+    BinaryHeap(BinaryHeapQueue<E>),
+    #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+    CalendarQueue(super::CalendarQueue<E>),
+    Custom(Box<dyn EventQueue<E>>),
+}
 
-                    if self.last_event_simtime == time {
-                        self.zero_queue.push_back(node);
-                    } else {
-                        self.heap.push(node);
-                    }
-                }
-            }
+impl<E> Backend<E> {
+    fn new(kind: EventQueueKind) -> Self {
+        match kind {
+            EventQueueKind::BinaryHeap => Self::BinaryHeap(BinaryHeapQueue::new()),
+            #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+            EventQueueKind::CalendarQueue {
+                num_buckets,
+                bucket_timespan,
+            } => Self::CalendarQueue(super::CalendarQueue::new(num_buckets, bucket_timespan)),
         }
-
-        pub(crate) use default_impl::*;
     }
 
-    cfg_cqueue! {
-        mod cqueue_impl {
-            use crate::{runtime::{Application, Builder}, time::SimTime};
-            use des_cqueue::CQueue;
-
-            pub(crate) struct FutureEventSet<A>
-            where
-                A: Application,
-            {
-                inner: CQueue<A::EventSet>,
-            }
-
-            impl<A> FutureEventSet<A>
-            where
-                A: Application,
-            {
-                #[allow(clippy::unused_self)]
-                pub(crate) fn descriptor(&self) -> String {
-                    format!("FutureEventSet::CQueue::{}", self.inner.descriptor())
-                }
-
-                pub(crate) fn len(&self) -> usize {
-                    self.inner.len()
-                }
-
-                pub(crate) fn is_empty(&self) -> bool {
-                    self.inner.is_empty()
-                }
-
-                pub(crate) fn new_with(options: &Builder) -> Self {
-                    Self {
-                        inner: CQueue::new(options.cqueue_num_buckets, options.cqueue_bucket_timespan),
-                    }
-                }
-
-                #[allow(clippy::needless_pass_by_value)]
-                pub(crate) fn fetch_next(
-                    &mut self,
-                ) -> (A::EventSet, SimTime) {
-
-                    let (event, time) = self.inner.fetch_next();
-                    (event, SimTime::from_duration(time))
-                }
-
-                #[allow(clippy::needless_pass_by_value)]
-                pub(crate) fn add(
-                    &mut self,
-                    time: SimTime,
-                    event: impl Into<A::EventSet>,
-                ) {
-                    self.inner.add(*time, event.into());
-                }
-            }
+    fn get(&self) -> &dyn EventQueue<E> {
+        match self {
+            Self::BinaryHeap(queue) => queue,
+            #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+            Self::CalendarQueue(queue) => queue,
+            Self::Custom(queue) => queue.as_ref(),
         }
-
-        pub(crate) use self::cqueue_impl::*;
     }
 
-}
-
-cfg_miri! {
-    mod default_impl {
-        use crate::{runtime::{Application, EventId}, time::SimTime};
-        use std::{collections::{BinaryHeap, VecDeque}, cmp, marker};
-        use crate::runtime::Builder;
-
-        #[derive(Debug)]
-        pub(crate) struct EventNode<A>
-        where
-            A: Application,
-        {
-            /// The deadline timestamp for the event.
-            pub(crate) time: SimTime,
-            /// A runtime-specific unique identifier.
-            pub(crate) id: EventId,
-            /// The actual event.
-            pub(crate) event: A::EventSet,
-
-            /// A marker to preserve the type information concerning the application
-            /// not only the Event set.
-            pub(crate) _phantom: marker::PhantomData<A>,
-        }
-
-        impl<A> cmp::PartialEq for EventNode<A>
-        where
-            A: Application,
-        {
-            fn eq(&self, other: &Self) -> bool {
-                self.id == other.id
-            }
-        }
-
-        impl<A> cmp::Eq for EventNode<A> where A: Application {}
-
-        impl<A> cmp::PartialOrd for EventNode<A>
-        where
-            A: Application,
-        {
-            fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-                Some(self.cmp(other))
-            }
-
-            fn lt(&self, other: &Self) -> bool {
-                other.time < self.time
-            }
-
-            fn le(&self, other: &Self) -> bool {
-                other.time <= self.time
-            }
-
-            fn gt(&self, other: &Self) -> bool {
-                other.time > self.time
-            }
-
-            fn ge(&self, other: &Self) -> bool {
-                other.time >= self.time
-            }
+    fn get_mut(&mut self) -> &mut dyn EventQueue<E> {
+        match self {
+            Self::BinaryHeap(queue) => queue,
+            #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+            Self::CalendarQueue(queue) => queue,
+            Self::Custom(queue) => queue.as_mut(),
         }
+    }
+}
 
-        impl<A> cmp::Ord for EventNode<A>
-        where
-            A: Application,
-        {
-            fn cmp(&self, other: &Self) -> cmp::Ordering {
-                // Inverted call should act as reverse
-                other.time.cmp(&self.time)
-            }
-        }
+/// The future event set of a runtime, backed by a configurable [`EventQueue`].
+pub(crate) struct FutureEventSet<A>
+where
+    A: Application,
+{
+    inner: Backend<A::EventSet>,
+    last_event_simtime: SimTime,
+}
 
+impl<A> FutureEventSet<A>
+where
+    A: Application,
+{
+    pub(crate) fn descriptor(&self) -> String {
+        self.inner.get().descriptor()
+    }
 
+    pub(crate) fn len(&self) -> usize {
+        self.inner.get().len()
+    }
 
-        pub(crate) struct FutureEventSet<A>
-        where
-            A: Application,
-        {
-            heap: BinaryHeap<EventNode<A>>,
-            zero_queue: VecDeque<EventNode<A>>,
+    pub(crate) fn is_empty(&self) -> bool {
+        self.inner.get().is_empty()
+    }
 
-            last_event_simtime: SimTime,
+    pub(crate) fn new_with(
+        kind: EventQueueKind,
+        custom: Option<Box<dyn EventQueue<A::EventSet>>>,
+        start_time: SimTime,
+    ) -> Self {
+        let inner = match custom {
+            Some(custom) => Backend::Custom(custom),
+            None => Backend::new(kind),
+        };
+
+        Self {
+            inner,
+            last_event_simtime: start_time,
         }
+    }
 
-        impl<A> FutureEventSet<A>
-        where
-            A: Application,
-        {
-            #[allow(clippy::unused_self)]
-            pub(crate) fn descriptor(&self) -> String {
-                "FutureEventSet::BinaryHeap()".to_string()
-            }
-
-            pub(crate) fn len(&self) -> usize {
-                self.len_zero() + self.len_nonzero()
-            }
-
-            pub(crate) fn is_empty(&self) -> bool {
-                self.heap.is_empty() && self.zero_queue.is_empty()
-            }
-
-            pub(crate) fn len_zero(&self) -> usize {
-                self.zero_queue.len()
-            }
-
-            pub(crate) fn len_nonzero(&self) -> usize {
-                self.heap.len()
-            }
-
-            pub(crate) fn new_with(options: &Builder) -> Self {
-                Self {
-                    heap: BinaryHeap::with_capacity(64),
-                    zero_queue: VecDeque::with_capacity(32),
-
-                    last_event_simtime: options.start_time,
-                }
-            }
-
-            //
-            // clippy::let_and_return occures on not(feature = "metrics")
-            // but would produce invalid code with feature "metrics"
-            //
-            #[allow(clippy::let_and_return)]
-            #[allow(clippy::needless_pass_by_value)]
-            #[allow(clippy::cast_precision_loss)]
-            pub(crate) fn fetch_next(
-                &mut self,
-            ) -> (A::EventSet, SimTime) {
-
-                let event = if let Some(event) = self.zero_queue.pop_front() {
-                    self.last_event_simtime = event.time;
-                    event
-                } else {
-                    let event = self.heap.pop().expect("unreachable: fetch_next shall only be called with the guarantee that an event exists");
-                    self.last_event_simtime = event.time;
-                    event
-                };
-
-                (event.event, event.time)
-            }
+    pub(crate) fn fetch_next(&mut self) -> (A::EventSet, SimTime) {
+        let (event, time) = self.inner.get_mut().fetch_next().expect(
+            "unreachable: fetch_next shall only be called with the guarantee that an event exists",
+        );
+        self.last_event_simtime = time;
+        (event, time)
+    }
 
-            #[allow(clippy::needless_pass_by_value)]
-            pub(crate) fn add(
-                &mut self,
-                time: SimTime,
-                event: impl Into<A::EventSet>,
-            ) {
-                assert!(
-                    time >= self.last_event_simtime,
-                    "Sorry we cannot timetravel yet"
-                );
+    pub(crate) fn add(&mut self, time: SimTime, event: impl Into<A::EventSet>) -> EventHandle {
+        assert!(
+            time >= self.last_event_simtime,
+            "Sorry we cannot timetravel yet"
+        );
+        self.inner.get_mut().add(time, event.into())
+    }
 
-                let node = EventNode {
-                    id: 0,
-                    event: event.into(),
-                    time,
+    pub(crate) fn cancel(&mut self, handle: EventHandle) -> bool {
+        self.inner.get_mut().cancel(handle)
+    }
 
-                    _phantom: std::marker::PhantomData,
-                };
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&A::EventSet, EventHandle)> {
+        self.inner.get().iter()
+    }
 
-                if self.last_event_simtime == time {
-                    self.zero_queue.push_back(node);
-                } else {
-                    self.heap.push(node);
-                }
-            }
-        }
+    pub(crate) fn peek_time(&self) -> Option<SimTime> {
+        self.inner.get().peek_time()
     }
 
-    pub(crate) use default_impl::*;
+    pub(crate) fn peek(&self) -> Option<(&A::EventSet, SimTime)> {
+        self.inner.get().peek()
+    }
 }
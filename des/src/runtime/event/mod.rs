@@ -3,6 +3,9 @@ use crate::time::SimTime;
 mod event_set;
 pub(crate) use event_set::*;
 
+mod queue;
+pub use queue::*;
+
 #[allow(unused)]
 pub(crate) trait EventSink<E> {
     fn add(&mut self, event: E, time: SimTime);
@@ -10,7 +13,7 @@ pub(crate) trait EventSink<E> {
 
 impl<A: Application> EventSink<A::EventSet> for Runtime<A> {
     fn add(&mut self, event: A::EventSet, time: SimTime) {
-        self.add_event(event, time);
+        let _ = self.add_event(event, time);
     }
 }
 
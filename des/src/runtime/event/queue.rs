@@ -0,0 +1,417 @@
+use std::{
+    cmp,
+    collections::{BinaryHeap, VecDeque},
+    fmt::{self, Debug},
+};
+
+use super::{EventHandle, EventId};
+use crate::time::SimTime;
+
+///
+/// A future event set, that stores pending events ordered by their
+/// deadline.
+///
+/// The [`Runtime`](crate::runtime::Runtime) uses an event queue to determine
+/// the next event to be dispatched. Which implementation is used can be
+/// configured using [`Builder::event_queue`](crate::runtime::Builder::event_queue) for
+/// built-in backends, or [`AppBuilder::custom_event_queue`](crate::runtime::AppBuilder::custom_event_queue)
+/// for custom implementations.
+///
+/// Implementations must dispatch events in order of their deadline. Events
+/// with the same deadline must be dispatched in the order they were added.
+///
+pub trait EventQueue<E> {
+    ///
+    /// A short human-readable description of the queue and its parameters.
+    ///
+    fn descriptor(&self) -> String;
+
+    ///
+    /// The number of events pending in the queue.
+    ///
+    fn len(&self) -> usize;
+
+    ///
+    /// Indicates whether no events are pending.
+    ///
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Adds an event with the deadline `time` to the queue.
+    ///
+    /// The returned handle must identify the event uniquely within
+    /// this queue.
+    ///
+    fn add(&mut self, time: SimTime, event: E) -> EventHandle;
+
+    ///
+    /// Removes the next event from the queue, returning the
+    /// event and its deadline.
+    ///
+    fn fetch_next(&mut self) -> Option<(E, SimTime)>;
+
+    ///
+    /// Returns the deadline of the next event, without removing it.
+    ///
+    fn peek_time(&self) -> Option<SimTime>;
+
+    ///
+    /// Returns the next event and its deadline, without removing it.
+    ///
+    /// This must be the event returned by the next call to
+    /// [`fetch_next`](EventQueue::fetch_next).
+    ///
+    fn peek(&self) -> Option<(&E, SimTime)>;
+
+    ///
+    /// Removes the event identified by the handle from the queue. Returns
+    /// whether the event was still pending.
+    ///
+    fn cancel(&mut self, handle: EventHandle) -> bool;
+
+    ///
+    /// An iterator over all pending events in arbitrary order.
+    ///
+    /// This function is used to capture pending events in snapshots,
+    /// and to cancel all events of modules that are torn down.
+    ///
+    fn iter(&self) -> Box<dyn Iterator<Item = (&E, EventHandle)> + '_>;
+}
+
+///
+/// The built-in event queues, selectable using [`Builder::event_queue`](crate::runtime::Builder::event_queue).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventQueueKind {
+    /// A [`BinaryHeapQueue`].
+    BinaryHeap,
+    /// A [`CalendarQueue`] with the given number of buckets and
+    /// bucket timespan.
+    #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cqueue")))]
+    CalendarQueue {
+        /// The number of buckets.
+        num_buckets: usize,
+        /// The timespan covered by each bucket.
+        bucket_timespan: std::time::Duration,
+    },
+}
+
+impl EventQueueKind {
+    ///
+    /// All built-in event queues, using their default parameters.
+    ///
+    #[must_use]
+    pub fn all() -> Vec<EventQueueKind> {
+        vec![
+            EventQueueKind::BinaryHeap,
+            #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+            EventQueueKind::calendar_queue(),
+        ]
+    }
+
+    ///
+    /// A [`CalendarQueue`] using the default parameters.
+    ///
+    #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cqueue")))]
+    #[must_use]
+    pub fn calendar_queue() -> EventQueueKind {
+        EventQueueKind::CalendarQueue {
+            num_buckets: 1028,
+            bucket_timespan: std::time::Duration::from_secs_f64(0.0025),
+        }
+    }
+}
+
+impl Default for EventQueueKind {
+    fn default() -> Self {
+        #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+        return EventQueueKind::calendar_queue();
+        #[cfg(not(all(feature = "cqueue", not(feature = "miri"))))]
+        return EventQueueKind::BinaryHeap;
+    }
+}
+
+impl fmt::Display for EventQueueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BinaryHeap => write!(f, "binary-heap"),
+            #[cfg(all(feature = "cqueue", not(feature = "miri")))]
+            Self::CalendarQueue { .. } => write!(f, "calendar-queue"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct EventNode<E> {
+    /// The deadline timestamp for the event.
+    time: SimTime,
+    /// A queue-specific unique identifier.
+    id: EventId,
+    /// The actual event.
+    event: E,
+}
+
+impl<E> EventNode<E> {
+    fn handle(&self) -> EventHandle {
+        EventHandle {
+            id: self.id,
+            time: self.time,
+        }
+    }
+}
+
+impl<E> cmp::PartialEq for EventNode<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<E> cmp::Eq for EventNode<E> {}
+
+impl<E> cmp::PartialOrd for EventNode<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> cmp::Ord for EventNode<E> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        // Inverted call should act as reverse,
+        // ties are broken by insertion order.
+        other.time.cmp(&self.time).then(other.id.cmp(&self.id))
+    }
+}
+
+///
+/// An event queue based on a binary heap.
+///
+/// Events scheduled for the current simulation time are stored in a
+/// separate FIFO queue, bypassing the heap.
+///
+pub struct BinaryHeapQueue<E> {
+    heap: BinaryHeap<EventNode<E>>,
+    zero_queue: VecDeque<EventNode<E>>,
+
+    last_event_simtime: SimTime,
+    next_id: EventId,
+}
+
+impl<E> BinaryHeapQueue<E> {
+    ///
+    /// Creates a new empty queue.
+    ///
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::with_capacity(64),
+            zero_queue: VecDeque::with_capacity(32),
+
+            last_event_simtime: SimTime::MIN,
+            next_id: 0,
+        }
+    }
+
+    fn next_is_zero(&self) -> bool {
+        match (self.zero_queue.front(), self.heap.peek()) {
+            (Some(zero), Some(heap)) => zero >= heap,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+impl<E> Default for BinaryHeapQueue<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Debug for BinaryHeapQueue<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BinaryHeapQueue")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> EventQueue<E> for BinaryHeapQueue<E> {
+    fn descriptor(&self) -> String {
+        "FutureEventSet::BinaryHeap()".to_string()
+    }
+
+    fn len(&self) -> usize {
+        self.zero_queue.len() + self.heap.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.heap.is_empty() && self.zero_queue.is_empty()
+    }
+
+    fn add(&mut self, time: SimTime, event: E) -> EventHandle {
+        let id = self.next_id;
+        self.next_id = id.wrapping_add(1);
+
+        let node = EventNode { time, id, event };
+        if self.last_event_simtime == time {
+            self.zero_queue.push_back(node);
+        } else {
+            self.heap.push(node);
+        }
+
+        EventHandle { id, time }
+    }
+
+    fn fetch_next(&mut self) -> Option<(E, SimTime)> {
+        // Events in the zero queue may be preceded by events at the same
+        // time, that were added to the heap beforehand.
+        let node = if self.next_is_zero() {
+            self.zero_queue.pop_front()?
+        } else {
+            self.heap.pop()?
+        };
+        self.last_event_simtime = node.time;
+        Some((node.event, node.time))
+    }
+
+    fn peek_time(&self) -> Option<SimTime> {
+        if self.next_is_zero() {
+            self.zero_queue.front().map(|node| node.time)
+        } else {
+            self.heap.peek().map(|node| node.time)
+        }
+    }
+
+    fn peek(&self) -> Option<(&E, SimTime)> {
+        let node = if self.next_is_zero() {
+            self.zero_queue.front()?
+        } else {
+            self.heap.peek()?
+        };
+        Some((&node.event, node.time))
+    }
+
+    fn cancel(&mut self, handle: EventHandle) -> bool {
+        if handle.time < self.last_event_simtime {
+            return false;
+        }
+
+        if let Some(i) = self.zero_queue.iter().position(|node| node.id == handle.id) {
+            self.zero_queue.remove(i);
+            return true;
+        }
+
+        let len = self.heap.len();
+        self.heap.retain(|node| node.id != handle.id);
+        self.heap.len() != len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&E, EventHandle)> + '_> {
+        Box::new(
+            self.zero_queue
+                .iter()
+                .chain(self.heap.iter())
+                .map(|node| (&node.event, node.handle())),
+        )
+    }
+}
+
+cfg_cqueue! {
+    cfg_not_miri! {
+        ///
+        /// An event queue based on a calendar queue, provided by `des-cqueue`.
+        ///
+        /// Calendar queues provide amortized constant time operations,
+        /// if the bucket parameters match the distribution of event deadlines.
+        ///
+        pub struct CalendarQueue<E> {
+            inner: des_cqueue::CQueue<E>,
+        }
+
+        impl<E> CalendarQueue<E> {
+            ///
+            /// Creates a new empty queue with `num_buckets` buckets, each
+            /// spanning a timeslot of `bucket_timespan`.
+            ///
+            #[must_use]
+            pub fn new(num_buckets: usize, bucket_timespan: std::time::Duration) -> Self {
+                Self {
+                    inner: des_cqueue::CQueue::new(num_buckets, bucket_timespan),
+                }
+            }
+        }
+
+        impl<E> Debug for CalendarQueue<E> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct("CalendarQueue")
+                    .field("descriptor", &self.inner.descriptor())
+                    .field("len", &self.inner.len())
+                    .finish()
+            }
+        }
+
+        impl<E> EventQueue<E> for CalendarQueue<E> {
+            fn descriptor(&self) -> String {
+                format!("FutureEventSet::CQueue::{}", self.inner.descriptor())
+            }
+
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            fn is_empty(&self) -> bool {
+                self.inner.is_empty()
+            }
+
+            fn add(&mut self, time: SimTime, event: E) -> EventHandle {
+                let handle = self.inner.add(*time, event);
+                EventHandle {
+                    id: handle.id(),
+                    time,
+                }
+            }
+
+            fn fetch_next(&mut self) -> Option<(E, SimTime)> {
+                if self.inner.is_empty() {
+                    return None;
+                }
+                let (event, time) = self.inner.fetch_next();
+                Some((event, SimTime::from_duration(time)))
+            }
+
+            fn peek_time(&self) -> Option<SimTime> {
+                self.inner
+                    .peek()
+                    .map(|(_, time)| SimTime::from_duration(time))
+            }
+
+            fn peek(&self) -> Option<(&E, SimTime)> {
+                self.inner
+                    .peek()
+                    .map(|(event, time)| (event, SimTime::from_duration(time)))
+            }
+
+            fn cancel(&mut self, handle: EventHandle) -> bool {
+                self.inner.cancel(des_cqueue::EventHandle::from_raw_parts(
+                    handle.id,
+                    *handle.time,
+                ))
+            }
+
+            fn iter(&self) -> Box<dyn Iterator<Item = (&E, EventHandle)> + '_> {
+                Box::new(self.inner.iter().map(|(event, handle)| {
+                    (
+                        event,
+                        EventHandle {
+                            id: handle.id(),
+                            time: SimTime::from_duration(handle.time()),
+                        },
+                    )
+                }))
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::runtime::{Runtime, RuntimeError};
+use crate::time::SimTime;
 
 ///
 /// A trait that defines an runtime application
@@ -39,6 +40,27 @@ where
     /// event set implementations.
     ///
     fn handle(self, runtime: &mut Runtime<App>);
+
+    ///
+    /// Indicates whether handling this event counts as activity within
+    /// the simulation. Used by [`RuntimeLimit::Idle`](crate::runtime::RuntimeLimit::Idle)
+    /// to detect quiescent simulations.
+    ///
+    /// By default all events count as activity.
+    ///
+    fn is_activity(&self) -> bool {
+        true
+    }
+
+    ///
+    /// A short human-readable description of the event, used by
+    /// [`Runtime::step`](crate::runtime::Runtime::step).
+    ///
+    /// By default the type name of the event is used.
+    ///
+    fn describe(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
 }
 
 ///
@@ -77,6 +99,20 @@ pub trait EventLifecycle<A = Self> {
     {
     }
 
+    ///
+    /// A function that is called once the warm-up period of the simulation
+    /// has ended, see [`Builder::warmup`](crate::runtime::Builder::warmup).
+    ///
+    /// This function is called before the first event at or after the end of the
+    /// warm-up period is handled. The simulation time is set to the end of
+    /// the warm-up period.
+    #[allow(unused_variables)]
+    fn at_warmup_end(runtime: &mut Runtime<A>)
+    where
+        A: Application,
+    {
+    }
+
     ///
     /// A function that is called once the simulation reachted its limit.
     ///
@@ -128,6 +164,50 @@ impl<A> EventLifecycle<A> for () {}
 ///
 pub(crate) type EventId = usize;
 
+///
+/// A handle to an event scheduled on a [`Runtime`].
+///
+/// Handles are returned by [`Runtime::add_event`] and can be used
+/// to cancel the associated event using [`Runtime::cancel_event`].
+/// A handle remains valid, even if the associated event was already
+/// dispatched, but cancelling such events has no effect.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventHandle {
+    pub(crate) id: EventId,
+    pub(crate) time: SimTime,
+}
+
+impl EventHandle {
+    ///
+    /// Creates a new handle from a queue-specific identifier and the
+    /// deadline of the associated event.
+    ///
+    /// This function is intended for custom [`EventQueue`](super::EventQueue)
+    /// implementations.
+    ///
+    #[must_use]
+    pub fn new(id: usize, time: SimTime) -> Self {
+        Self { id, time }
+    }
+
+    ///
+    /// The queue-specific identifier of the associated event.
+    ///
+    #[must_use]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    ///
+    /// The time the associated event is scheduled for.
+    ///
+    #[must_use]
+    pub fn time(&self) -> SimTime {
+        self.time
+    }
+}
+
 impl<F: FnMut() -> R, R> Application for F {
     type EventSet = ();
     type Lifecycle = Self;
@@ -1,11 +1,15 @@
-use crate::time::SimTime;
-use std::{fmt::Display, mem};
+use crate::time::{Duration, SimTime};
+use std::{
+    fmt::{self, Debug, Display},
+    mem,
+    sync::Arc,
+    time::Instant,
+};
 
 ///
 /// A composed limit that terminates the event execution of
 /// a runtime.
 ///
-#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RuntimeLimit {
     /// A unbounded runtime. A runtime with this limit will
@@ -24,6 +28,29 @@ pub enum RuntimeLimit {
     /// scheduled before the given simulation time are left.
     SimTime(SimTime),
 
+    /// A bound based on the real time since the start of the simulation.
+    /// A runtime with this bound will terminate once the given duration
+    /// has elapsed, e.g. to ensure that test runs cannot hang.
+    WallClock(Duration),
+
+    /// A bound based on the activity within the simulation.
+    /// A runtime with this bound will terminate once no activity
+    /// occured for the given span of simulation time.
+    ///
+    /// See [`Event::is_activity`](super::Event::is_activity) for more information.
+    Idle(Duration),
+
+    /// A custom bound, evaluated before each event.
+    /// A runtime with this bound will terminate once the predicate
+    /// returns `true`.
+    ///
+    /// The predicate only sees the [`RuntimeView`], **not** the application
+    /// itself. To stop on application state, capture a shared handle
+    /// (e.g. an `Arc<AtomicBool>`) that the application updates.
+    /// This limit cannot be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Predicate(Box<dyn LimitPredicate>),
+
     /// This bound combines two other bounds with a logical AND.
     /// This will only terminated the simulation if both given
     /// limits are fulfilled.
@@ -33,22 +60,150 @@ pub enum RuntimeLimit {
     /// This will terminated the simulation if one of given
     /// limits is fulfilled.
     CombinedOr(Box<RuntimeLimit>, Box<RuntimeLimit>),
+
+    /// A bound based on the precision of a recorded statistic.
+    /// A runtime with this bound will terminate once the confidence
+    /// interval of the targeted vector is narrow enough.
+    ///
+    /// See [`PrecisionTarget`](crate::stats::PrecisionTarget) for more information.
+    #[cfg(feature = "net")]
+    Precision(crate::stats::PrecisionTarget),
+}
+
+/// The predicate of a [`RuntimeLimit::Predicate`].
+///
+/// Predicates are created using [`RuntimeLimit::predicate`]. Clones of a
+/// predicate share the underlying closure and compare as equal.
+pub trait LimitPredicate: Send + Sync {
+    /// Evaluates the predicate for the given view.
+    fn applies(&self, view: &RuntimeView) -> bool;
+
+    #[doc(hidden)]
+    fn clone_box(&self) -> Box<dyn LimitPredicate>;
+
+    #[doc(hidden)]
+    fn id(&self) -> *const ();
+}
+
+struct SharedPredicate<F>(Arc<F>);
+
+impl<F> LimitPredicate for SharedPredicate<F>
+where
+    F: Fn(&RuntimeView) -> bool + Send + Sync + 'static,
+{
+    fn applies(&self, view: &RuntimeView) -> bool {
+        (self.0)(view)
+    }
+
+    fn clone_box(&self) -> Box<dyn LimitPredicate> {
+        Box::new(Self(self.0.clone()))
+    }
+
+    fn id(&self) -> *const () {
+        Arc::as_ptr(&self.0).cast()
+    }
+}
+
+/// The state of a runtime, as seen by a [`RuntimeLimit`].
+///
+/// Limits are evaluated before an event is dispatched, so the view
+/// refers to the next event in the future event set. The view does
+/// not expose the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeView {
+    pub(super) dispatched: usize,
+    pub(super) remaining: usize,
+    pub(super) time: SimTime,
+    pub(super) time_start: Instant,
+    pub(super) last_activity: SimTime,
+}
+
+impl RuntimeView {
+    /// The number of events that were dispatched so far.
+    #[must_use]
+    pub fn num_events_dispatched(&self) -> usize {
+        self.dispatched
+    }
+
+    /// The number of events remaining in the future event set.
+    #[must_use]
+    pub fn num_events_remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// The time of the next event.
+    #[must_use]
+    pub fn next_event_time(&self) -> SimTime {
+        self.time
+    }
+
+    /// The real time since the start of the simulation.
+    ///
+    /// The clock is only read when this function is called.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.time_start.elapsed()
+    }
+
+    /// The time of the last event that counted as activity.
+    #[must_use]
+    pub fn last_activity(&self) -> SimTime {
+        self.last_activity
+    }
+
+    /// The span of simulation time between the last activity and the next event.
+    #[must_use]
+    pub fn idle_time(&self) -> Duration {
+        self.time.saturating_duration_since(self.last_activity)
+    }
 }
 
 impl RuntimeLimit {
-    pub(crate) fn applies(&self, itr_count: usize, time: SimTime) -> bool {
+    /// Creates a [`RuntimeLimit::Predicate`] from a closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use des::prelude::*;
+    /// use des::runtime::RuntimeLimit;
+    /// use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+    ///
+    /// let done = Arc::new(AtomicBool::new(false));
+    /// let flag = done.clone();
+    /// let builder = Builder::new().limit(RuntimeLimit::predicate(move |_| {
+    ///     flag.load(Ordering::SeqCst)
+    /// }));
+    /// ```
+    pub fn predicate(f: impl Fn(&RuntimeView) -> bool + Send + Sync + 'static) -> Self {
+        Self::Predicate(Box::new(SharedPredicate(Arc::new(f))))
+    }
+
+    pub(crate) fn applies(&self, view: &RuntimeView) -> bool {
         match self {
             Self::None => false,
 
-            Self::EventCount(e) => itr_count > *e,
-            Self::SimTime(t) => time > *t,
+            Self::EventCount(e) => view.dispatched + 1 > *e,
+            Self::SimTime(t) => view.time > *t,
+            Self::WallClock(d) => view.elapsed() > *d,
+            Self::Idle(d) => view.idle_time() > *d,
+            Self::Predicate(f) => f.applies(view),
 
-            Self::CombinedAnd(lhs, rhs) => {
-                lhs.applies(itr_count, time) && rhs.applies(itr_count, time)
-            }
-            Self::CombinedOr(lhs, rhs) => {
-                lhs.applies(itr_count, time) || rhs.applies(itr_count, time)
-            }
+            Self::CombinedAnd(lhs, rhs) => lhs.applies(view) && rhs.applies(view),
+            Self::CombinedOr(lhs, rhs) => lhs.applies(view) || rhs.applies(view),
+
+            #[cfg(feature = "net")]
+            Self::Precision(target) => target.is_reached(),
+        }
+    }
+
+    /// Returns the part of the limit that applies, if any.
+    ///
+    /// For combinations using a logical OR, the first applying
+    /// part is returned, otherwise the limit itself.
+    pub(crate) fn fired(&self, view: &RuntimeView) -> Option<&RuntimeLimit> {
+        match self {
+            Self::CombinedOr(lhs, rhs) => lhs.fired(view).or_else(|| rhs.fired(view)),
+            limit => limit.applies(view).then_some(limit),
         }
     }
 
@@ -63,16 +218,81 @@ impl RuntimeLimit {
     }
 }
 
+impl Clone for RuntimeLimit {
+    fn clone(&self) -> Self {
+        match self {
+            Self::None => Self::None,
+            Self::EventCount(e) => Self::EventCount(*e),
+            Self::SimTime(t) => Self::SimTime(*t),
+            Self::WallClock(d) => Self::WallClock(*d),
+            Self::Idle(d) => Self::Idle(*d),
+            Self::Predicate(f) => Self::Predicate(f.clone_box()),
+            Self::CombinedAnd(lhs, rhs) => Self::CombinedAnd(lhs.clone(), rhs.clone()),
+            Self::CombinedOr(lhs, rhs) => Self::CombinedOr(lhs.clone(), rhs.clone()),
+            #[cfg(feature = "net")]
+            Self::Precision(target) => Self::Precision(target.clone()),
+        }
+    }
+}
+
+impl PartialEq for RuntimeLimit {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::None, Self::None) => true,
+            (Self::EventCount(lhs), Self::EventCount(rhs)) => lhs == rhs,
+            (Self::SimTime(lhs), Self::SimTime(rhs)) => lhs == rhs,
+            (Self::WallClock(lhs), Self::WallClock(rhs)) | (Self::Idle(lhs), Self::Idle(rhs)) => {
+                lhs == rhs
+            }
+            (Self::Predicate(lhs), Self::Predicate(rhs)) => lhs.id() == rhs.id(),
+            (Self::CombinedAnd(ll, lr), Self::CombinedAnd(rl, rr))
+            | (Self::CombinedOr(ll, lr), Self::CombinedOr(rl, rr)) => ll == rl && lr == rr,
+            #[cfg(feature = "net")]
+            (Self::Precision(lhs), Self::Precision(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for RuntimeLimit {}
+
+impl Debug for RuntimeLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::EventCount(e) => f.debug_tuple("EventCount").field(e).finish(),
+            Self::SimTime(t) => f.debug_tuple("SimTime").field(t).finish(),
+            Self::WallClock(d) => f.debug_tuple("WallClock").field(d).finish(),
+            Self::Idle(d) => f.debug_tuple("Idle").field(d).finish(),
+            Self::Predicate(_) => f.debug_tuple("Predicate").finish_non_exhaustive(),
+            Self::CombinedAnd(lhs, rhs) => {
+                f.debug_tuple("CombinedAnd").field(lhs).field(rhs).finish()
+            }
+            Self::CombinedOr(lhs, rhs) => {
+                f.debug_tuple("CombinedOr").field(lhs).field(rhs).finish()
+            }
+            #[cfg(feature = "net")]
+            Self::Precision(target) => f.debug_tuple("Precision").field(target).finish(),
+        }
+    }
+}
+
 impl Display for RuntimeLimit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::None => write!(f, "None"),
 
             Self::EventCount(e) => write!(f, "MaxEventCount({e})"),
             Self::SimTime(t) => write!(f, "MaxSimTime({t})"),
+            Self::WallClock(d) => write!(f, "MaxWallClock({d:?})"),
+            Self::Idle(d) => write!(f, "MaxIdle({d:?})"),
+            Self::Predicate(_) => write!(f, "Predicate"),
 
             Self::CombinedAnd(lhs, rhs) => write!(f, "{lhs} and {rhs}"),
             Self::CombinedOr(lhs, rhs) => write!(f, "{lhs} or {rhs}"),
+
+            #[cfg(feature = "net")]
+            Self::Precision(target) => write!(f, "{target}"),
         }
     }
 }
@@ -81,27 +301,37 @@ impl Display for RuntimeLimit {
 mod tests {
     use super::*;
 
+    fn view(itr_count: usize, time: SimTime) -> RuntimeView {
+        RuntimeView {
+            dispatched: itr_count.saturating_sub(1),
+            remaining: 0,
+            time,
+            time_start: Instant::now(),
+            last_activity: SimTime::ZERO,
+        }
+    }
+
     #[test]
     fn raw_limits() {
         let limit = RuntimeLimit::None;
         assert_eq!(limit.to_string(), "None");
-        assert!(!limit.applies(123, 100.0.into()));
-        assert!(!limit.applies(0, 0.0.into()));
-        assert!(!limit.applies(usize::MAX, SimTime::MAX));
+        assert!(!limit.applies(&view(123, 100.0.into())));
+        assert!(!limit.applies(&view(0, 0.0.into())));
+        assert!(!limit.applies(&view(usize::MAX, SimTime::MAX)));
 
         let limit = RuntimeLimit::EventCount(100);
         assert_eq!(limit.to_string(), "MaxEventCount(100)");
-        assert!(!limit.applies(23, 100.0.into()));
-        assert!(limit.applies(101, 0.0.into()));
-        assert!(limit.applies(101, SimTime::MAX));
-        assert!(limit.applies(230, 23.0.into()));
+        assert!(!limit.applies(&view(23, 100.0.into())));
+        assert!(limit.applies(&view(101, 0.0.into())));
+        assert!(limit.applies(&view(101, SimTime::MAX)));
+        assert!(limit.applies(&view(230, 23.0.into())));
 
         let limit = RuntimeLimit::SimTime(100.0.into());
         assert_eq!(limit.to_string(), "MaxSimTime(100s)");
-        assert!(!limit.applies(0, 10.0.into()));
-        assert!(!limit.applies(0, 100.0.into()));
-        assert!(limit.applies(0, 100.000001.into()));
-        assert!(limit.applies(0, SimTime::MAX));
+        assert!(!limit.applies(&view(0, 10.0.into())));
+        assert!(!limit.applies(&view(0, 100.0.into())));
+        assert!(limit.applies(&view(0, 100.000001.into())));
+        assert!(limit.applies(&view(0, SimTime::MAX)));
     }
 
     #[test]
@@ -110,18 +340,42 @@ mod tests {
 
         let limit = CombinedAnd(Box::new(EventCount(100)), Box::new(SimTime(100.0.into())));
         assert_eq!(limit.to_string(), "MaxEventCount(100) and MaxSimTime(100s)");
-        assert!(!limit.applies(200, 10.0.into()));
-        assert!(!limit.applies(0, 200.0.into()));
-        assert!(limit.applies(101, 100.000001.into()));
+        assert!(!limit.applies(&view(200, 10.0.into())));
+        assert!(!limit.applies(&view(0, 200.0.into())));
+        assert!(limit.applies(&view(101, 100.000001.into())));
 
         let limit = CombinedOr(Box::new(EventCount(100)), Box::new(SimTime(100.0.into())));
         assert_eq!(limit.to_string(), "MaxEventCount(100) or MaxSimTime(100s)");
-        assert!(!limit.applies(20, 10.0.into()));
-        assert!(limit.applies(0, 200.0.into()));
-        assert!(limit.applies(101, 10.0.into()));
+        assert!(!limit.applies(&view(20, 10.0.into())));
+        assert!(limit.applies(&view(0, 200.0.into())));
+        assert!(limit.applies(&view(101, 10.0.into())));
 
         let mut other = RuntimeLimit::EventCount(100);
         other.add(SimTime(100.0.into()));
         assert_eq!(limit, other);
     }
+
+    #[test]
+    fn fired_limits() {
+        use RuntimeLimit::*;
+
+        let limit = CombinedOr(
+            Box::new(EventCount(100)),
+            Box::new(Idle(Duration::from_secs(5))),
+        );
+        assert_eq!(limit.to_string(), "MaxEventCount(100) or MaxIdle(5s)");
+        assert_eq!(limit.fired(&view(20, 5.0.into())), Option::None);
+        assert_eq!(limit.fired(&view(101, 5.0.into())), Some(&EventCount(100)));
+        assert_eq!(
+            limit.fired(&view(20, 5.5.into())),
+            Some(&Idle(Duration::from_secs(5)))
+        );
+
+        let predicate = RuntimeLimit::predicate(|view| view.num_events_dispatched() == 42);
+        assert_eq!(predicate.to_string(), "Predicate");
+        assert_eq!(predicate, predicate.clone());
+        assert_ne!(predicate, RuntimeLimit::predicate(|_| true));
+        assert!(predicate.applies(&view(43, 0.0.into())));
+        assert!(!predicate.applies(&view(42, 0.0.into())));
+    }
 }
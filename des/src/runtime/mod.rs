@@ -2,17 +2,13 @@
 //! Central primitives for running a discrete event simulation.
 //!
 
-use crate::{
-    macros::support::SyncWrap,
-    time::{Duration, SimTime},
-};
+use crate::time::{Duration, SimTime};
 use rand::{distr::StandardUniform, prelude::Distribution, Rng, RngCore};
 use std::{
     any::type_name,
     cell::UnsafeCell,
     fmt::{Debug, Display},
     mem,
-    sync::MutexGuard,
 };
 
 mod event;
@@ -21,6 +17,9 @@ pub use self::event::*;
 mod limit;
 pub use self::limit::*;
 
+mod observer;
+pub use self::observer::*;
+
 mod bench;
 pub use bench::*;
 
@@ -32,6 +31,11 @@ pub use error::*;
 
 mod metrics;
 
+cfg_serde! {
+    mod checkpoint;
+    pub use checkpoint::*;
+}
+
 pub(crate) const FT_NET: bool = cfg!(feature = "net");
 pub(crate) const FT_CQUEUE: bool = cfg!(feature = "cqueue");
 pub(crate) const FT_ASYNC: bool = cfg!(feature = "async");
@@ -39,11 +43,27 @@ pub(crate) const FT_ASYNC: bool = cfg!(feature = "async");
 pub(crate) const SYM_CHECKMARK: char = '\u{2713}';
 pub(crate) const SYM_CROSSMARK: char = '\u{02df}';
 
-pub(crate) static RNG: SyncWrap<UnsafeCell<Option<Box<dyn RngCore>>>> =
-    SyncWrap::new(UnsafeCell::new(None));
+/// The RNG used by all runtimes. A `ChaCha12Rng` is equivalent to
+/// the `StdRng`, but exposes its internal state.
+pub(crate) type SimRng = rand_chacha::ChaCha12Rng;
+
+thread_local! {
+    static RNG: UnsafeCell<Option<SimRng>> = const { UnsafeCell::new(None) };
+}
+
+/// Returns the RNG of the simulation running on the current thread.
+///
+/// SAFETY: The reference must not outlive the current thread. Since the
+/// simulation runs on a single thread, no other references are alive
+/// while events are handled.
+#[allow(clippy::mut_from_ref)]
+pub(crate) fn rng_slot() -> &'static mut Option<SimRng> {
+    RNG.with(|rng| unsafe { &mut *rng.get() })
+}
 
 ///
-/// Returns a reference to a given rng.
+/// Returns a reference to the RNG of the simulation running
+/// on the current thread.
 ///
 /// # Panics
 ///
@@ -52,9 +72,7 @@ pub(crate) static RNG: SyncWrap<UnsafeCell<Option<Box<dyn RngCore>>>> =
 ///
 #[must_use]
 pub fn rng() -> &'static mut dyn RngCore {
-    unsafe { &mut *RNG.get() }
-        .as_mut()
-        .expect("RNG not yet initalized")
+    rng_slot().as_mut().expect("RNG not yet initalized")
 }
 
 ///
@@ -116,20 +134,42 @@ where
 
     // Rt limits
     limit: RuntimeLimit,
+    limit_reached: Option<RuntimeLimit>,
+    warmup: Option<SimTime>,
+    last_activity: SimTime,
 
     event_id: EventId,
     itr: usize,
+    restored: bool,
 
     // Misc
     quiet: bool,
-    profiler: Profiler<App::EventSet>,
+    pub(crate) profiler: Profiler<App::EventSet>,
+    observers: Vec<Box<dyn RuntimeObserver<App::EventSet>>>,
 
     #[allow(dead_code)]
-    permit: MutexGuard<'static, ()>,
+    permit: Permit,
 
     future_event_set: FutureEventSet<App>,
 }
 
+/// A description of an event dispatched using [`Runtime::step`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// The number of the event, counting all events dispatched by the runtime.
+    pub index: usize,
+    /// The simulation time the event was dispatched at.
+    pub time: SimTime,
+    /// A description of the event, as provided by [`Event::describe`].
+    pub description: String,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{} @ {}: {}", self.index, self.time, self.description)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 enum State {
     Ready,
@@ -191,7 +231,25 @@ where
 
     /// Indicates whether the simulation has reached its limit.
     pub fn has_reached_limit(&self) -> bool {
-        self.limit.applies(self.itr + 1, self.sim_time())
+        self.limit.applies(&self.view(self.sim_time()))
+    }
+
+    /// Returns the limit that stopped the last dispatching of events, if any.
+    ///
+    /// For limits combined with a logical OR, only the part that applied
+    /// is returned.
+    pub fn limit_reached(&self) -> Option<&RuntimeLimit> {
+        self.limit_reached.as_ref()
+    }
+
+    fn view(&self, time: SimTime) -> RuntimeView {
+        RuntimeView {
+            dispatched: self.itr,
+            remaining: self.future_event_set.len(),
+            time,
+            time_start: self.profiler.time_start,
+            last_activity: self.last_activity,
+        }
     }
 
     ///
@@ -313,7 +371,11 @@ where
         // (0) Publish sim-start message
         if !self.quiet {
             println!("\u{23A1}");
-            println!("\u{23A2} Simulation starting");
+            if self.restored {
+                println!("\u{23A2} Simulation resuming at {}", self.sim_time());
+            } else {
+                println!("\u{23A2} Simulation starting");
+            }
             println!(
                 "\u{23A2}  net [{}] cqueue [{}] async[{}]",
                 symbol!(FT_NET),
@@ -325,14 +387,23 @@ where
                 self.future_event_set.descriptor()
             );
             println!("\u{23A2}  Event limit := {}", self.limit);
+            if let Some(warmup) = self.warmup {
+                println!("\u{23A2}  Warm-up := {warmup}");
+            }
             println!("\u{23A3}");
         }
 
         // (1) Start profiler
         self.profiler.start();
+        for observer in &mut self.observers {
+            observer.on_sim_start(SimTime::now());
+        }
 
-        // (2) sim-starting on application object
-        A::Lifecycle::at_sim_start(self);
+        // (2) sim-starting on application object, unless the
+        // application was restored from a snapshot
+        if !self.restored {
+            A::Lifecycle::at_sim_start(self);
+        }
 
         self.state = State::Running;
     }
@@ -390,6 +461,49 @@ where
         while !self.dispatch_event() {}
     }
 
+    /// Executes the next runtime event, returning a description of the
+    /// handled event.
+    ///
+    /// Returns `None` if no events are left, or the runtime has reached
+    /// its limit.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the simulation has not been started.
+    pub fn step(&mut self) -> Option<Step> {
+        assert_eq!(
+            self.state,
+            State::Running,
+            "dispatching is only allowed for running simulations"
+        );
+
+        let (event, time) = self.next_event()?;
+        let step = Step {
+            index: self.itr,
+            time,
+            description: event.describe(),
+        };
+        event.handle(self);
+        Some(step)
+    }
+
+    /// Returns the next event and its deadline, without dispatching it.
+    ///
+    /// Returns `None` if no events are left.
+    pub fn peek_next(&self) -> Option<(&A::EventSet, SimTime)> {
+        self.future_event_set.peek()
+    }
+
+    /// An iterator over all pending events and their deadlines,
+    /// in the order they will be dispatched.
+    pub fn pending_events(&self) -> impl Iterator<Item = (&A::EventSet, SimTime)> {
+        let mut pending = self.future_event_set.iter().collect::<Vec<_>>();
+        pending.sort_by_key(|(_, handle)| (handle.time, handle.id));
+        pending
+            .into_iter()
+            .map(|(event, handle)| (event, handle.time))
+    }
+
     /// Decontructs the runtime and returns the application and the final `sim_time`.
     ///
     /// This funtions should only be used when running the simulation with manual calls
@@ -413,7 +527,10 @@ where
 
         // Call the fin-handler on the allocated application
         A::Lifecycle::at_sim_end(&mut self)?;
-        self.profiler.finish(self.itr);
+        self.profiler.finish(self.itr, self.limit_reached.take());
+        for observer in &mut self.observers {
+            observer.on_sim_end(SimTime::now());
+        }
 
         if self.future_event_set.is_empty() && self.itr == 0 {
             if !self.quiet {
@@ -450,6 +567,9 @@ where
                     self.future_event_set.len(),
                     time
                 );
+                if let Some(ref limit) = self.profiler.limit {
+                    println!("\u{23A2}  Reached limit {limit}");
+                }
                 println!("\u{23A3}");
             }
 
@@ -465,30 +585,48 @@ where
 
     /// Processes the next event in the future event list by calling its handler.
     /// Returns `true` if the simulation should stop.
-    ///
-    /// This function requires the caller to guarantee that at least one
-    /// event exists in the future event set.
-    #[allow(clippy::should_implement_trait)]
     fn dispatch_event(&mut self) -> bool {
-        if self.future_event_set.is_empty() {
-            return true;
+        match self.next_event() {
+            Some((event, _)) => {
+                event.handle(self);
+                false
+            }
+            None => true,
         }
+    }
 
-        let (event, time) = self.future_event_set.fetch_next();
+    /// Removes the next event from the future event list and advances the
+    /// simulation time, unless the simulation should stop.
+    fn next_event(&mut self) -> Option<(A::EventSet, SimTime)> {
+        let time = self.future_event_set.peek_time()?;
+        if let Some(limit) = self.limit.fired(&self.view(time)) {
+            self.limit_reached = Some(limit.clone());
+            return None;
+        }
+        self.limit_reached = None;
 
-        if self.limit.applies(self.itr + 1, time) {
-            self.future_event_set.add(time, event);
-            return true;
+        if let Some(warmup) = self.warmup.filter(|warmup| time >= *warmup) {
+            self.warmup = None;
+            if warmup > SimTime::now() {
+                SimTime::set_now(warmup);
+            }
+            A::Lifecycle::at_warmup_end(self);
         }
 
+        let (event, time) = self.future_event_set.fetch_next();
+
         self.itr += 1;
 
         // Let this be the only position where SimTime is changed
         SimTime::set_now(time);
 
-        event.handle(self);
-
-        false
+        if event.is_activity() {
+            self.last_activity = time;
+        }
+        for observer in &mut self.observers {
+            observer.on_event_dispatch(time, &event);
+        }
+        Some((event, time))
     }
 
     ///
@@ -530,8 +668,12 @@ where
     /// }
     /// ```
     ///
-    pub fn add_event_in(&mut self, event: impl Into<A::EventSet>, duration: impl Into<Duration>) {
-        self.add_event(event, self.sim_time() + duration.into());
+    pub fn add_event_in(
+        &mut self,
+        event: impl Into<A::EventSet>,
+        duration: impl Into<Duration>,
+    ) -> EventHandle {
+        self.add_event(event, self.sim_time() + duration.into())
     }
 
     ///
@@ -574,14 +716,84 @@ where
     /// }
     /// ```
     ///
-    pub fn add_event(&mut self, event: impl Into<A::EventSet>, time: SimTime) {
-        self.future_event_set.add(time, event);
+    pub fn add_event(&mut self, event: impl Into<A::EventSet>, time: SimTime) -> EventHandle {
+        let event = event.into();
+        for observer in &mut self.observers {
+            observer.on_event_scheduled(time, &event);
+        }
+
         self.event_id += 1;
+        self.future_event_set.add(time, event)
+    }
+
+    ///
+    /// Cancels a previously scheduled event, so that it will never be dispatched.
+    ///
+    /// Returns `true` if the event was still pending and has been removed from the
+    /// future event set. Returns `false` if the event was already dispatched, or
+    /// cancelled before. Cancelled events are neither counted by
+    /// [`num_events_remaining`](Runtime::num_events_remaining), nor reported in
+    /// [`Profiler::remaining`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use des::prelude::*;
+    ///
+    /// # struct MyApp();
+    /// # impl Application for MyApp {
+    /// #     type EventSet = MyEventSet;
+    /// #     type Lifecycle = ();
+    /// # }
+    /// #
+    /// # enum MyEventSet {
+    /// #     Timeout,
+    /// #     Response
+    /// # }
+    /// # impl Event<MyApp> for MyEventSet {
+    /// #     fn handle(self, rt: &mut Runtime<MyApp>) {}
+    /// # }
+    /// #
+    /// fn main() {
+    ///     let mut runtime = Builder::seeded(1).build(MyApp());
+    ///     let timeout = runtime.add_event(MyEventSet::Timeout, SimTime::from(10.0));
+    ///     runtime.add_event(MyEventSet::Response, SimTime::from(2.0));
+    ///
+    ///     // The response arrived in time
+    ///     assert!(runtime.cancel_event(timeout));
+    ///     assert_eq!(runtime.num_events_remaining(), 1);
+    ///
+    ///     match runtime.run() {
+    ///         Ok((_, time, profiler)) => {
+    ///             assert_eq!(time, SimTime::from(2.0));
+    ///             assert_eq!(profiler.event_count, 1);
+    ///         },
+    ///         _ => panic!("They can't do that! Shoot them or something!")
+    ///     }
+    /// }
+    /// ```
+    ///
+    pub fn cancel_event(&mut self, handle: EventHandle) -> bool {
+        self.future_event_set.cancel(handle)
+    }
+
+    /// Cancels all pending events, that match the predicate.
+    #[cfg_attr(not(feature = "net"), allow(dead_code))]
+    pub(crate) fn cancel_events_where(&mut self, mut f: impl FnMut(&A::EventSet) -> bool) {
+        let handles = self
+            .future_event_set
+            .iter()
+            .filter(|(event, _)| f(event))
+            .map(|(_, handle)| handle)
+            .collect::<Vec<_>>();
+        for handle in handles {
+            self.future_event_set.cancel(handle);
+        }
     }
 }
 
 cfg_net! {
-    use crate::net::{gate::{GateRef, Connection},  HandleMessageEvent, message::Message, MessageExitingConnection, module::ModuleRef, NetEvents, Sim};
+    use crate::net::{channel::ChannelMetrics, gate::{GateRef, Connection},  HandleMessageEvent, LinkChange, LinkStateChange, message::Message, MessageExitingConnection, module::ModuleRef, NetEvents, ObjectPath, Sim};
 
     impl<A> Runtime<Sim<A>> where
         A: EventLifecycle<Sim<A>>,{
@@ -593,13 +805,13 @@ cfg_net! {
             gate: GateRef,
             message: impl Into<Message>,
             time: SimTime,
-        ) {
+        ) -> EventHandle {
             let event = MessageExitingConnection {
                 con: Connection::new(gate),
                 msg: message.into(),
             };
 
-            self.add_event(NetEvents::MessageExitingConnection(event), time);
+            self.add_event(NetEvents::MessageExitingConnection(event), time)
         }
 
         ///
@@ -610,13 +822,103 @@ cfg_net! {
             module: impl Into<ModuleRef>,
             message: impl Into<Message>,
             time: SimTime,
-        ) {
+        ) -> EventHandle {
             let event = HandleMessageEvent {
                 module: module.into(),
                 message: message.into(),
             };
 
-            self.add_event(NetEvents::HandleMessageEvent(event), time);
+            self.add_event(NetEvents::HandleMessageEvent(event), time)
+        }
+
+        ///
+        /// Takes the link at a gate down at the given time.
+        ///
+        /// The link is the first connection on the path of the gate, given by
+        /// its full path (e.g. `alice.eth` or `alice.port[2]`). Both directions
+        /// are taken down, dropping all buffered messages, as well as
+        /// all messages currently on the medium. See [`Channel::set_down`]
+        /// for more information.
+        ///
+        /// [`Channel::set_down`]: crate::net::channel::Channel::set_down
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use des::prelude::*;
+        /// # struct Host;
+        /// # impl Module for Host {}
+        /// let mut sim = Sim::new(());
+        /// sim.node("alice", Host);
+        /// sim.node("bob", Host);
+        /// sim.gate("alice", "eth").connect(sim.gate("bob", "eth"), None);
+        ///
+        /// let mut rt = Builder::new().build(sim.freeze());
+        /// rt.link_down_at("alice.eth", SimTime::from(1.0));
+        /// rt.link_up_at("alice.eth", SimTime::from(2.0));
+        /// let _ = rt.run();
+        /// ```
+        ///
+        /// # Panics
+        ///
+        /// Panics if no gate exists at the given path.
+        pub fn link_down_at(
+            &mut self,
+            gate: impl Into<ObjectPath>,
+            time: SimTime,
+        ) -> EventHandle {
+            self.add_link_state_change(&gate.into(), LinkChange::Down, time)
+        }
+
+        ///
+        /// Brings the link at a gate up again at the given time.
+        ///
+        /// See [`link_down_at`](Self::link_down_at) for more information.
+        ///
+        /// # Panics
+        ///
+        /// Panics if no gate exists at the given path.
+        pub fn link_up_at(
+            &mut self,
+            gate: impl Into<ObjectPath>,
+            time: SimTime,
+        ) -> EventHandle {
+            self.add_link_state_change(&gate.into(), LinkChange::Up, time)
+        }
+
+        ///
+        /// Replaces the metrics of the link at a gate at the given time, in both directions.
+        ///
+        /// See [`link_down_at`](Self::link_down_at) and [`Channel::set_metrics`] for more information.
+        ///
+        /// [`Channel::set_metrics`]: crate::net::channel::Channel::set_metrics
+        ///
+        /// # Panics
+        ///
+        /// Panics if no gate exists at the given path.
+        pub fn set_link_metrics_at(
+            &mut self,
+            gate: impl Into<ObjectPath>,
+            metrics: ChannelMetrics,
+            time: SimTime,
+        ) -> EventHandle {
+            self.add_link_state_change(&gate.into(), LinkChange::Metrics(metrics), time)
+        }
+
+        fn add_link_state_change(
+            &mut self,
+            path: &ObjectPath,
+            change: LinkChange,
+            time: SimTime,
+        ) -> EventHandle {
+            let Some(gate) = self.app.find_gate(path) else {
+                panic!("cannot change link state, because gate '{path}' does not exist")
+            };
+
+            self.add_event(
+                NetEvents::LinkStateChange(LinkStateChange { gate, change }),
+                time,
+            )
         }
     }
 }
@@ -0,0 +1,74 @@
+use crate::time::SimTime;
+
+///
+/// A hook into the event processing of a [`Runtime`](crate::runtime::Runtime).
+///
+/// Observers are registered using [`AppBuilder::observer`](crate::runtime::AppBuilder::observer)
+/// and are notified about all events passing through the future event set,
+/// independent of the application. This can be used to write event traces,
+/// report the progress of long running simulations or to compare the event
+/// sequence of multiple runs.
+///
+/// All callbacks default to a no-op, so implementations only need to
+/// provide the callbacks they are interested in.
+///
+/// # Examples
+///
+/// ```
+/// use des::prelude::*;
+/// use des::runtime::RuntimeObserver;
+///
+/// struct Progress;
+/// impl<E> RuntimeObserver<E> for Progress {
+///     fn on_event_dispatch(&mut self, time: SimTime, _event: &E) {
+///         println!("dispatching event at {time}");
+///     }
+/// }
+///
+/// # struct App;
+/// # impl Application for App {
+/// #     type EventSet = Events;
+/// #     type Lifecycle = ();
+/// # }
+/// # enum Events {}
+/// # impl Event<App> for Events {
+/// #     fn handle(self, rt: &mut Runtime<App>) {}
+/// # }
+/// let rt = Builder::seeded(123)
+///     .with_app::<App>()
+///     .observer(Progress)
+///     .build(App);
+/// ```
+///
+pub trait RuntimeObserver<E> {
+    ///
+    /// Called once when the simulation is started, before the
+    /// application is notified.
+    ///
+    fn on_sim_start(&mut self, time: SimTime) {
+        let _ = time;
+    }
+
+    ///
+    /// Called when an event is added to the future event set, with the
+    /// deadline of the event.
+    ///
+    fn on_event_scheduled(&mut self, time: SimTime, event: &E) {
+        let _ = (time, event);
+    }
+
+    ///
+    /// Called right before an event is handled, with the current simulation time.
+    ///
+    fn on_event_dispatch(&mut self, time: SimTime, event: &E) {
+        let _ = (time, event);
+    }
+
+    ///
+    /// Called once when the simulation is finished, after the
+    /// application was notified.
+    ///
+    fn on_sim_end(&mut self, time: SimTime) {
+        let _ = time;
+    }
+}
@@ -0,0 +1,358 @@
+use std::{fmt, ops::Range};
+
+use serde::{Deserialize, Serialize};
+
+use super::Summary;
+use crate::net::{Globals, ObjectPath};
+
+/// An estimate of a mean, with a 95% confidence interval.
+///
+/// Confidence intervals assume that the observations are independent and
+/// approximately normally distributed. Observations are either the results
+/// of independent replications, or the means of batches within a single run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    count: usize,
+    mean: f64,
+    variance: f64,
+    half_width: f64,
+}
+
+impl Estimate {
+    /// Estimates the mean over the results of independent replications.
+    ///
+    /// Returns `None` if less than two values are provided.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use des::stats::Estimate;
+    /// let estimate = Estimate::replications([9.8, 10.1, 10.0, 10.3, 9.9]).unwrap();
+    /// assert!(estimate.interval().contains(&10.0));
+    /// ```
+    pub fn replications(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        Self::from_summary(&values.into_iter().collect())
+    }
+
+    /// Estimates the mean of a series of values by splitting it into `batches`
+    /// batches of equal size, and using the mean of each batch as one observation.
+    ///
+    /// Values that do not fill a complete batch at the end of the series are
+    /// ignored. Returns `None` if less than two batches can be formed.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn batch_means(values: &[f64], batches: usize) -> Option<Self> {
+        if batches < 2 || values.len() < batches {
+            return None;
+        }
+        let size = values.len() / batches;
+        Self::from_summary(
+            &values
+                .chunks_exact(size)
+                .take(batches)
+                .map(|batch| batch.iter().sum::<f64>() / size as f64)
+                .collect(),
+        )
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn from_summary(summary: &Summary) -> Option<Self> {
+        let variance = summary.variance()?;
+        let count = summary.count();
+        Some(Self {
+            count,
+            mean: summary.mean()?,
+            variance,
+            half_width: t_quantile(count - 1) * (variance / count as f64).sqrt(),
+        })
+    }
+
+    /// The number of observations the estimate is based on.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The estimated mean.
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// The sample variance of the observations.
+    #[must_use]
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    /// The half-width of the 95% confidence interval.
+    #[must_use]
+    pub fn half_width(&self) -> f64 {
+        self.half_width
+    }
+
+    /// The half-width of the confidence interval, relative to the mean.
+    ///
+    /// Returns infinity, if the mean is zero, but the half-width is not.
+    #[must_use]
+    pub fn relative_half_width(&self) -> f64 {
+        if self.half_width == 0.0 {
+            0.0
+        } else {
+            self.half_width / self.mean.abs()
+        }
+    }
+
+    /// The 95% confidence interval of the mean.
+    #[must_use]
+    pub fn interval(&self) -> Range<f64> {
+        (self.mean - self.half_width)..(self.mean + self.half_width)
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} \u{00b1} {}", self.mean, self.half_width)
+    }
+}
+
+/// The 0.975 quantile of the Student's t-distribution with `df` degrees of freedom.
+///
+/// Small degrees of freedom use tabulated values, larger ones use
+/// the Cornish-Fisher expansion around the normal quantile.
+#[allow(clippy::cast_precision_loss)]
+fn t_quantile(df: usize) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.7062, 4.3027, 3.1824, 2.7764, 2.5706, 2.4469, 2.3646, 2.3060, 2.2622, 2.2281, 2.2010,
+        2.1788, 2.1604, 2.1448, 2.1314, 2.1199, 2.1098, 2.1009, 2.0930, 2.0860, 2.0796, 2.0739,
+        2.0687, 2.0639, 2.0595, 2.0555, 2.0518, 2.0484, 2.0452, 2.0423,
+    ];
+    const Z: f64 = 1.959_964;
+
+    if (1..=TABLE.len()).contains(&df) {
+        return TABLE[df - 1];
+    }
+
+    let df = df as f64;
+    let z3 = Z.powi(3);
+    let z5 = Z.powi(5);
+    let z7 = Z.powi(7);
+    Z + (z3 + Z) / (4.0 * df)
+        + (5.0 * z5 + 16.0 * z3 + 3.0 * Z) / (96.0 * df.powi(2))
+        + (3.0 * z7 + 19.0 * z5 + 17.0 * z3 - 15.0 * Z) / (384.0 * df.powi(3))
+}
+
+/// An incremental batch means estimator.
+///
+/// Values are grouped into batches of equal size, while they are recorded.
+/// Once twice the minimum number of batches is complete, adjacent batches
+/// are merged, doubling the batch size. Thus the estimator only stores
+/// the means of a bounded number of batches, while the batches grow with
+/// the number of recorded values, reducing their correlation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchMeans {
+    min_batches: usize,
+    batch_size: usize,
+    batches: Vec<f64>,
+    partial_sum: f64,
+    partial_count: usize,
+}
+
+impl BatchMeans {
+    /// The minimum number of batches used by [`Vector`](super::Vector)s.
+    pub const DEFAULT_MIN_BATCHES: usize = 30;
+
+    /// Creates a new estimator, that requires at least `min_batches`
+    /// batches to provide an estimate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_batches` is less than two.
+    #[must_use]
+    pub fn new(min_batches: usize) -> Self {
+        assert!(min_batches >= 2, "batch means require at least two batches");
+        Self {
+            min_batches,
+            batch_size: 1,
+            batches: Vec::with_capacity(2 * min_batches),
+            partial_sum: 0.0,
+            partial_count: 0,
+        }
+    }
+
+    /// Adds a value to the current batch.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn record(&mut self, value: f64) {
+        self.partial_sum += value;
+        self.partial_count += 1;
+        if self.partial_count < self.batch_size {
+            return;
+        }
+
+        self.batches.push(self.partial_sum / self.batch_size as f64);
+        self.partial_sum = 0.0;
+        self.partial_count = 0;
+
+        if self.batches.len() == 2 * self.min_batches {
+            for i in 0..self.min_batches {
+                self.batches[i] = f64::midpoint(self.batches[2 * i], self.batches[2 * i + 1]);
+            }
+            self.batches.truncate(self.min_batches);
+            self.batch_size *= 2;
+        }
+    }
+
+    /// The number of values per batch.
+    #[must_use]
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// The number of complete batches.
+    #[must_use]
+    pub fn num_batches(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Estimates the mean over all complete batches.
+    ///
+    /// Returns `None` if less than the minimum number of batches are complete.
+    #[must_use]
+    pub fn estimate(&self) -> Option<Estimate> {
+        if self.batches.len() < self.min_batches {
+            return None;
+        }
+        Estimate::from_summary(&self.batches.iter().copied().collect())
+    }
+}
+
+impl Default for BatchMeans {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MIN_BATCHES)
+    }
+}
+
+/// A target precision for the mean of a vector.
+///
+/// The target is reached, once the relative half-width of the batch means
+/// confidence interval of the vector drops below the given threshold (see
+/// [`Vector::batch_means`](super::Vector::batch_means)). Used with
+/// [`RuntimeLimit::Precision`](crate::runtime::RuntimeLimit::Precision)
+/// to stop a simulation once a statistic is precise enough.
+///
+/// # Examples
+///
+/// ```
+/// # use des::prelude::*;
+/// # use des::runtime::RuntimeLimit;
+/// use des::stats::PrecisionTarget;
+///
+/// // Stop once the mean delay is known within ±5%, but after 1000s at the latest.
+/// let builder = Builder::new()
+///     .limit(RuntimeLimit::Precision(PrecisionTarget::new("sink", "delay", 0.05)))
+///     .max_time(1000.0.into());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrecisionTarget {
+    module: ObjectPath,
+    name: String,
+    relative_half_width: f64,
+}
+
+impl PrecisionTarget {
+    /// Creates a target for the vector `name` of the module at `module`.
+    #[must_use]
+    pub fn new(module: impl Into<ObjectPath>, name: &str, relative_half_width: f64) -> Self {
+        Self {
+            module: module.into(),
+            name: name.to_string(),
+            relative_half_width,
+        }
+    }
+
+    /// Indicates whether the target is reached within the current simulation.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn is_reached(&self) -> bool {
+        let Some(globals) = Globals::try_current() else {
+            return false;
+        };
+        let stats = globals.stats.lock().expect("failed to lock statistics");
+        stats
+            .vector(self.module.as_str(), &self.name)
+            .and_then(super::Vector::batch_means)
+            .is_some_and(|estimate| estimate.relative_half_width() < self.relative_half_width)
+    }
+}
+
+impl PartialEq for PrecisionTarget {
+    fn eq(&self, other: &Self) -> bool {
+        self.module == other.module
+            && self.name == other.name
+            && self.relative_half_width.to_bits() == other.relative_half_width.to_bits()
+    }
+}
+
+impl Eq for PrecisionTarget {}
+
+impl fmt::Display for PrecisionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RelativeHalfWidth({}:{} < {})",
+            self.module, self.name, self.relative_half_width
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_quantiles() {
+        assert!((t_quantile(1) - 12.7062).abs() < 1e-4);
+        assert!((t_quantile(30) - 2.0423).abs() < 1e-4);
+        assert!((t_quantile(31) - 2.0395).abs() < 1e-4);
+        assert!((t_quantile(60) - 2.0003).abs() < 1e-4);
+        assert!((t_quantile(1000) - 1.9623).abs() < 1e-4);
+    }
+
+    #[test]
+    fn estimate_from_batches() {
+        let values = (0..100).map(f64::from).collect::<Vec<_>>();
+        let estimate = Estimate::batch_means(&values, 4).unwrap();
+        assert_eq!(estimate.count(), 4);
+        assert!((estimate.mean() - 49.5).abs() < 1e-9);
+        assert!(estimate.interval().contains(&49.5));
+
+        assert!(Estimate::batch_means(&values, 1).is_none());
+        assert!(Estimate::batch_means(&values[..3], 4).is_none());
+    }
+
+    #[test]
+    fn batch_means_merge_batches() {
+        let mut batches = BatchMeans::new(4);
+        for i in 0..7 {
+            batches.record(f64::from(i));
+        }
+        assert_eq!(batches.batch_size(), 1);
+        assert_eq!(batches.num_batches(), 7);
+
+        batches.record(7.0);
+        assert_eq!(batches.batch_size(), 2);
+        assert_eq!(batches.num_batches(), 4);
+
+        let values = (0..100).map(f64::from).collect::<Vec<_>>();
+        for value in &values[8..] {
+            batches.record(*value);
+        }
+        let estimate = batches.estimate().unwrap();
+        let expected = Estimate::batch_means(
+            &values[..batches.batch_size() * batches.num_batches()],
+            batches.num_batches(),
+        )
+        .unwrap();
+        assert_eq!(estimate, expected);
+    }
+}
@@ -0,0 +1,821 @@
+//! Statistics collected by modules during a simulation.
+//!
+//! Modules can record three kinds of statistics:
+//! - scalars, single values like the total number of processed packets,
+//! - vectors, series of values timestamped with the current simulation time,
+//! - histograms, distributions of values over a fixed set of bins.
+//!
+//! All records are namespaced by the path of the module that recorded them,
+//! and collected by the [`Sim`](crate::net::Sim). Once the simulation has
+//! finished, the collected [`Statistics`] are returned as part of the
+//! [`Profiler`](crate::runtime::Profiler), and can be exported as CSV or JSON.
+//!
+//! For steady-state studies, statistics recorded during the transient phase
+//! can be discarded using [`Builder::warmup`](crate::runtime::Builder::warmup).
+//! Confidence intervals can be computed using batch means over a single
+//! run, or over independent replications, see [`Estimate`]. A run can be
+//! stopped, once a statistic is precise enough, see [`PrecisionTarget`].
+//!
+//! # Examples
+//!
+//! ```
+//! use des::prelude::*;
+//! use des::stats;
+//!
+//! struct Queue {
+//!     len: usize,
+//! }
+//!
+//! impl Module for Queue {
+//!     fn at_sim_start(&mut self, _stage: usize) {
+//!         schedule_in(Message::default(), Duration::from_secs(1));
+//!     }
+//!
+//!     fn handle_message(&mut self, _msg: Message) {
+//!         self.len += 1;
+//!         stats::vector("queue_len").record(self.len as f64);
+//!         if self.len < 10 {
+//!             schedule_in(Message::default(), Duration::from_secs(1));
+//!         }
+//!     }
+//!
+//!     fn at_sim_end(&mut self) -> Result<(), RuntimeError> {
+//!         stats::record_scalar("final_len", self.len as f64);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut sim = Sim::new(());
+//! sim.node("queue", Queue { len: 0 });
+//!
+//! let (_, _, profiler) = Builder::new().build(sim.freeze()).run().unwrap();
+//! let statistics = profiler.statistics;
+//! assert_eq!(statistics.scalar("queue", "final_len"), Some(10.0));
+//! assert_eq!(statistics.vector("queue", "queue_len").unwrap().len(), 10);
+//!
+//! let csv = statistics.to_csv();
+//! ```
+
+use std::{io, ops::Range};
+
+mod estimate;
+pub use self::estimate::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    experiment::csv_row,
+    net::{module::try_current, Globals, ObjectPath},
+    time::SimTime,
+};
+
+/// Records a scalar value for the current module.
+///
+/// Should a scalar with the same name already exist for this module,
+/// its value will be replaced.
+///
+/// > *This function should only be called within the simulation*
+///
+/// # Panics
+///
+/// This function panics if no simulation is active.
+pub fn record_scalar(name: &str, value: f64) {
+    with_statistics(|stats, module| stats.record_scalar(module, name, value));
+}
+
+/// Returns a handle to the vector `name` of the current module,
+/// creating it if it does not yet exist.
+///
+/// > *This function should only be called within the simulation*
+///
+/// # Panics
+///
+/// This function panics if no simulation is active.
+#[must_use]
+pub fn vector(name: &str) -> VectorRef {
+    with_statistics(|stats, module| VectorRef {
+        index: stats.vector_index(module, name),
+    })
+}
+
+/// Returns a handle to the histogram `name` of the current module,
+/// creating it if it does not yet exist.
+///
+/// A new histogram splits `range` into `bins` bins of equal width. Values
+/// outside of the range are counted as underflow or overflow. Should the
+/// histogram already exist, `range` and `bins` are ignored.
+///
+/// > *This function should only be called within the simulation*
+///
+/// # Panics
+///
+/// This function panics if no simulation is active, or if a new histogram
+/// would have no bins or an empty range.
+#[must_use]
+pub fn histogram(name: &str, range: Range<f64>, bins: usize) -> HistogramRef {
+    with_statistics(|stats, module| HistogramRef {
+        index: stats.histogram_index(module, name, range, bins),
+    })
+}
+
+fn with_statistics<R>(f: impl FnOnce(&mut Statistics, ObjectPath) -> R) -> R {
+    let module = try_current().map(|ctx| ctx.path()).unwrap_or_default();
+    let globals = Globals::current();
+    let mut stats = globals.stats.lock().expect("failed to lock statistics");
+    f(&mut stats, module)
+}
+
+/// A handle to a vector of the current simulation.
+///
+/// The handle may be stored, but it is only valid within
+/// the simulation it was created in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VectorRef {
+    index: usize,
+}
+
+impl VectorRef {
+    /// Appends a value to the vector, timestamped with the current simulation time.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no simulation is active.
+    pub fn record(&self, value: f64) {
+        with_statistics(|stats, _| {
+            stats.vectors[self.index].record(value);
+        });
+    }
+}
+
+/// A handle to a histogram of the current simulation.
+///
+/// The handle may be stored, but it is only valid within
+/// the simulation it was created in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistogramRef {
+    index: usize,
+}
+
+impl HistogramRef {
+    /// Adds a value to the histogram.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no simulation is active.
+    pub fn record(&self, value: f64) {
+        with_statistics(|stats, _| stats.histograms[self.index].record(value));
+    }
+}
+
+/// Summary statistics over a set of values.
+///
+/// Mean and variance are computed incrementally, so that
+/// no values need to be stored.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Summary {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Summary {
+    /// Creates a summary of no values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value to the summary.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn record(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// The number of recorded values.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The mean of all values, if any values were recorded.
+    #[must_use]
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// The sample variance of all values, if at least two values were recorded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// The sample standard deviation of all values, if at least two values were recorded.
+    #[must_use]
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// The smallest value, if any values were recorded.
+    #[must_use]
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// The largest value, if any values were recorded.
+    #[must_use]
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+}
+
+impl PartialEq for Summary {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count
+            && self.mean.to_bits() == other.mean.to_bits()
+            && self.m2.to_bits() == other.m2.to_bits()
+            && self.min.to_bits() == other.min.to_bits()
+            && self.max.to_bits() == other.max.to_bits()
+    }
+}
+
+impl Eq for Summary {}
+
+impl FromIterator<f64> for Summary {
+    fn from_iter<T: IntoIterator<Item = f64>>(iter: T) -> Self {
+        let mut summary = Summary::new();
+        for value in iter {
+            summary.record(value);
+        }
+        summary
+    }
+}
+
+/// A single named value recorded by a module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scalar {
+    module: ObjectPath,
+    name: String,
+    value: f64,
+}
+
+impl Scalar {
+    /// The path of the module that recorded the scalar.
+    #[must_use]
+    pub fn module(&self) -> &ObjectPath {
+        &self.module
+    }
+
+    /// The name of the scalar.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The recorded value.
+    #[must_use]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl PartialEq for Scalar {
+    fn eq(&self, other: &Self) -> bool {
+        self.module == other.module
+            && self.name == other.name
+            && self.value.to_bits() == other.value.to_bits()
+    }
+}
+
+impl Eq for Scalar {}
+
+/// A series of timestamped values recorded by a module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "VectorData")]
+pub struct Vector {
+    module: ObjectPath,
+    name: String,
+    samples: Vec<(SimTime, f64)>,
+    #[serde(skip)]
+    batches: BatchMeans,
+}
+
+/// The serialized form of a [`Vector`], since the batch means
+/// can be recomputed from the samples.
+#[derive(Deserialize)]
+struct VectorData {
+    module: ObjectPath,
+    name: String,
+    samples: Vec<(SimTime, f64)>,
+}
+
+impl From<VectorData> for Vector {
+    fn from(data: VectorData) -> Self {
+        let mut batches = BatchMeans::default();
+        for (_, value) in &data.samples {
+            batches.record(*value);
+        }
+        Self {
+            module: data.module,
+            name: data.name,
+            samples: data.samples,
+            batches,
+        }
+    }
+}
+
+impl Vector {
+    fn new(module: ObjectPath, name: &str) -> Self {
+        Self {
+            module,
+            name: name.to_string(),
+            samples: Vec::new(),
+            batches: BatchMeans::default(),
+        }
+    }
+
+    fn record(&mut self, value: f64) {
+        self.samples.push((SimTime::now(), value));
+        self.batches.record(value);
+    }
+
+    fn reset(&mut self) {
+        self.samples.clear();
+        self.batches = BatchMeans::default();
+    }
+
+    /// The path of the module that recorded the vector.
+    #[must_use]
+    pub fn module(&self) -> &ObjectPath {
+        &self.module
+    }
+
+    /// The name of the vector.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of recorded values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Indicates whether no values were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// The recorded values, with the simulation time they were recorded at.
+    #[must_use]
+    pub fn samples(&self) -> &[(SimTime, f64)] {
+        &self.samples
+    }
+
+    /// Summary statistics over all recorded values.
+    #[must_use]
+    pub fn summary(&self) -> Summary {
+        self.samples.iter().map(|(_, value)| *value).collect()
+    }
+
+    /// A confidence interval for the mean of the vector, using batch means.
+    ///
+    /// The samples are grouped into batches as they are recorded, see
+    /// [`BatchMeans`] for more information. Returns `None` if not enough
+    /// samples were recorded to form the minimum number of batches.
+    #[must_use]
+    pub fn batch_means(&self) -> Option<Estimate> {
+        self.batches.estimate()
+    }
+}
+
+impl PartialEq for Vector {
+    // The batch means are derived from the samples.
+    fn eq(&self, other: &Self) -> bool {
+        self.module == other.module
+            && self.name == other.name
+            && self.samples.len() == other.samples.len()
+            && self
+                .samples
+                .iter()
+                .zip(&other.samples)
+                .all(|(lhs, rhs)| lhs.0 == rhs.0 && lhs.1.to_bits() == rhs.1.to_bits())
+    }
+}
+
+impl Eq for Vector {}
+
+/// A distribution of values recorded by a module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Histogram {
+    module: ObjectPath,
+    name: String,
+    lower: f64,
+    upper: f64,
+    bins: Vec<u64>,
+    underflow: u64,
+    overflow: u64,
+    summary: Summary,
+}
+
+impl Histogram {
+    fn new(module: ObjectPath, name: &str, range: Range<f64>, bins: usize) -> Self {
+        assert!(bins > 0, "histogram '{name}' must have at least one bin");
+        assert!(
+            range.start < range.end,
+            "histogram '{name}' must have a non-empty range"
+        );
+
+        Self {
+            module,
+            name: name.to_string(),
+            lower: range.start,
+            upper: range.end,
+            bins: vec![0; bins],
+            underflow: 0,
+            overflow: 0,
+            summary: Summary::new(),
+        }
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn record(&mut self, value: f64) {
+        self.summary.record(value);
+        if value < self.lower {
+            self.underflow += 1;
+        } else if value >= self.upper {
+            self.overflow += 1;
+        } else {
+            let bin = ((value - self.lower) / self.width()) as usize;
+            // Rounding may place values just below the upper bound out of range.
+            let bin = bin.min(self.bins.len() - 1);
+            self.bins[bin] += 1;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.bins.fill(0);
+        self.underflow = 0;
+        self.overflow = 0;
+        self.summary = Summary::new();
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn width(&self) -> f64 {
+        (self.upper - self.lower) / self.bins.len() as f64
+    }
+
+    /// The path of the module that recorded the histogram.
+    #[must_use]
+    pub fn module(&self) -> &ObjectPath {
+        &self.module
+    }
+
+    /// The name of the histogram.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The range covered by the bins.
+    #[must_use]
+    pub fn range(&self) -> Range<f64> {
+        self.lower..self.upper
+    }
+
+    /// The bins of the histogram, with their range and the number of values within.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bins(&self) -> impl Iterator<Item = (Range<f64>, u64)> + '_ {
+        let width = self.width();
+        self.bins.iter().enumerate().map(move |(i, count)| {
+            let lower = self.lower + width * i as f64;
+            (lower..lower + width, *count)
+        })
+    }
+
+    /// The number of values below the range of the histogram.
+    #[must_use]
+    pub fn underflow(&self) -> u64 {
+        self.underflow
+    }
+
+    /// The number of values above the range of the histogram.
+    #[must_use]
+    pub fn overflow(&self) -> u64 {
+        self.overflow
+    }
+
+    /// Summary statistics over all recorded values, including under- and overflows.
+    #[must_use]
+    pub fn summary(&self) -> Summary {
+        self.summary
+    }
+}
+
+impl PartialEq for Histogram {
+    fn eq(&self, other: &Self) -> bool {
+        self.module == other.module
+            && self.name == other.name
+            && self.lower.to_bits() == other.lower.to_bits()
+            && self.upper.to_bits() == other.upper.to_bits()
+            && self.bins == other.bins
+            && self.underflow == other.underflow
+            && self.overflow == other.overflow
+            && self.summary == other.summary
+    }
+}
+
+impl Eq for Histogram {}
+
+/// All statistics recorded within a simulation.
+///
+/// Statistics are collected by a [`Sim`](crate::net::Sim) and can be
+/// accessed through [`Globals::statistics`], or the
+/// [`Profiler`](crate::runtime::Profiler) returned from
+/// [`Runtime::run`](crate::runtime::Runtime::run).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Statistics {
+    scalars: Vec<Scalar>,
+    vectors: Vec<Vector>,
+    histograms: Vec<Histogram>,
+}
+
+impl Statistics {
+    /// Indicates whether no statistics were recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.scalars.is_empty() && self.vectors.is_empty() && self.histograms.is_empty()
+    }
+
+    /// All recorded scalars, in the order they were first recorded.
+    #[must_use]
+    pub fn scalars(&self) -> &[Scalar] {
+        &self.scalars
+    }
+
+    /// All recorded vectors, in the order they were created.
+    #[must_use]
+    pub fn vectors(&self) -> &[Vector] {
+        &self.vectors
+    }
+
+    /// All recorded histograms, in the order they were created.
+    #[must_use]
+    pub fn histograms(&self) -> &[Histogram] {
+        &self.histograms
+    }
+
+    /// Returns the value of the scalar `name` recorded by the module at `module`.
+    #[must_use]
+    pub fn scalar(&self, module: &str, name: &str) -> Option<f64> {
+        self.scalars
+            .iter()
+            .find(|s| s.module.as_str() == module && s.name == name)
+            .map(Scalar::value)
+    }
+
+    /// Returns the vector `name` recorded by the module at `module`.
+    #[must_use]
+    pub fn vector(&self, module: &str, name: &str) -> Option<&Vector> {
+        self.vectors
+            .iter()
+            .find(|v| v.module.as_str() == module && v.name == name)
+    }
+
+    /// Returns the histogram `name` recorded by the module at `module`.
+    #[must_use]
+    pub fn histogram(&self, module: &str, name: &str) -> Option<&Histogram> {
+        self.histograms
+            .iter()
+            .find(|h| h.module.as_str() == module && h.name == name)
+    }
+
+    /// Discards all recorded values.
+    ///
+    /// Scalars are removed, while vectors and histograms are emptied, so that
+    /// existing handles remain valid. This is done automatically at the end of
+    /// the warm-up period, see [`Builder::warmup`](crate::runtime::Builder::warmup).
+    pub fn reset(&mut self) {
+        self.scalars.clear();
+        self.vectors.iter_mut().for_each(Vector::reset);
+        self.histograms.iter_mut().for_each(Histogram::reset);
+    }
+
+    fn record_scalar(&mut self, module: ObjectPath, name: &str, value: f64) {
+        if let Some(scalar) = self
+            .scalars
+            .iter_mut()
+            .find(|s| s.module == module && s.name == name)
+        {
+            scalar.value = value;
+        } else {
+            self.scalars.push(Scalar {
+                module,
+                name: name.to_string(),
+                value,
+            });
+        }
+    }
+
+    fn vector_index(&mut self, module: ObjectPath, name: &str) -> usize {
+        if let Some(index) = self
+            .vectors
+            .iter()
+            .position(|v| v.module == module && v.name == name)
+        {
+            return index;
+        }
+        self.vectors.push(Vector::new(module, name));
+        self.vectors.len() - 1
+    }
+
+    fn histogram_index(
+        &mut self,
+        module: ObjectPath,
+        name: &str,
+        range: Range<f64>,
+        bins: usize,
+    ) -> usize {
+        if let Some(index) = self
+            .histograms
+            .iter()
+            .position(|h| h.module == module && h.name == name)
+        {
+            return index;
+        }
+        self.histograms
+            .push(Histogram::new(module, name, range, bins));
+        self.histograms.len() - 1
+    }
+
+    /// Formats the statistics as a table of comma-separated values.
+    ///
+    /// The table has the columns `kind,module,name,key,value`, with one row
+    /// per scalar, one row per sample of a vector, and one row per bin of
+    /// a histogram. The `key` of a vector sample is the simulation time
+    /// it was recorded at, the `key` of a bin is its lower bound. Underflows
+    /// use the key `-inf`, while overflows use the upper bound of the histogram.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_csv(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_csv(&mut buf)
+            .expect("writing to a vec cannot fail");
+        String::from_utf8(buf).expect("csv output is valid UTF-8")
+    }
+
+    /// Writes the statistics as CSV, see [`Statistics::to_csv`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_csv(&self, mut w: impl io::Write) -> io::Result<()> {
+        let row = |kind: &str, module: &ObjectPath, name: &str, key: String, value: String| {
+            csv_row(
+                [
+                    kind.to_string(),
+                    module.to_string(),
+                    name.to_string(),
+                    key,
+                    value,
+                ]
+                .into_iter(),
+            )
+        };
+
+        writeln!(w, "kind,module,name,key,value")?;
+        for scalar in &self.scalars {
+            let line = row(
+                "scalar",
+                &scalar.module,
+                &scalar.name,
+                String::new(),
+                scalar.value.to_string(),
+            );
+            writeln!(w, "{line}")?;
+        }
+        for vector in &self.vectors {
+            for (time, value) in &vector.samples {
+                let line = row(
+                    "vector",
+                    &vector.module,
+                    &vector.name,
+                    time.as_secs_f64().to_string(),
+                    value.to_string(),
+                );
+                writeln!(w, "{line}")?;
+            }
+        }
+        for histogram in &self.histograms {
+            let bins = [("-inf".to_string(), histogram.underflow)]
+                .into_iter()
+                .chain(
+                    histogram
+                        .bins()
+                        .map(|(range, count)| (range.start.to_string(), count)),
+                )
+                .chain([(histogram.upper.to_string(), histogram.overflow)]);
+            for (key, count) in bins {
+                let line = row(
+                    "histogram",
+                    &histogram.module,
+                    &histogram.name,
+                    key,
+                    count.to_string(),
+                );
+                writeln!(w, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats the statistics as a JSON object.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("statistics are serializable")
+    }
+
+    /// Writes the statistics as JSON, see [`Statistics::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_json(&self, w: impl io::Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(w, self).map_err(io::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let summary = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .into_iter()
+            .collect::<Summary>();
+        assert_eq!(summary.count(), 8);
+        assert_eq!(summary.mean(), Some(5.0));
+        assert_eq!(summary.min(), Some(2.0));
+        assert_eq!(summary.max(), Some(9.0));
+        assert!((summary.variance().unwrap() - 32.0 / 7.0).abs() < 1e-12);
+
+        let empty = Summary::new();
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.stddev(), None);
+    }
+
+    #[test]
+    fn histogram_bins() {
+        let mut histogram = Histogram::new(ObjectPath::from("a"), "h", 0.0..1.0, 4);
+        for value in [-1.0, 0.0, 0.1, 0.3, 0.5, 0.99, 1.0, 2.0] {
+            histogram.record(value);
+        }
+
+        assert_eq!(histogram.underflow(), 1);
+        assert_eq!(histogram.overflow(), 2);
+        assert_eq!(
+            histogram.bins().collect::<Vec<_>>(),
+            [
+                (0.0..0.25, 2),
+                (0.25..0.5, 1),
+                (0.5..0.75, 1),
+                (0.75..1.0, 1)
+            ]
+        );
+        assert_eq!(histogram.summary().count(), 8);
+    }
+
+    #[test]
+    fn scalars_are_replaced() {
+        let mut stats = Statistics::default();
+        stats.record_scalar(ObjectPath::from("a"), "x", 1.0);
+        stats.record_scalar(ObjectPath::from("b"), "x", 2.0);
+        stats.record_scalar(ObjectPath::from("a"), "x", 3.0);
+
+        assert_eq!(stats.scalars().len(), 2);
+        assert_eq!(stats.scalar("a", "x"), Some(3.0));
+        assert_eq!(stats.scalar("b", "x"), Some(2.0));
+        assert_eq!(stats.scalar("c", "x"), None);
+    }
+}
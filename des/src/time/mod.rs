@@ -31,9 +31,9 @@ use serde::de::Visitor;
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Serialize};
 
+use std::cell::Cell;
 use std::fmt::{Debug, Display};
 use std::ops::{Deref, Div, Sub, SubAssign};
-use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 cfg_async! {
     pub mod error;
@@ -51,7 +51,11 @@ cfg_async! {
     pub use interval::*;
 }
 
-static SIMTIME: (AtomicU64, AtomicU32) = (AtomicU64::new(0), AtomicU32::new(0));
+thread_local! {
+    // Each thread runs at most one simulation at a time,
+    // so the simulation time is thread-local.
+    static SIMTIME: Cell<SimTime> = const { Cell::new(SimTime::ZERO) };
+}
 
 ///
 /// A specific point of time in the simulation.
@@ -63,6 +67,9 @@ pub struct SimTime(Duration);
 impl SimTime {
     /// Returns an instant corresponding to "now" in the simulation context.
     ///
+    /// The simulation time is bound to the current thread, so
+    /// threads that never ran a simulation observe [`SimTime::ZERO`].
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -72,18 +79,14 @@ impl SimTime {
     /// ```
     #[must_use]
     pub fn now() -> Self {
-        SimTime(Duration::new(
-            SIMTIME.0.load(Ordering::SeqCst),
-            SIMTIME.1.load(Ordering::SeqCst),
-        ))
+        SIMTIME.with(Cell::get)
     }
 
     ///
     /// Sets the sim time
     ///
     pub(crate) fn set_now(time: SimTime) {
-        SIMTIME.0.store(time.as_secs(), Ordering::SeqCst);
-        SIMTIME.1.store(time.subsec_nanos(), Ordering::SeqCst);
+        SIMTIME.with(|now| now.set(time));
     }
 
     ///
@@ -216,11 +219,11 @@ impl<'de> Deserialize<'de> for SimTime {
             {
                 let mut secs = 0;
                 let mut nanos = 0;
-                while let Some(key) = map.next_key()? {
-                    match key {
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
                         "secs" => secs = map.next_value()?,
                         "nanos" => nanos = map.next_value()?,
-                        _ => return Err(serde::de::Error::unknown_field(key, &["secs", "nanos"])),
+                        _ => return Err(serde::de::Error::unknown_field(&key, &["secs", "nanos"])),
                     }
                 }
                 Ok(SimTime::from_duration(Duration::new(secs, nanos)))
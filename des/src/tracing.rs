@@ -1,8 +1,11 @@
 //! Alternative tracing impl
 
-use std::sync::{
-    atomic::{AtomicU64, Ordering},
-    mpsc::Sender,
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::Sender,
+    },
 };
 
 use crate::{
@@ -23,7 +26,9 @@ use tracing_subscriber::{
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ScopeToken(u64);
 
-static SCOPE_CURRENT_TOKEN: AtomicU64 = AtomicU64::new(u64::MAX);
+thread_local! {
+    static SCOPE_CURRENT_TOKEN: Cell<u64> = const { Cell::new(u64::MAX) };
+}
 static SCOPE_TOKEN_NEXT: AtomicU64 = AtomicU64::new(0);
 static SCOPES: std::sync::Mutex<Option<Sender<(ScopeToken, ObjectPath)>>> =
     std::sync::Mutex::new(None);
@@ -50,7 +55,7 @@ pub fn new_scope(obj_path: ObjectPath) -> ScopeToken {
 /// public, since it may be usefull in rare scenarios
 #[doc(hidden)]
 pub fn enter_scope(token: ScopeToken) {
-    SCOPE_CURRENT_TOKEN.store(token.0, Ordering::SeqCst);
+    SCOPE_CURRENT_TOKEN.with(|current| current.set(token.0));
 }
 
 /// Indicates that no scope is currently active.
@@ -59,7 +64,7 @@ pub fn enter_scope(token: ScopeToken) {
 /// public, since it may be usefull in rare scenarios
 #[doc(hidden)]
 pub fn leave_scope() {
-    SCOPE_CURRENT_TOKEN.store(u64::MAX, Ordering::SeqCst);
+    SCOPE_CURRENT_TOKEN.with(|current| current.set(u64::MAX));
 }
 
 /// The log level that will be used if `RUST_LOG` is not defined.
@@ -1,9 +1,12 @@
 use des::{
     prelude::*,
-    runtime::{RuntimeError, RuntimeLimit},
+    runtime::{
+        EventHandle, EventQueue, EventQueueKind, RuntimeError, RuntimeLimit, RuntimeObserver,
+    },
 };
 use rand::{distr::StandardUniform, prelude::SliceRandom, Rng};
 use serial_test::serial;
+use std::{cell::RefCell, rc::Rc};
 
 /// The Event ste
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -81,7 +84,7 @@ impl RepeatWithDelay {
         if self.repeat <= self.repeat_limit {
             let delay = self.delay;
             self.repeat += 1;
-            rt.add_event_in(MyEventSet::RepeatWithDelay(self), delay)
+            rt.add_event_in(MyEventSet::RepeatWithDelay(self), delay);
         }
     }
 }
@@ -138,10 +141,23 @@ fn ensure_event_order() {
 
     events.shuffle(&mut rng);
 
-    let mut rt: Runtime<App> = Builder::seeded(123).build(App {
-        event_list: Vec::with_capacity(128),
-    });
+    for queue in EventQueueKind::all() {
+        let rt = Builder::seeded(123).event_queue(queue).build(App {
+            event_list: Vec::with_capacity(128),
+        });
+        check_event_order(rt, events.clone(), time);
+    }
 
+    let rt = Builder::seeded(123)
+        .with_app()
+        .custom_event_queue(SortedVecQueue::default())
+        .build(App {
+            event_list: Vec::with_capacity(128),
+        });
+    check_event_order(rt, events, time);
+}
+
+fn check_event_order(mut rt: Runtime<App>, events: Vec<(MyEventSet, SimTime)>, time: SimTime) {
     for (event, time) in events {
         rt.add_event(event, time);
     }
@@ -167,7 +183,6 @@ fn ensure_event_order() {
 }
 
 #[test]
-#[cfg(not(feature = "cqueue"))]
 #[serial]
 fn ensure_event_order_same_time() {
     let one = SimTime::from_duration(Duration::new(1, 0));
@@ -196,9 +211,11 @@ fn ensure_event_order_same_time() {
         ),
     ];
 
-    let mut rt: Runtime<App> = Builder::seeded(123).build(App {
-        event_list: Vec::with_capacity(32),
-    });
+    let mut rt: Runtime<App> = Builder::seeded(123)
+        .event_queue(EventQueueKind::BinaryHeap)
+        .build(App {
+            event_list: Vec::with_capacity(32),
+        });
 
     for (event, time) in events {
         rt.add_event(event, time);
@@ -421,7 +438,7 @@ struct PausableAppEvent(usize);
 impl Event<PausableApp> for PausableAppEvent {
     fn handle(mut self, runtime: &mut Runtime<PausableApp>) {
         self.0 += 1;
-        runtime.add_event_in(self, Duration::from_secs(1))
+        runtime.add_event_in(self, Duration::from_secs(1));
     }
 }
 
@@ -447,3 +464,486 @@ fn pausable_app() {
     assert_eq!(sim.num_events_dispatched() + 1, sim.num_events_scheduled());
     assert_eq!(sim.num_events_dispatched(), 1000);
 }
+
+#[test]
+#[serial]
+fn wall_clock_limit() {
+    let (_, _, profiler) = Builder::new()
+        .quiet()
+        .limit(RuntimeLimit::WallClock(Duration::from_millis(50)))
+        .build(PausableApp)
+        .run()
+        .unwrap();
+
+    assert!(profiler.duration >= Duration::from_millis(50));
+    assert!(!profiler.remaining.is_empty());
+    assert_eq!(
+        profiler.limit,
+        Some(RuntimeLimit::WallClock(Duration::from_millis(50)))
+    );
+}
+
+#[test]
+#[serial]
+fn predicate_limit() {
+    let predicate = RuntimeLimit::predicate(|view| view.next_event_time() > SimTime::from(20.0));
+    let (_, time, profiler) = Builder::new()
+        .quiet()
+        .limit(predicate.clone())
+        .max_itr(1000)
+        .build(PausableApp)
+        .run()
+        .unwrap();
+
+    assert_eq!(time, SimTime::from(20.0));
+    assert_eq!(profiler.event_count, 21);
+    assert_eq!(profiler.limit, Some(predicate));
+}
+
+struct HeartbeatApp;
+impl Application for HeartbeatApp {
+    type EventSet = Heartbeat;
+    type Lifecycle = HeartbeatApp;
+}
+
+impl EventLifecycle for HeartbeatApp {
+    fn at_sim_start(runtime: &mut Runtime<Self>) {
+        runtime.add_event(Heartbeat(0), SimTime::ZERO);
+    }
+}
+
+struct Heartbeat(usize);
+impl Event<HeartbeatApp> for Heartbeat {
+    fn handle(self, runtime: &mut Runtime<HeartbeatApp>) {
+        runtime.add_event_in(Heartbeat(self.0 + 1), Duration::from_secs(1));
+    }
+
+    fn is_activity(&self) -> bool {
+        self.0 < 5
+    }
+}
+
+#[test]
+#[serial]
+fn idle_limit() {
+    let (_, time, profiler) = Builder::new()
+        .quiet()
+        .max_itr(1000)
+        .limit(RuntimeLimit::Idle(Duration::from_millis(2500)))
+        .build(HeartbeatApp)
+        .run()
+        .unwrap();
+
+    // last activity at 4s, so the event at 7s exceeds the idle span
+    assert_eq!(time, SimTime::from(6.0));
+    assert_eq!(profiler.event_count, 7);
+    assert_eq!(
+        profiler.limit,
+        Some(RuntimeLimit::Idle(Duration::from_millis(2500)))
+    );
+}
+
+#[test]
+#[serial]
+fn no_limit_reached() {
+    let (_, _, profiler) = Builder::new()
+        .quiet()
+        .max_time(100.0.into())
+        .build(TimeoutApp {
+            timeout: None,
+            log: Vec::new(),
+        })
+        .run()
+        .unwrap();
+    assert_eq!(profiler.limit, None);
+}
+
+struct TimeoutApp {
+    timeout: Option<EventHandle>,
+    log: Vec<(SimTime, TimeoutEvent)>,
+}
+impl Application for TimeoutApp {
+    type EventSet = TimeoutEvent;
+    type Lifecycle = ();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeoutEvent {
+    Request,
+    Response,
+    Timeout,
+}
+
+impl Event<TimeoutApp> for TimeoutEvent {
+    fn handle(self, rt: &mut Runtime<TimeoutApp>) {
+        rt.app.log.push((SimTime::now(), self));
+        match self {
+            Self::Request => {
+                let handle = rt.add_event_in(Self::Timeout, Duration::from_secs(5));
+                rt.app.timeout = Some(handle);
+                rt.add_event_in(Self::Response, Duration::from_secs(2));
+            }
+            Self::Response => {
+                let handle = rt.app.timeout.take().unwrap();
+                assert!(rt.cancel_event(handle));
+                assert!(!rt.cancel_event(handle));
+            }
+            Self::Timeout => {}
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn cancel_pending_event() {
+    let mut rt = Builder::seeded(123).quiet().build(TimeoutApp {
+        timeout: None,
+        log: Vec::new(),
+    });
+    rt.add_event(TimeoutEvent::Request, SimTime::from(1.0));
+
+    let (app, time, profiler) = rt.run().unwrap();
+    assert_eq!(
+        app.log,
+        vec![
+            (SimTime::from(1.0), TimeoutEvent::Request),
+            (SimTime::from(3.0), TimeoutEvent::Response),
+        ]
+    );
+    assert_eq!(time, SimTime::from(3.0));
+    assert_eq!(profiler.event_count, 2);
+    assert!(profiler.remaining.is_empty());
+}
+
+#[test]
+#[serial]
+fn cancel_excluded_from_remaining() {
+    let mut rt = Builder::seeded(123)
+        .quiet()
+        .limit(RuntimeLimit::SimTime(5.0.into()))
+        .build(TimeoutApp {
+            timeout: None,
+            log: Vec::new(),
+        });
+
+    let handles = (1..=10)
+        .map(|i| rt.add_event(TimeoutEvent::Timeout, SimTime::from(f64::from(i))))
+        .collect::<Vec<_>>();
+    assert_eq!(rt.num_events_remaining(), 10);
+
+    // cancel two events before and two events after the limit
+    for i in [1, 3, 6, 9] {
+        assert!(rt.cancel_event(handles[i]));
+    }
+    assert_eq!(rt.num_events_remaining(), 6);
+    assert_eq!(rt.num_events_scheduled(), 10);
+
+    let (app, _, profiler) = rt.run().unwrap();
+    assert_eq!(
+        app.log.iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+        vec![SimTime::from(1.0), SimTime::from(3.0), SimTime::from(5.0)]
+    );
+    assert_eq!(
+        profiler
+            .remaining
+            .iter()
+            .map(|(_, t)| *t)
+            .collect::<Vec<_>>(),
+        vec![SimTime::from(6.0), SimTime::from(8.0), SimTime::from(9.0)]
+    );
+}
+
+#[test]
+#[serial]
+fn step_and_inspect_events() {
+    let mut rt = Builder::seeded(123)
+        .quiet()
+        .limit(RuntimeLimit::SimTime(4.0.into()))
+        .build(TimeoutApp {
+            timeout: None,
+            log: Vec::new(),
+        });
+    rt.add_event(TimeoutEvent::Timeout, SimTime::from(5.0));
+    rt.add_event(TimeoutEvent::Request, SimTime::from(1.0));
+    rt.add_event(TimeoutEvent::Timeout, SimTime::from(1.0));
+    rt.start();
+
+    assert_eq!(
+        rt.pending_events().collect::<Vec<_>>(),
+        vec![
+            (&TimeoutEvent::Request, SimTime::from(1.0)),
+            (&TimeoutEvent::Timeout, SimTime::from(1.0)),
+            (&TimeoutEvent::Timeout, SimTime::from(5.0)),
+        ]
+    );
+    assert_eq!(
+        rt.peek_next(),
+        Some((&TimeoutEvent::Request, SimTime::from(1.0)))
+    );
+
+    let step = rt.step().unwrap();
+    assert_eq!(step.index, 1);
+    assert_eq!(step.time, SimTime::from(1.0));
+    assert!(step.description.ends_with("TimeoutEvent"));
+    assert_eq!(
+        rt.app.log,
+        vec![(SimTime::from(1.0), TimeoutEvent::Request)]
+    );
+
+    // The request scheduled a response and a timeout.
+    assert_eq!(
+        rt.pending_events().collect::<Vec<_>>(),
+        vec![
+            (&TimeoutEvent::Timeout, SimTime::from(1.0)),
+            (&TimeoutEvent::Response, SimTime::from(3.0)),
+            (&TimeoutEvent::Timeout, SimTime::from(5.0)),
+            (&TimeoutEvent::Timeout, SimTime::from(6.0)),
+        ]
+    );
+
+    assert_eq!(rt.step().map(|step| step.time), Some(SimTime::from(1.0)));
+    assert_eq!(rt.step().map(|step| step.time), Some(SimTime::from(3.0)));
+
+    // The limit stops stepping, the canceled timeout is no longer pending.
+    assert_eq!(rt.step(), None);
+    assert_eq!(rt.limit_reached(), Some(&RuntimeLimit::SimTime(4.0.into())));
+    assert_eq!(
+        rt.peek_next(),
+        Some((&TimeoutEvent::Timeout, SimTime::from(5.0)))
+    );
+    assert_eq!(rt.pending_events().count(), 1);
+}
+
+#[derive(Clone, Default)]
+struct Trace(Rc<RefCell<Vec<String>>>);
+
+impl RuntimeObserver<TimeoutEvent> for Trace {
+    fn on_sim_start(&mut self, time: SimTime) {
+        self.0.borrow_mut().push(format!("start {time}"));
+    }
+
+    fn on_event_scheduled(&mut self, time: SimTime, event: &TimeoutEvent) {
+        self.0
+            .borrow_mut()
+            .push(format!("schedule {event:?} {time}"));
+    }
+
+    fn on_event_dispatch(&mut self, time: SimTime, event: &TimeoutEvent) {
+        self.0
+            .borrow_mut()
+            .push(format!("dispatch {event:?} {time}"));
+    }
+
+    fn on_sim_end(&mut self, time: SimTime) {
+        self.0.borrow_mut().push(format!("end {time}"));
+    }
+}
+
+#[test]
+#[serial]
+fn observe_events() {
+    let trace = Trace::default();
+    let mut rt = Builder::seeded(123)
+        .quiet()
+        .with_app()
+        .observer(trace.clone())
+        .build(TimeoutApp {
+            timeout: None,
+            log: Vec::new(),
+        });
+    rt.add_event(TimeoutEvent::Request, SimTime::from(1.0));
+    let _ = rt.run().unwrap();
+
+    assert_eq!(
+        *trace.0.borrow(),
+        [
+            "schedule Request 1s",
+            "start 0ns",
+            "dispatch Request 1s",
+            "schedule Timeout 6s",
+            "schedule Response 3s",
+            "dispatch Response 3s",
+            "end 3s",
+        ]
+    );
+}
+
+/// A naive event queue, sorted by deadline and insertion order.
+#[derive(Default)]
+struct SortedVecQueue {
+    events: Vec<(SimTime, usize, MyEventSet)>,
+    next_id: usize,
+}
+
+impl EventQueue<MyEventSet> for SortedVecQueue {
+    fn descriptor(&self) -> String {
+        "SortedVecQueue".to_string()
+    }
+
+    fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    fn add(&mut self, time: SimTime, event: MyEventSet) -> EventHandle {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let i = self.events.partition_point(|(t, _, _)| *t <= time);
+        self.events.insert(i, (time, id, event));
+        EventHandle::new(id, time)
+    }
+
+    fn fetch_next(&mut self) -> Option<(MyEventSet, SimTime)> {
+        if self.events.is_empty() {
+            return None;
+        }
+        let (time, _, event) = self.events.remove(0);
+        Some((event, time))
+    }
+
+    fn peek_time(&self) -> Option<SimTime> {
+        self.events.first().map(|(time, _, _)| *time)
+    }
+
+    fn peek(&self) -> Option<(&MyEventSet, SimTime)> {
+        self.events.first().map(|(time, _, event)| (event, *time))
+    }
+
+    fn cancel(&mut self, handle: EventHandle) -> bool {
+        let len = self.events.len();
+        self.events.retain(|(_, id, _)| *id != handle.id());
+        self.events.len() != len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&MyEventSet, EventHandle)> + '_> {
+        Box::new(
+            self.events
+                .iter()
+                .map(|(time, id, event)| (event, EventHandle::new(*id, *time))),
+        )
+    }
+}
+
+#[test]
+#[serial]
+fn custom_event_queue() {
+    let mut rt = Builder::seeded(123)
+        .quiet()
+        .with_app()
+        .custom_event_queue(SortedVecQueue::default())
+        .build(App {
+            event_list: Vec::new(),
+        });
+    let handle = rt.add_event(MyEventSet::B(B { id: 1 }), SimTime::from(1.0));
+    assert_eq!(handle.id(), 0);
+    let cancelled = rt.add_event(MyEventSet::B(B { id: 2 }), SimTime::from(2.0));
+    assert_eq!(rt.num_events_remaining(), 2);
+
+    assert!(matches!(
+        rt.peek_next(),
+        Some((MyEventSet::B(B { id: 1 }), _))
+    ));
+    assert!(rt.cancel_event(cancelled));
+    assert!(!rt.cancel_event(cancelled));
+    assert_eq!(rt.pending_events().count(), 1);
+
+    let (app, time, _) = rt.run().unwrap();
+    assert_eq!(app.event_list.len(), 1);
+    assert_eq!(time, SimTime::from(1.0));
+}
+
+struct OrderApp {
+    log: Vec<u32>,
+}
+
+impl Application for OrderApp {
+    type EventSet = OrderEvent;
+    type Lifecycle = ();
+}
+
+struct OrderEvent(u32);
+
+impl Event<OrderApp> for OrderEvent {
+    fn handle(self, rt: &mut Runtime<OrderApp>) {
+        rt.app.log.push(self.0);
+        if self.0 == 1 {
+            rt.add_event(OrderEvent(3), SimTime::now());
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn same_time_events_preserve_insertion_order() {
+    // Events added at the current time must not overtake events
+    // scheduled for the same time beforehand.
+    for queue in EventQueueKind::all() {
+        let mut rt = Builder::seeded(123)
+            .quiet()
+            .event_queue(queue)
+            .build(OrderApp { log: Vec::new() });
+        rt.add_event(OrderEvent(1), SimTime::from(1.0));
+        rt.add_event(OrderEvent(2), SimTime::from(1.0));
+
+        let (app, _, _) = rt.run().unwrap();
+        assert_eq!(app.log, vec![1, 2, 3], "{queue}");
+    }
+}
+
+struct RandomWalk {
+    log: Vec<(SimTime, u64)>,
+}
+
+impl Application for RandomWalk {
+    type EventSet = Step;
+    type Lifecycle = RandomWalk;
+}
+
+impl EventLifecycle for RandomWalk {
+    fn at_sim_start(rt: &mut Runtime<Self>) {
+        rt.add_event(Step, SimTime::ZERO);
+    }
+}
+
+struct Step;
+
+impl Event<RandomWalk> for Step {
+    fn handle(self, rt: &mut Runtime<RandomWalk>) {
+        let value = rt.random::<u64>();
+        rt.app.log.push((SimTime::now(), value));
+        if rt.app.log.len() < 1000 {
+            let delay = Duration::from_secs_f64(rt.random::<f64>());
+            rt.add_event_in(Step, delay);
+        }
+    }
+}
+
+fn random_walk(seed: u64) -> Vec<(SimTime, u64)> {
+    let rt = Builder::seeded(seed)
+        .quiet()
+        .build(RandomWalk { log: Vec::new() });
+    rt.run().unwrap().0.log
+}
+
+#[test]
+fn parallel_runtimes_are_independent() {
+    let expected = (0..4).map(random_walk).collect::<Vec<_>>();
+
+    let handles = (0..4)
+        .map(|seed| std::thread::spawn(move || random_walk(seed)))
+        .collect::<Vec<_>>();
+    for (handle, expected) in handles.into_iter().zip(expected) {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
+
+#[test]
+#[should_panic = "another runtime allready exists on this thread"]
+fn one_runtime_per_thread() {
+    let _a = Builder::seeded(1)
+        .quiet()
+        .build(RandomWalk { log: Vec::new() });
+    let _b = Builder::seeded(2)
+        .quiet()
+        .build(RandomWalk { log: Vec::new() });
+}
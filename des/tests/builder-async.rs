@@ -127,7 +127,7 @@ fn builder_async_fn_channeled() {
     );
 
     let _ = Builder::seeded(123).build(sim.freeze()).run();
-    assert_eq!(counter.load(Ordering::SeqCst), (0..16).sum());
+    assert_eq!(counter.load(Ordering::SeqCst), (0..16).sum::<u16>());
 }
 
 #[test]
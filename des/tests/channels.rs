@@ -1,13 +1,26 @@
 #![cfg(feature = "net")]
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
-    Arc,
+    Arc, Mutex,
 };
 
-use des::{net::channel::ChannelProbe, prelude::*};
+use des::{
+    net::{
+        blocks::HandlerFn,
+        channel::{
+            ChannelProbe, CoDel, DropReason, DropTail, QueueDiscipline, Red, StrictPriority,
+            ThroughputProbe, UtilisationProbe, WeightedFair,
+        },
+    },
+    prelude::*,
+};
 use rand::{rngs::StdRng, SeedableRng};
+use receiver::{receiver, Received};
 use serial_test::serial;
 
+#[path = "common/receiver.rs"]
+mod receiver;
+
 #[derive(Default)]
 struct DropChanModule {
     send: usize,
@@ -251,3 +264,483 @@ fn latency_only_channel() {
 
     let _ = Builder::seeded(123).build(sim.freeze()).run();
 }
+
+/// Runs a simulation, in which `tx` sends messages on its gate `out` to
+/// a receiving node. The channel between both nodes is configured using `setup`.
+fn transmit(
+    seed: u64,
+    tx: impl Module,
+    metrics: ChannelMetrics,
+    setup: impl FnOnce(&Channel),
+) -> (Received, ChannelRef) {
+    let mut sim = Sim::new(());
+    sim.node("tx", tx);
+    let received = receiver(&mut sim, "rx");
+
+    let tx = sim.gate("tx", "out");
+    tx.clone()
+        .connect(sim.gate("rx", "in"), Some(Channel::new(metrics)));
+    let channel = tx.channel().unwrap();
+    setup(&channel);
+
+    let _ = Builder::seeded(seed)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+    (received, channel)
+}
+
+struct LossySender {
+    count: u16,
+    len: usize,
+}
+
+impl Module for LossySender {
+    fn at_sim_start(&mut self, _stage: usize) {
+        for i in 0..self.count {
+            send_in(
+                Message::default().id(i).with_content(vec![0u8; self.len]),
+                "out",
+                Duration::from_millis(u64::from(i)),
+            );
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct LossLog(Arc<Mutex<Vec<u16>>>);
+
+impl ChannelProbe for LossLog {
+    fn on_message_transmit(&mut self, _: &ChannelMetrics, _: &Message) {}
+
+    fn on_message_lost(&mut self, _: &ChannelMetrics, msg: &Message) {
+        self.0.lock().unwrap().push(msg.header().id);
+    }
+}
+
+/// Sends `count` messages of `len` bytes over a channel using the given loss model,
+/// returning the ids of the lost and received messages.
+fn lossy_run(seed: u64, loss: LossModel, count: u16, len: usize) -> (Vec<u16>, Vec<u16>) {
+    let lost = LossLog::default();
+    let (received, _) = transmit(
+        seed,
+        LossySender { count, len },
+        ChannelMetrics::new(
+            0,
+            Duration::from_millis(10),
+            Duration::ZERO,
+            ChannelDropBehaviour::Drop,
+        ),
+        |channel| {
+            channel.set_loss_model(loss);
+            channel.attach_probe(lost.clone());
+        },
+    );
+
+    let lost = lost.0.lock().unwrap().clone();
+    (lost, received.ids())
+}
+
+#[test]
+#[serial]
+fn bernoulli_loss() {
+    let (lost, received) = lossy_run(123, LossModel::Bernoulli(0.3), 1000, 0);
+    assert_eq!(lost.len() + received.len(), 1000);
+    assert!((250..350).contains(&lost.len()), "lost {}", lost.len());
+    assert!(lost.iter().all(|id| !received.contains(id)));
+
+    // Losses are drawn from the simulation RNG.
+    assert_eq!(
+        lossy_run(123, LossModel::Bernoulli(0.3), 1000, 0),
+        (lost, received)
+    );
+
+    let (lost, received) = lossy_run(123, LossModel::None, 1000, 0);
+    assert!(lost.is_empty());
+    assert_eq!(received.len(), 1000);
+}
+
+#[test]
+#[serial]
+fn bit_error_loss_scales_with_length() {
+    let model = LossModel::BitError(1e-4);
+    let msg = Message::default().with_content(vec![0u8; 100]);
+    let expected = 1.0 - (1.0 - 1e-4f64).powi(8 * msg.length() as i32);
+    assert!((model.loss_probability(&msg, false) - expected).abs() < 1e-12);
+
+    for len in [100, 1000] {
+        let msg = Message::default().with_content(vec![0u8; len]);
+        let p = model.loss_probability(&msg, false);
+        let (lost, _) = lossy_run(123, model, 1000, len);
+        let rate = lost.len() as f64 / 1000.0;
+        assert!((rate - p).abs() < 0.05, "lost {rate}, expected {p}");
+    }
+}
+
+#[test]
+#[serial]
+fn gilbert_elliott_burst_loss() {
+    // stationary loss p / (p + r) = 1/6, mean burst length 1 / r = 4
+    let (lost, received) = lossy_run(123, LossModel::gilbert(0.05, 0.25), 2000, 0);
+    assert_eq!(lost.len() + received.len(), 2000);
+    assert!((250..420).contains(&lost.len()), "lost {}", lost.len());
+
+    let bursts = 1 + lost.windows(2).filter(|w| w[1] != w[0] + 1).count();
+    let mean_burst = lost.len() as f64 / bursts as f64;
+    assert!((3.0..5.5).contains(&mean_burst), "mean burst {mean_burst}");
+}
+
+#[test]
+#[serial]
+fn corruption_delivers_marked_messages() {
+    let run = |loss: LossModel| {
+        let (received, _) = transmit(
+            123,
+            LossySender {
+                count: 1000,
+                len: 0,
+            },
+            ChannelMetrics::new(
+                0,
+                Duration::from_millis(10),
+                Duration::ZERO,
+                ChannelDropBehaviour::Drop,
+            ),
+            |channel| {
+                channel.set_loss_model(loss);
+                channel.set_corruption_model(LossModel::Bernoulli(0.3));
+            },
+        );
+        (received.ids(), received.corrupted())
+    };
+
+    // Corrupted messages are still delivered.
+    let (received, corrupted) = run(LossModel::None);
+    assert_eq!(received.len(), 1000);
+    assert!(
+        (250..350).contains(&corrupted.len()),
+        "corrupted {}",
+        corrupted.len()
+    );
+
+    // Lost messages are never corrupted.
+    let (received, corrupted) = run(LossModel::Bernoulli(1.0));
+    assert!(received.is_empty());
+    assert!(corrupted.is_empty());
+}
+
+struct BurstSender {
+    msgs: Vec<(MessageKind, usize)>,
+    queued: Arc<AtomicUsize>,
+}
+
+impl Module for BurstSender {
+    fn at_sim_start(&mut self, _stage: usize) {
+        for (i, (kind, len)) in self.msgs.iter().enumerate() {
+            send(
+                Message::default()
+                    .id(i as u16)
+                    .kind(*kind)
+                    .with_content(vec![0u8; *len]),
+                "out",
+            );
+        }
+
+        let chan = current().gate("out", 0).unwrap().channel().unwrap();
+        self.queued.store(chan.queue_len(), Ordering::SeqCst);
+    }
+}
+
+#[derive(Clone, Default)]
+struct DropLog(Arc<Mutex<Vec<u16>>>);
+
+impl ChannelProbe for DropLog {
+    fn on_message_transmit(&mut self, _: &ChannelMetrics, _: &Message) {}
+
+    fn on_message_dropped(&mut self, _: &ChannelMetrics, msg: &Message, reason: DropReason) {
+        assert_eq!(reason, DropReason::Queue);
+        self.0.lock().unwrap().push(msg.header().id);
+    }
+}
+
+struct QueuedRun {
+    received: Vec<(u16, MessageKind)>,
+    dropped: Vec<u16>,
+    queued: usize,
+}
+
+/// Sends all messages at once over a busy channel with 8 kbit/s, using
+/// the given queue discipline.
+fn queued_run(discipline: impl QueueDiscipline, msgs: Vec<(MessageKind, usize)>) -> QueuedRun {
+    let dropped = DropLog::default();
+    let queued = Arc::new(AtomicUsize::new(0));
+    let (received, chan) = transmit(
+        123,
+        BurstSender {
+            msgs,
+            queued: queued.clone(),
+        },
+        ChannelMetrics::new(
+            8000,
+            Duration::from_millis(10),
+            Duration::ZERO,
+            ChannelDropBehaviour::Drop,
+        ),
+        |chan| {
+            chan.set_queue_discipline(discipline);
+            chan.attach_probe(dropped.clone());
+        },
+    );
+
+    assert_eq!(chan.queue_len(), 0);
+    assert_eq!(chan.queue_bytes(), 0);
+    assert_eq!(chan.dropped(), dropped.0.lock().unwrap().len());
+
+    let dropped = dropped.0.lock().unwrap().clone();
+    QueuedRun {
+        received: received.ids_and_kinds(),
+        dropped,
+        queued: queued.load(Ordering::SeqCst),
+    }
+}
+
+#[test]
+#[serial]
+fn drop_tail_packet_limit() {
+    let run = queued_run(DropTail::new().packet_limit(3), vec![(0, 100); 10]);
+    assert_eq!(run.queued, 3);
+    assert_eq!(
+        run.received.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+        [0, 1, 2, 3]
+    );
+    assert_eq!(run.dropped, [4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+#[serial]
+fn strict_priority_serves_high_priority_first() {
+    let msgs = (0..10).map(|i| (i % 2, 100)).collect::<Vec<_>>();
+    let run = queued_run(StrictPriority::new().priority(1, 5), msgs);
+    assert_eq!(run.queued, 9);
+    assert!(run.dropped.is_empty());
+
+    // The first message is transmitted immediately, all others are queued.
+    assert_eq!(
+        run.received.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+        [0, 1, 3, 5, 7, 9, 2, 4, 6, 8]
+    );
+}
+
+#[test]
+#[serial]
+fn weighted_fair_shares_by_kind() {
+    let msgs = (0..80).map(|i| (i % 2, 100)).collect::<Vec<_>>();
+    let run = queued_run(WeightedFair::new().weight(1, 3.0), msgs);
+    assert!(run.dropped.is_empty());
+    assert_eq!(run.received.len(), 80);
+
+    // While both kinds are backlogged, kind 1 gets three times the share.
+    let share = run.received[1..41]
+        .iter()
+        .filter(|(_, kind)| *kind == 1)
+        .count();
+    assert!((29..=31).contains(&share), "share {share}");
+
+    let limited = queued_run(WeightedFair::new().limit(5), vec![(0, 100); 10]);
+    assert_eq!(limited.received.len(), 6);
+    assert_eq!(limited.dropped.len(), 4);
+}
+
+#[test]
+#[serial]
+fn red_drops_early() {
+    let run = queued_run(Red::new(5, 15, 0.1).weight(0.2), vec![(0, 100); 100]);
+    assert_eq!(run.received.len() + run.dropped.len(), 100);
+    assert!(!run.dropped.is_empty());
+    assert!(run.queued < 20, "queued {}", run.queued);
+
+    // Short bursts stay below the threshold.
+    let run = queued_run(Red::new(5, 15, 0.1).weight(0.2), vec![(0, 100); 5]);
+    assert!(run.dropped.is_empty());
+}
+
+#[test]
+#[serial]
+fn codel_drops_on_persistent_delay() {
+    // Each message occupies the channel for 100ms.
+    let codel = CoDel::new(Duration::from_millis(5), Duration::from_millis(100));
+    let run = queued_run(codel, vec![(0, 1000); 50]);
+    assert_eq!(run.queued, 49);
+    assert_eq!(run.received.len() + run.dropped.len(), 50);
+    assert!(!run.dropped.is_empty());
+    assert!(run.received.len() > 2);
+
+    let run = queued_run(DropTail::new(), vec![(0, 1000); 50]);
+    assert_eq!(run.received.len(), 50);
+}
+
+#[test]
+fn delay_models_follow_distribution() {
+    let jitter = Duration::from_millis(100);
+    let mut rng = StdRng::seed_from_u64(123);
+    let mut mean = |model: DelayModel| {
+        (0..20_000)
+            .map(|_| model.sample(jitter, &mut rng).as_secs_f64())
+            .sum::<f64>()
+            / 20_000.0
+    };
+
+    assert!((mean(DelayModel::Uniform) - 0.05).abs() < 0.005);
+    assert!((mean(DelayModel::Normal) - 0.1 * (2.0 / std::f64::consts::PI).sqrt()).abs() < 0.005);
+    assert!((mean(DelayModel::Exponential) - 0.1).abs() < 0.005);
+    assert!((mean(DelayModel::Pareto(3.0)) - 0.05).abs() < 0.01);
+
+    let custom = DelayModel::custom(
+        rand::distr::Uniform::new(Duration::from_millis(5), Duration::from_millis(10)).unwrap(),
+    );
+    assert_eq!(custom, custom);
+    assert_ne!(custom, DelayModel::Uniform);
+    for _ in 0..100 {
+        let delay = custom.sample(Duration::ZERO, &mut rng);
+        assert!((Duration::from_millis(5)..Duration::from_millis(10)).contains(&delay));
+    }
+
+    // Built-in models scale with the jitter.
+    assert_eq!(
+        DelayModel::Exponential.sample(Duration::ZERO, &mut rng),
+        Duration::ZERO
+    );
+}
+
+/// Sends 200 messages, one each millisecond, over a channel with exponential
+/// jitter, returning the ids in the order they were received.
+fn jitter_run(fifo: bool) -> Vec<u16> {
+    let (received, _) = transmit(
+        123,
+        LossySender { count: 200, len: 0 },
+        ChannelMetrics::new(
+            0,
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+            ChannelDropBehaviour::Drop,
+        ),
+        |channel| {
+            channel.set_delay_model(DelayModel::Exponential);
+            channel.set_fifo(fifo);
+        },
+    );
+    received.ids()
+}
+
+#[test]
+#[serial]
+fn fifo_delivery_despite_jitter() {
+    let reordered = jitter_run(false);
+    assert_eq!(reordered.len(), 200);
+    assert!(reordered.windows(2).any(|w| w[0] > w[1]));
+
+    let ordered = jitter_run(true);
+    assert_eq!(ordered, (0..200).collect::<Vec<_>>());
+}
+
+#[derive(Clone, Default)]
+struct EventLog(Arc<Mutex<Vec<String>>>);
+
+impl EventLog {
+    fn push(&self, event: String) {
+        self.0.lock().unwrap().push(event);
+    }
+}
+
+impl ChannelProbe for EventLog {
+    fn on_message_transmit(&mut self, _: &ChannelMetrics, msg: &Message) {
+        self.push(format!("transmit {}", msg.header().id));
+    }
+
+    fn on_transmission_end(&mut self, _: &ChannelMetrics) {
+        self.push("end".to_string());
+    }
+
+    fn on_message_enqueued(&mut self, _: &ChannelMetrics, msg: &Message, depth: usize) {
+        self.push(format!("enqueue {} depth {depth}", msg.header().id));
+    }
+
+    fn on_message_dropped(&mut self, _: &ChannelMetrics, msg: &Message, reason: DropReason) {
+        self.push(format!("drop {} {reason:?}", msg.header().id));
+    }
+
+    fn on_message_delivered(&mut self, _: &ChannelMetrics, msg: &Message) {
+        self.push(format!("deliver {}", msg.header().id));
+    }
+
+    fn on_busy(&mut self, _: &ChannelMetrics) {
+        self.push("busy".to_string());
+    }
+
+    fn on_idle(&mut self, _: &ChannelMetrics) {
+        self.push("idle".to_string());
+    }
+}
+
+#[test]
+#[serial]
+fn probe_callbacks() {
+    let mut sim = Sim::new(());
+    sim.node(
+        "tx",
+        BurstSender {
+            msgs: vec![(0, 0); 3],
+            queued: Arc::default(),
+        },
+    );
+    sim.node("rx", HandlerFn::new(|_| {}));
+
+    let tx = sim.gate("tx", "out");
+    tx.clone().connect(
+        sim.gate("rx", "in"),
+        Some(Channel::new(ChannelMetrics::new(
+            8000,
+            Duration::from_millis(10),
+            Duration::ZERO,
+            ChannelDropBehaviour::Drop,
+        ))),
+    );
+    let chan = tx.channel().unwrap();
+    chan.set_queue_discipline(DropTail::new().packet_limit(1));
+
+    let log = EventLog::default();
+    let throughput = ThroughputProbe::new();
+    let utilisation = UtilisationProbe::new();
+    chan.attach_probe(log.clone());
+    chan.attach_probe(throughput.clone());
+    chan.attach_probe(utilisation.clone());
+
+    let _ = Builder::seeded(123)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        *log.0.lock().unwrap(),
+        [
+            "transmit 0",
+            "busy",
+            "enqueue 1 depth 0",
+            "enqueue 2 depth 1",
+            "drop 2 Queue",
+            "end",
+            "transmit 1",
+            "deliver 0",
+            "end",
+            "idle",
+            "deliver 1",
+        ]
+    );
+
+    let msg = Message::default().with_content(Vec::<u8>::new());
+    assert_eq!(throughput.delivered(), 2);
+    assert_eq!(throughput.delivered_bytes(), 2 * msg.length());
+    assert_eq!(utilisation.busy_time(), 2 * chan.calculate_busy(&msg));
+}
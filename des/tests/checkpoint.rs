@@ -0,0 +1,342 @@
+#![cfg(feature = "net")]
+use des::{
+    net::{message::MessageBody, SimBuilder},
+    prelude::*,
+    runtime::{Checkpoint, CheckpointError, EventCheckpoint, Snapshot},
+};
+use serde::{Deserialize, Serialize};
+use serial_test::serial;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Tick(u32);
+
+impl Event<Counter> for Tick {
+    fn handle(self, rt: &mut Runtime<Counter>) {
+        rt.app.log.push((SimTime::now(), self.0, random()));
+        if self.0 < 100 {
+            let delay = Duration::from_secs_f64(rt.random::<f64>());
+            rt.add_event_in(Tick(self.0 + 1), delay);
+        }
+    }
+}
+
+impl EventCheckpoint<Counter> for Tick {
+    type Snapshot = u32;
+    fn checkpoint(&self, _: &Counter) -> Result<u32, CheckpointError> {
+        Ok(self.0)
+    }
+    fn restore(snapshot: u32, _: &Counter) -> Result<Self, CheckpointError> {
+        Ok(Tick(snapshot))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counter {
+    log: Vec<(SimTime, u32, u64)>,
+}
+
+impl Application for Counter {
+    type EventSet = Tick;
+    type Lifecycle = Self;
+}
+
+impl EventLifecycle for Counter {
+    fn at_sim_start(rt: &mut Runtime<Self>) {
+        rt.add_event(Tick(0), SimTime::ZERO);
+        rt.add_event(Tick(50), SimTime::ZERO);
+    }
+}
+
+impl Checkpoint for Counter {
+    type Snapshot = Vec<(SimTime, u32, u64)>;
+    fn checkpoint(&self) -> Result<Self::Snapshot, CheckpointError> {
+        Ok(self.log.clone())
+    }
+    fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), CheckpointError> {
+        self.log = snapshot;
+        Ok(())
+    }
+}
+
+#[test]
+#[serial]
+fn restore_generic_runtime() {
+    let (reference, ref_time, ref_profiler) = Builder::seeded(42)
+        .quiet()
+        .build(Counter::default())
+        .run()
+        .unwrap();
+
+    let mut rt = Builder::seeded(42).quiet().build(Counter::default());
+    rt.start();
+    rt.dispatch_n_events(60);
+
+    let snapshot = rt.checkpoint().unwrap();
+    assert_eq!(snapshot.num_events_dispatched(), 60);
+    assert_eq!(snapshot.num_events_remaining(), rt.num_events_remaining());
+    let serialized = serde_yml::to_string(&snapshot).unwrap();
+    drop(rt);
+
+    let snapshot: Snapshot<Counter> = serde_yml::from_str(&serialized).unwrap();
+    let rt = Builder::seeded(0)
+        .quiet()
+        .restore(Counter::default(), snapshot)
+        .unwrap();
+    let (restored, time, profiler) = rt.run().unwrap();
+
+    assert_eq!(restored.log, reference.log);
+    assert_eq!(time, ref_time);
+    assert_eq!(profiler.event_count, ref_profiler.event_count);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Ping {
+    seq: usize,
+}
+
+impl MessageBody for Ping {
+    fn byte_len(&self) -> usize {
+        64
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Node {
+    received: Vec<(SimTime, usize)>,
+}
+
+impl Module for Node {
+    fn at_sim_start(&mut self, _stage: usize) {
+        if current().name() == "alice" {
+            for seq in 0..3 {
+                send(Message::default().with_content(Ping { seq }), "port");
+            }
+        }
+    }
+
+    fn handle_message(&mut self, msg: Message) {
+        let ping = msg.content::<Ping>();
+        self.received.push((SimTime::now(), ping.seq));
+        if ping.seq < 60 {
+            send(
+                Message::default().with_content(Ping { seq: ping.seq + 3 }),
+                "port",
+            );
+        }
+    }
+
+    fn checkpoint(&self) -> Option<serde_yml::Value> {
+        serde_yml::to_value(self).ok()
+    }
+
+    fn restore_checkpoint(&mut self, state: serde_yml::Value) {
+        *self = serde_yml::from_value(state).unwrap();
+    }
+}
+
+fn ping_pong_builder() -> SimBuilder<()> {
+    let mut sim = Sim::new(());
+    sim.register_body::<Ping>();
+    sim.node("alice", Node::default());
+    sim.node("bob", Node::default());
+
+    let alice = sim.gate("alice", "port");
+    let bob = sim.gate("bob", "port");
+    alice.connect(
+        bob,
+        Some(Channel::new(ChannelMetrics {
+            bitrate: 10_000,
+            latency: Duration::from_millis(20),
+            jitter: Duration::from_millis(5),
+            drop_behaviour: ChannelDropBehaviour::Queue(None),
+        })),
+    );
+    sim
+}
+
+fn ping_pong() -> Sim<()> {
+    ping_pong_builder().freeze()
+}
+
+fn received(sim: &Sim<()>, node: &str) -> Vec<(SimTime, usize)> {
+    sim.get(&node.into())
+        .unwrap()
+        .as_ref::<Node>()
+        .received
+        .clone()
+}
+
+#[test]
+#[serial]
+fn restore_sim() {
+    let (reference, ref_time, ref_profiler) =
+        Builder::seeded(7).quiet().build(ping_pong()).run().unwrap();
+    let ref_alice = received(&reference, "alice");
+    let ref_bob = received(&reference, "bob");
+    drop(reference);
+
+    let mut rt = Builder::seeded(7).quiet().build(ping_pong());
+    rt.start();
+    rt.dispatch_events_until(SimTime::from(0.5));
+
+    let snapshot = rt.checkpoint().unwrap();
+    let serialized = serde_yml::to_string(&snapshot).unwrap();
+    drop(rt);
+
+    let snapshot: Snapshot<Sim<()>> = serde_yml::from_str(&serialized).unwrap();
+    let rt = Builder::seeded(0)
+        .quiet()
+        .restore(ping_pong(), snapshot)
+        .unwrap();
+    let (restored, time, profiler) = rt.run().unwrap();
+
+    assert_eq!(received(&restored, "alice"), ref_alice);
+    assert_eq!(received(&restored, "bob"), ref_bob);
+    assert_eq!(time, ref_time);
+    assert_eq!(profiler.event_count, ref_profiler.event_count);
+}
+
+/// The ping pong simulation, using a lossy channel with exponential jitter.
+fn lossy_ping_pong() -> Sim<()> {
+    let sim = ping_pong();
+    for node in ["alice", "bob"] {
+        let gate = sim.get(&node.into()).unwrap().gate("port", 0).unwrap();
+        let channel = gate.channel().unwrap();
+        channel.set_loss_model(LossModel::Bernoulli(0.02));
+        channel.set_delay_model(DelayModel::Exponential);
+        channel.set_fifo(true);
+    }
+    sim
+}
+
+fn reconfigure_link(rt: &mut Runtime<Sim<()>>) {
+    rt.set_link_metrics_at(
+        "alice.port",
+        ChannelMetrics::new(
+            20_000,
+            Duration::from_millis(10),
+            Duration::from_millis(2),
+            ChannelDropBehaviour::Queue(None),
+        ),
+        SimTime::from(1.0),
+    );
+}
+
+#[test]
+#[serial]
+fn restore_reconfigured_channels() {
+    let mut rt = Builder::seeded(7).quiet().build(lossy_ping_pong());
+    reconfigure_link(&mut rt);
+    let (reference, ref_time, ref_profiler) = rt.run().unwrap();
+    let ref_alice = received(&reference, "alice");
+    let ref_bob = received(&reference, "bob");
+    drop(reference);
+    assert!(ref_time > SimTime::from(1.0));
+
+    let mut rt = Builder::seeded(7).quiet().build(lossy_ping_pong());
+    reconfigure_link(&mut rt);
+    rt.start();
+    rt.dispatch_events_until(SimTime::from(0.5));
+
+    let snapshot = rt.checkpoint().unwrap();
+    let serialized = serde_yml::to_string(&snapshot).unwrap();
+    drop(rt);
+
+    // The channel configuration and the pending reconfiguration
+    // are restored from the snapshot.
+    let snapshot: Snapshot<Sim<()>> = serde_yml::from_str(&serialized).unwrap();
+    let rt = Builder::seeded(0)
+        .quiet()
+        .restore(ping_pong(), snapshot)
+        .unwrap();
+    let (restored, time, profiler) = rt.run().unwrap();
+
+    assert_eq!(received(&restored, "alice"), ref_alice);
+    assert_eq!(received(&restored, "bob"), ref_bob);
+    assert_eq!(time, ref_time);
+    assert_eq!(profiler.event_count, ref_profiler.event_count);
+}
+
+/// The ping pong simulation, with a degraded, a corrupting and a recurring lossy link.
+fn faulty_ping_pong() -> Sim<()> {
+    let mut sim = ping_pong_builder();
+    sim.include_faults(
+        r"
+faults:
+  - degrade: { link: alice.port, latency: 0.05 }
+    at: 0.3
+    mttr: 0.5
+  - corrupt: { link: alice.port, bit-error-rate: 1e-3 }
+    at: 0.4
+    mttr: 0.4
+  - loss: { link: bob.port, probability: 0.1 }
+    mtbf: { exponential: 0.2 }
+    mttr: { uniform: [0.05, 0.1] }
+    until: 2.0
+",
+    )
+    .unwrap();
+    sim.freeze()
+}
+
+#[test]
+#[serial]
+fn restore_fault_scenario() {
+    let (reference, ref_time, ref_profiler) = Builder::seeded(7)
+        .quiet()
+        .build(faulty_ping_pong())
+        .run()
+        .unwrap();
+    let ref_alice = received(&reference, "alice");
+    let ref_bob = received(&reference, "bob");
+    drop(reference);
+
+    let mut rt = Builder::seeded(7).quiet().build(faulty_ping_pong());
+    rt.start();
+    rt.dispatch_events_until(SimTime::from(0.5));
+
+    let snapshot = rt.checkpoint().unwrap();
+    let serialized = serde_yml::to_string(&snapshot).unwrap();
+    drop(rt);
+
+    // Pending injections and repairs are restored from the snapshot,
+    // even though the restored simulation is not started again.
+    let snapshot: Snapshot<Sim<()>> = serde_yml::from_str(&serialized).unwrap();
+    let rt = Builder::seeded(0)
+        .quiet()
+        .restore(faulty_ping_pong(), snapshot)
+        .unwrap();
+    let (restored, time, profiler) = rt.run().unwrap();
+
+    assert_eq!(received(&restored, "alice"), ref_alice);
+    assert_eq!(received(&restored, "bob"), ref_bob);
+    assert_eq!(time, ref_time);
+    assert_eq!(profiler.event_count, ref_profiler.event_count);
+    drop(restored);
+
+    // Without the fault scenario, the pending faults cannot be restored.
+    let snapshot: Snapshot<Sim<()>> = serde_yml::from_str(&serialized).unwrap();
+    assert!(matches!(
+        Builder::seeded(0).quiet().restore(ping_pong(), snapshot),
+        Err(CheckpointError::Missing(_))
+    ));
+}
+
+#[test]
+#[serial]
+fn checkpoint_unregistered_body() {
+    let mut sim = Sim::new(());
+    sim.node("alice", Node::default());
+    sim.node("bob", Node::default());
+    let alice = sim.gate("alice", "port");
+    let bob = sim.gate("bob", "port");
+    alice.connect(bob, None);
+
+    let mut rt = Builder::seeded(7).quiet().build(sim.freeze());
+    rt.start();
+
+    assert!(matches!(
+        rt.checkpoint(),
+        Err(CheckpointError::Unsupported(_))
+    ));
+}
@@ -0,0 +1,68 @@
+#![allow(unused)]
+
+use std::sync::{Arc, Mutex};
+
+use des::{
+    net::{blocks::HandlerFn, SimBuilder},
+    prelude::*,
+};
+
+/// The messages received by a node created using [`receiver`].
+#[derive(Debug, Clone, Default)]
+pub struct Received(Arc<Mutex<Vec<Receipt>>>);
+
+#[derive(Debug, Clone, Copy)]
+struct Receipt {
+    id: MessageId,
+    kind: MessageKind,
+    time: SimTime,
+    corrupted: bool,
+}
+
+impl Received {
+    /// The ids of all received messages, in the order they were received.
+    pub fn ids(&self) -> Vec<MessageId> {
+        self.0.lock().unwrap().iter().map(|r| r.id).collect()
+    }
+
+    /// The ids and kinds of all received messages, in the order they were received.
+    pub fn ids_and_kinds(&self) -> Vec<(MessageId, MessageKind)> {
+        let received = self.0.lock().unwrap();
+        received.iter().map(|r| (r.id, r.kind)).collect()
+    }
+
+    /// The ids and arrival times of all received messages, in the order they were received.
+    pub fn ids_and_times(&self) -> Vec<(MessageId, SimTime)> {
+        let received = self.0.lock().unwrap();
+        received.iter().map(|r| (r.id, r.time)).collect()
+    }
+
+    /// The ids of all received messages, that were marked as corrupted.
+    pub fn corrupted(&self) -> Vec<MessageId> {
+        let received = self.0.lock().unwrap();
+        received
+            .iter()
+            .filter(|r| r.corrupted)
+            .map(|r| r.id)
+            .collect()
+    }
+}
+
+/// Adds a node, that records all messages it receives.
+pub fn receiver<A>(sim: &mut SimBuilder<A>, path: &str) -> Received {
+    let received = Received::default();
+    let log = received.clone();
+    sim.node(
+        path,
+        HandlerFn::new(move |msg| {
+            let header = msg.header();
+            log.0.lock().unwrap().push(Receipt {
+                id: header.id,
+                kind: header.kind,
+                time: SimTime::now(),
+                corrupted: header.corrupted,
+            });
+        }),
+    );
+    received
+}
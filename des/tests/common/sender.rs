@@ -0,0 +1,60 @@
+#![allow(unused)]
+
+use des::{net::SimBuilder, prelude::*};
+
+use super::receiver::{receiver, Received};
+
+/// A node, that sends header-only messages onto its gate `out` at the start of the simulation.
+pub struct Sender {
+    sends: Vec<(MessageId, MessageKind, Duration)>,
+}
+
+impl Sender {
+    /// Sends a message with each id, after the given delay.
+    pub fn new(sends: Vec<(MessageId, Duration)>) -> Self {
+        let sends = sends
+            .into_iter()
+            .map(|(id, delay)| (id, 0, delay))
+            .collect();
+        Self { sends }
+    }
+
+    /// Sends a message of each kind immediately, using their position as id.
+    pub fn kinds(kinds: Vec<MessageKind>) -> Self {
+        let sends = kinds
+            .into_iter()
+            .enumerate()
+            .map(|(id, kind)| (id as MessageId, kind, Duration::ZERO))
+            .collect();
+        Self { sends }
+    }
+}
+
+impl Module for Sender {
+    fn at_sim_start(&mut self, _stage: usize) {
+        for &(id, kind, delay) in &self.sends {
+            send_in(Message::default().id(id).kind(kind), "out", delay);
+        }
+    }
+}
+
+/// The metrics of a queueing link with 100ms latency.
+pub fn link() -> ChannelMetrics {
+    // 10ms per header-only message
+    ChannelMetrics::new(
+        51_200,
+        Duration::from_millis(100),
+        Duration::ZERO,
+        ChannelDropBehaviour::Queue(None),
+    )
+}
+
+/// Connects a [`Sender`] `tx` to a [`receiver`] `rx`, using a [`link`].
+pub fn link_sim(sends: Vec<(MessageId, Duration)>) -> (SimBuilder<()>, Received) {
+    let mut sim = Sim::new(());
+    sim.node("tx", Sender::new(sends));
+    let received = receiver(&mut sim, "rx");
+    sim.gate("tx", "out")
+        .connect(sim.gate("rx", "in"), Some(Channel::new(link())));
+    (sim, received)
+}
@@ -0,0 +1,8 @@
+#![allow(unused)]
+
+use des::prelude::*;
+
+/// The simulation time `millis` milliseconds after the start of the simulation.
+pub fn at_millis(millis: u64) -> SimTime {
+    SimTime::ZERO + Duration::from_millis(millis)
+}
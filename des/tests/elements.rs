@@ -0,0 +1,168 @@
+#![cfg(feature = "net")]
+use des::{
+    net::processing::{
+        Delay, Direction, Filter, KindCounter, PacketCapture, ProcessingStack, RandomDrop,
+        TokenBucket,
+    },
+    prelude::*,
+};
+use receiver::receiver;
+use sender::Sender;
+use serial_test::serial;
+use time::at_millis;
+
+#[path = "common/receiver.rs"]
+mod receiver;
+#[path = "common/sender.rs"]
+mod sender;
+#[path = "common/time.rs"]
+mod time;
+
+/// Sends one header-only message (64 bytes) per kind from `tx` to `rx` at t=0,
+/// with the given stack and config installed on both nodes.
+fn run_sim(
+    cfg: &str,
+    stack: impl FnMut() -> ProcessingStack + 'static,
+    kinds: Vec<MessageKind>,
+) -> Vec<(u16, SimTime)> {
+    let mut sim = Sim::new(());
+    sim.include_cfg(cfg);
+    sim.set_stack(stack);
+    sim.node("tx", Sender::kinds(kinds));
+    let received = receiver(&mut sim, "rx");
+    sim.gate("tx", "out").connect(sim.gate("rx", "in"), None);
+
+    let _ = Builder::seeded(123).quiet().build(sim.freeze()).run();
+    received.ids_and_times()
+}
+
+#[test]
+#[serial]
+fn capture_and_count_messages() {
+    let capture = PacketCapture::new();
+    let counter = KindCounter::new();
+    let (c, k) = (capture.clone(), counter.clone());
+    let received = run_sim(
+        "tx.capture: { incoming: false, limit: 2 }",
+        move || (c.clone(), k.clone()).into(),
+        vec![1, 2, 2],
+    );
+    assert_eq!(received.len(), 3);
+
+    let records = capture.records();
+    let summary = records
+        .iter()
+        .map(|r| (r.module.as_str().to_string(), r.direction, r.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            ("tx".to_string(), Direction::Outgoing, 1),
+            ("tx".to_string(), Direction::Outgoing, 2),
+            ("rx".to_string(), Direction::Incoming, 1),
+            ("rx".to_string(), Direction::Incoming, 2),
+            ("rx".to_string(), Direction::Incoming, 2),
+        ]
+    );
+    assert_eq!(records[0].gate.as_ref().unwrap().as_str(), "tx.out");
+    assert_eq!(records[2].gate.as_ref().unwrap().as_str(), "rx.in");
+    assert_eq!(records[0].length, 64);
+
+    assert_eq!(counter.kinds(), [1, 2]);
+    assert_eq!((counter.incoming(1), counter.outgoing(1)), (1, 1));
+    assert_eq!((counter.incoming(2), counter.outgoing(2)), (2, 2));
+}
+
+#[test]
+#[serial]
+fn token_bucket_queues_or_drops() {
+    // 640 bytes/s and a burst of one message
+    let received = run_sim(
+        "tx.token-bucket: { rate: 5120, burst: 64, queue: true }",
+        || TokenBucket::new().into(),
+        vec![0, 0, 0],
+    );
+    assert_eq!(
+        received,
+        [(0, at_millis(0)), (1, at_millis(100)), (2, at_millis(200))]
+    );
+
+    let received = run_sim(
+        "tx.token-bucket: { rate: 5120, burst: 128 }",
+        || TokenBucket::new().into(),
+        vec![0, 0, 0],
+    );
+    assert_eq!(received, [(0, at_millis(0)), (1, at_millis(0))]);
+}
+
+#[test]
+#[serial]
+fn invalid_configurations_drop_messages() {
+    for cfg in [
+        "tx.token-bucket: { rate: 0, burst: 64, queue: true }",
+        "tx.token-bucket: { rate: -1.0 }",
+    ] {
+        let received = run_sim(cfg, || TokenBucket::new().into(), vec![0, 0]);
+        assert!(received.is_empty(), "{cfg}");
+    }
+
+    for cfg in [
+        "tx.delay: { delay: -1.0 }",
+        "tx.delay: { delay: .inf }",
+        "tx.delay: { delay: 0.1, jitter: .nan }",
+    ] {
+        let received = run_sim(cfg, || Delay::new().into(), vec![0, 0]);
+        assert!(received.is_empty(), "{cfg}");
+    }
+
+    // a rate so low, that the delay cannot be represented
+    let received = run_sim(
+        "tx.token-bucket: { rate: 1.0e-300, burst: 64, queue: true }",
+        || TokenBucket::new().into(),
+        vec![0, 0],
+    );
+    assert_eq!(received, [(0, at_millis(0))]);
+}
+
+#[test]
+#[serial]
+fn delay_and_random_drop_from_props() {
+    let received = run_sim(
+        "tx.delay: { delay: 0.5 }",
+        || Delay::new().into(),
+        vec![0, 0],
+    );
+    assert_eq!(received, [(0, at_millis(500)), (1, at_millis(500))]);
+
+    let received = run_sim(
+        "tx.lossy: { probability: 1.0 }",
+        || RandomDrop::new().with_key("lossy").into(),
+        vec![0; 10],
+    );
+    assert!(received.is_empty());
+
+    let received = run_sim(
+        "tx.random-drop: { probability: 0.5, incoming: false }",
+        || RandomDrop::new().into(),
+        vec![0; 100],
+    );
+    assert!(received.len() > 20 && received.len() < 80);
+}
+
+#[test]
+#[serial]
+fn filter_drops_messages_by_predicate() {
+    let received = run_sim(
+        "rx.filter: { incoming: false }",
+        || Filter::new(|msg| msg.header().kind != 42).into(),
+        vec![1, 42, 2],
+    );
+    assert_eq!(received, [(0, at_millis(0)), (2, at_millis(0))]);
+
+    let received = run_sim(
+        "tx.filter: { outgoing: false }",
+        || Filter::new(|msg| msg.header().kind != 42).into(),
+        vec![1, 42, 2],
+    );
+    assert_eq!(received, [(0, at_millis(0)), (2, at_millis(0))]);
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "net")]
+
+use des::{
+    experiment::{Experiment, ExperimentResults, ParameterGrid},
+    prelude::*,
+    runtime::Profiler,
+};
+use serde::Serialize;
+
+struct Source {
+    rate: f64,
+    sent: usize,
+}
+
+impl Module for Source {
+    fn at_sim_start(&mut self, _stage: usize) {
+        self.rate = current().prop::<f64>("rate").unwrap().or(1.0).get();
+        self.schedule();
+    }
+
+    fn handle_message(&mut self, _msg: Message) {
+        self.sent += 1;
+        self.schedule();
+    }
+}
+
+impl Source {
+    fn schedule(&self) {
+        let delay = -random::<f64>().ln() / self.rate;
+        schedule_in(Message::default(), Duration::from_secs_f64(delay));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Output {
+    sent: usize,
+    rate: f64,
+}
+
+fn experiment() -> Experiment<Sim<()>> {
+    Experiment::new(|run| {
+        let mut sim = Sim::new(());
+        sim.include_cfg(&run.parameters().to_cfg());
+        sim.node("source", Source { rate: 0.0, sent: 0 });
+        sim.freeze()
+    })
+    .grid(
+        ParameterGrid::new()
+            .axis("source.rate", [1.0, 10.0])
+            .axis("source.unused", ["a", "b,c"]),
+    )
+    .replications(5)
+    .seed(42)
+    .configure(|builder| builder.max_time(10.0.into()))
+}
+
+fn measure<E>(sim: Sim<()>, _: &Profiler<E>) -> Output {
+    let source = sim.get(&"source".into()).unwrap();
+    let source = source.as_ref::<Source>();
+    Output {
+        sent: source.sent,
+        rate: source.rate,
+    }
+}
+
+#[test]
+fn runs_cover_grid_and_replications() {
+    let results = experiment().run(measure);
+    assert_eq!(results.len(), 4 * 5);
+
+    for (i, record) in results.records().iter().enumerate() {
+        assert_eq!(record.run.point(), i / 5);
+        assert_eq!(record.run.replication(), i % 5);
+
+        let output = record.output.as_ref().unwrap();
+        let rate = record.run.parameters().get("source.rate").unwrap();
+        assert_eq!(output.rate, rate.as_f64().unwrap());
+        assert!(record.sim_time <= SimTime::from(10.0));
+    }
+
+    // higher rates should produce more messages
+    let mean = |point| {
+        results
+            .point(point)
+            .map(|r| r.output.as_ref().unwrap().sent)
+            .sum::<usize>()
+    };
+    assert!(mean(2) > mean(0));
+
+    // seeds are unique per run
+    let mut seeds = results
+        .records()
+        .iter()
+        .map(|r| r.run.seed())
+        .collect::<Vec<_>>();
+    seeds.sort_unstable();
+    seeds.dedup();
+    assert_eq!(seeds.len(), 20);
+}
+
+#[test]
+fn estimates_over_replications() {
+    let results = experiment().run(measure);
+
+    // rate 10.0 over 10s should produce about 100 messages
+    let estimate = results.estimate(2, |output| output.sent as f64).unwrap();
+    assert_eq!(estimate.count(), 5);
+    assert!(estimate.half_width() > 0.0);
+    assert!(estimate.interval().contains(&100.0));
+}
+
+#[test]
+fn runs_are_reproducible() {
+    let parallel = experiment().threads(4).run(measure);
+    let serial = experiment().threads(1).run(measure);
+
+    let outputs = |results: &ExperimentResults<Output>| {
+        results
+            .records()
+            .iter()
+            .map(|r| (r.run.seed(), r.output.clone().unwrap()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(outputs(&parallel), outputs(&serial));
+}
+
+#[test]
+fn export_csv_and_json() {
+    let results = experiment().run(measure);
+
+    let csv = results.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "point,replication,seed,source.rate,source.unused,sim_time,event_count,wall_time,sent,rate,error"
+    );
+    let first = lines.next().unwrap();
+    assert!(first.starts_with("0,0,"));
+    assert!(first.contains(",1.0,a,"));
+    assert_eq!(lines.count(), 19);
+    assert!(csv.contains(",\"b,c\","));
+
+    let json = results.to_json();
+    assert!(json.starts_with('['));
+    assert!(json.contains("\"source.rate\": 10.0"));
+    assert!(json.contains("\"sent\":"));
+}
+
+struct Failing;
+
+impl Module for Failing {
+    fn at_sim_end(&mut self) -> Result<(), RuntimeError> {
+        Err(RuntimeError::new(vec![std::io::Error::other("failed")]))
+    }
+}
+
+#[test]
+fn failed_runs_are_recorded() {
+    let results = Experiment::new(|_| {
+        let mut sim = Sim::new(());
+        sim.node("failing", Failing);
+        sim.freeze()
+    })
+    .replications(2)
+    .run(|_, _| 1);
+    assert_eq!(results.len(), 2);
+
+    for record in results.records() {
+        assert!(record.output.as_ref().unwrap_err().contains("failed"));
+    }
+    let csv = results.to_csv();
+    assert!(csv.contains("- failed"));
+}
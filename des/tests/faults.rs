@@ -0,0 +1,328 @@
+#![cfg(feature = "net")]
+use std::sync::{Arc, Mutex};
+
+use des::{
+    net::{
+        fault::{Fault, FaultAction, Scenario, ScenarioError, TimeDistribution},
+        module::{Stereotyp, Supervision},
+        PanicError,
+    },
+    prelude::*,
+};
+use sender::{link_sim, Sender};
+use serial_test::serial;
+use time::at_millis;
+
+#[path = "common/receiver.rs"]
+mod receiver;
+#[path = "common/sender.rs"]
+mod sender;
+#[path = "common/time.rs"]
+mod time;
+
+type Log = Arc<Mutex<Vec<(u16, SimTime)>>>;
+
+#[test]
+#[serial]
+fn link_faults_from_scenario() {
+    let sends = (0..6)
+        .map(|id| (id, Duration::from_millis(500 + 1000 * u64::from(id))))
+        .collect();
+    let (mut sim, log) = link_sim(sends);
+    sim.include_faults(
+        r"
+faults:
+  - link-down: tx.out
+    at: 1.0
+    mttr: 1.0
+  - degrade: { link: tx.out, latency: 0.3 }
+    at: 2.0
+    duration: 1.0
+  - loss: { link: rx.in, probability: 1.0 }
+    at: 4.0
+    mttr: 1.0
+",
+    )
+    .unwrap();
+    let channel = sim.gate("tx", "out").channel().unwrap();
+
+    let _ = Builder::seeded(123)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        log.ids_and_times(),
+        vec![
+            (0, at_millis(610)),
+            (2, at_millis(2810)),
+            (3, at_millis(3610)),
+            (5, at_millis(5610)),
+        ]
+    );
+    assert!(channel.is_up());
+    assert_eq!(channel.metrics().latency, Duration::from_millis(100));
+    assert_eq!(channel.loss_model(), LossModel::None);
+}
+
+#[test]
+#[serial]
+fn corrupt_fault_delivers_corrupted_messages() {
+    let sends = (0..5)
+        .map(|id| (id, Duration::from_millis(500 + 1000 * u64::from(id))))
+        .collect();
+    let (mut sim, log) = link_sim(sends);
+    sim.include_faults(
+        r"
+faults:
+  - corrupt: { link: tx.out, probability: 1.0 }
+    at: 1.0
+    mttr: 2.0
+",
+    )
+    .unwrap();
+    let channel = sim.gate("tx", "out").channel().unwrap();
+
+    let _ = Builder::seeded(123)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+
+    assert_eq!(log.ids(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(log.corrupted(), vec![1, 2]);
+    assert_eq!(channel.corruption_model(), LossModel::None);
+}
+
+type Starts = Arc<Mutex<Vec<(String, SimTime)>>>;
+
+struct Worker {
+    starts: Starts,
+    received: Log,
+}
+
+impl Module for Worker {
+    fn reset(&mut self) {}
+
+    fn at_sim_start(&mut self, _: usize) {
+        self.starts
+            .lock()
+            .unwrap()
+            .push((current().path().to_string(), SimTime::now()));
+    }
+
+    fn handle_message(&mut self, msg: Message) {
+        self.received
+            .lock()
+            .unwrap()
+            .push((msg.header().id, SimTime::now()));
+    }
+}
+
+#[test]
+#[serial]
+fn timed_crash_and_restart() {
+    let starts = Starts::default();
+    let received = Log::default();
+
+    let mut sim = Sim::new(());
+    for node in ["a", "b"] {
+        sim.node(
+            node,
+            Worker {
+                starts: starts.clone(),
+                received: received.clone(),
+            },
+        );
+    }
+    sim.include_faults(
+        r"
+faults:
+  - crash: a
+    at: 1.0
+    mttr: 0.5
+  - restart: b
+    at: 2.0
+",
+    )
+    .unwrap();
+    let gate = sim.gate("a", "port");
+
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    rt.add_message_onto(gate.clone(), Message::default().id(1), at_millis(1200));
+    rt.add_message_onto(gate, Message::default().id(2), at_millis(1700));
+    let _ = rt.run().unwrap();
+
+    assert_eq!(
+        *starts.lock().unwrap(),
+        vec![
+            ("a".to_string(), SimTime::ZERO),
+            ("b".to_string(), SimTime::ZERO),
+            ("a".to_string(), at_millis(1500)),
+            ("b".to_string(), at_millis(2000)),
+        ]
+    );
+    assert_eq!(*received.lock().unwrap(), vec![(2, at_millis(1700))]);
+}
+
+struct Supervisor {
+    failures: Arc<Mutex<Vec<String>>>,
+}
+
+impl Module for Supervisor {
+    fn handle_child_failure(&mut self, child: ModuleRef, err: &PanicError) -> Supervision {
+        self.failures
+            .lock()
+            .unwrap()
+            .push(format!("{} <- {}", child.path(), err.path));
+        Supervision::OneForOne
+    }
+}
+
+#[test]
+#[serial]
+fn injected_crash_informs_supervisor() {
+    let starts = Starts::default();
+    let failures = Arc::new(Mutex::new(Vec::new()));
+
+    let mut sim = Sim::new(());
+    sim.node(
+        "sup",
+        Supervisor {
+            failures: failures.clone(),
+        },
+    );
+    sim.node(
+        "sup.worker",
+        Worker {
+            starts: starts.clone(),
+            received: Log::default(),
+        },
+    );
+    sim.get(&"sup.worker".into())
+        .unwrap()
+        .set_stereotyp(Stereotyp::SUBPROCESS);
+    sim.include_faults("faults:\n  - crash: sup.worker\n    at: 1.0")
+        .unwrap();
+
+    let _ = Builder::seeded(123)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+
+    assert_eq!(*failures.lock().unwrap(), ["sup.worker <- sup.worker"]);
+    assert_eq!(
+        *starts.lock().unwrap(),
+        vec![
+            ("sup.worker".to_string(), SimTime::ZERO),
+            ("sup.worker".to_string(), at_millis(1000)),
+        ]
+    );
+}
+
+fn run_random_faults(seed: u64) -> Vec<(u16, SimTime)> {
+    let sends = (0..200)
+        .map(|id| (id, Duration::from_millis(50 * u64::from(id))))
+        .collect();
+    let (mut sim, log) = link_sim(sends);
+    sim.include_faults(
+        r"
+faults:
+  - link-down: tx.out
+    mtbf: { exponential: 1.0 }
+    mttr: { uniform: [0.1, 0.5] }
+    until: 8.0
+  - crash: rx
+    mtbf: { weibull: { shape: 1.5, scale: 2.0 } }
+    mttr: 0.2
+    until: 8.0
+",
+    )
+    .unwrap();
+
+    let _ = Builder::seeded(seed)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+    log.ids_and_times()
+}
+
+#[test]
+#[serial]
+fn random_faults_are_reproducible() {
+    let first = run_random_faults(1);
+    assert!(!first.is_empty());
+    assert!(first.len() < 200);
+
+    assert_eq!(run_random_faults(1), first);
+    assert_ne!(run_random_faults(2), first);
+}
+
+#[test]
+fn scenario_parsing_and_validation() {
+    let scenario = Scenario::from_yaml(
+        r"
+faults:
+  - crash: a.b
+    at: { uniform: [1.0, 2.0] }
+    mtbf: { weibull: { shape: 2.0, scale: 10.0 } }
+    mttr: { exponential: 0.5 }
+    until: 100.0
+",
+    )
+    .unwrap();
+    assert_eq!(
+        scenario.faults,
+        vec![Fault {
+            action: FaultAction::Crash("a.b".into()),
+            at: Some(TimeDistribution::Uniform { min: 1.0, max: 2.0 }),
+            mttr: Some(TimeDistribution::Exponential(0.5)),
+            mtbf: Some(TimeDistribution::Weibull {
+                shape: 2.0,
+                scale: 10.0
+            }),
+            until: Some(100.0),
+        }]
+    );
+
+    let mut sim = Sim::new(());
+    sim.node("a", Sender::new(Vec::new()));
+    sim.gate("a", "port");
+    for raw in [
+        "faults:\n  - crash: b\n    at: 1.0",
+        "faults:\n  - link-down: a.other\n    at: 1.0",
+        "faults:\n  - crash: a\n    at: 1.0\n  - restart: a.b\n    at: 1.0",
+    ] {
+        let err = sim.include_faults(raw).unwrap_err();
+        assert!(matches!(err, ScenarioError::Invalid(_)), "{err}");
+    }
+    sim.include_faults("faults:\n  - link-down: a.port\n    at: 1.0")
+        .unwrap();
+    drop(sim);
+
+    for (raw, parse_error) in [
+        ("faults:\n  - explode: a\n    at: 1.0", true),
+        ("faults:\n  - restart: a\n    at: 1.0\n    mttr: 1.0", false),
+        ("faults:\n  - crash: a\n    mtbf: 1.0", false),
+        ("faults:\n  - link-up: a.port", false),
+        ("faults:\n  - loss: { link: a.port }\n    at: 1.0", false),
+        (
+            "faults:\n  - loss: { link: a.port, probability: 2.0 }\n    at: 1.0",
+            false,
+        ),
+        (
+            "faults:\n  - corrupt: { link: a.port, bit-error-rate: 1e-3, probability: 0.5 }\n    at: 1.0",
+            false,
+        ),
+        (
+            "faults:\n  - crash: a\n    at: { exponential: -1.0 }",
+            false,
+        ),
+    ] {
+        let err = Scenario::from_yaml(raw).unwrap_err();
+        assert_eq!(matches!(err, ScenarioError::Parse(_)), parse_error, "{err}");
+    }
+}
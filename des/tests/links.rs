@@ -0,0 +1,94 @@
+#![cfg(feature = "net")]
+use des::prelude::*;
+use sender::link_sim;
+use serial_test::serial;
+use time::at_millis;
+
+#[path = "common/receiver.rs"]
+mod receiver;
+#[path = "common/sender.rs"]
+mod sender;
+#[path = "common/time.rs"]
+mod time;
+
+#[test]
+#[serial]
+fn link_down_drops_queued_and_in_flight_messages() {
+    let mut sends = (0..5).map(|id| (id, Duration::ZERO)).collect::<Vec<_>>();
+    sends.push((5, Duration::from_millis(60)));
+    let (mut sim, log) = link_sim(sends);
+    let channel = sim.gate("tx", "out").channel().unwrap();
+
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    rt.link_down_at("tx.out", at_millis(25));
+    rt.link_up_at("tx.out", at_millis(50));
+    let _ = rt.run().unwrap();
+
+    // Messages 0..3 were on the medium, messages 3..5 still queued
+    assert_eq!(log.ids_and_times(), vec![(5, at_millis(170))]);
+    assert_eq!(channel.dropped(), 5);
+    assert!(channel.is_up());
+}
+
+#[test]
+#[serial]
+fn messages_sent_onto_down_link_are_dropped() {
+    let (mut sim, log) = link_sim(vec![(0, Duration::ZERO), (1, Duration::from_millis(50))]);
+    let channel = sim.gate("tx", "out").channel().unwrap();
+    channel.set_down();
+    assert!(!channel.is_up());
+
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    rt.link_up_at("rx.in", at_millis(20));
+    let _ = rt.run().unwrap();
+
+    assert_eq!(log.ids_and_times(), vec![(1, at_millis(160))]);
+    assert_eq!(channel.dropped(), 1);
+}
+
+#[test]
+#[serial]
+fn link_metrics_change_mid_run() {
+    let (sim, log) = link_sim(vec![(0, Duration::ZERO), (1, Duration::from_millis(50))]);
+
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    rt.set_link_metrics_at(
+        "tx.out",
+        ChannelMetrics::new(
+            0,
+            Duration::from_millis(10),
+            Duration::ZERO,
+            ChannelDropBehaviour::Drop,
+        ),
+        at_millis(20),
+    );
+    let _ = rt.run().unwrap();
+
+    assert_eq!(
+        log.ids_and_times(),
+        vec![(1, at_millis(60)), (0, at_millis(110)),]
+    );
+}
+
+#[test]
+#[serial]
+fn link_state_in_topology() {
+    let (mut sim, _) = link_sim(Vec::new());
+    let modules = ["tx", "rx"].map(|name| sim.get(&name.into()).unwrap());
+
+    let alive = |topo: Topology<(), ()>| {
+        topo.with_edge_cost_attachment()
+            .edges()
+            .map(|edge| edge.attachment.alive)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(alive(Topology::from_modules(&modules)), [true, true]);
+
+    sim.gate("tx", "out").channel().unwrap().set_down();
+    let mut states = alive(Topology::from_modules(&modules));
+    states.sort_unstable();
+    assert_eq!(states, [false, true]);
+
+    assert!(sim.gate("tx", "out").disconnect(&sim.gate("rx", "in")));
+    assert_eq!(Topology::from_modules(&modules).edges().count(), 0);
+}
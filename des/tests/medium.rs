@@ -0,0 +1,183 @@
+#![cfg(feature = "net")]
+use std::sync::{Arc, Mutex};
+
+use des::{
+    net::channel::{Collision, Medium, MediumRef},
+    prelude::*,
+};
+use rand::distr::Uniform;
+use serial_test::serial;
+
+type Log = Arc<Mutex<Vec<(String, SimTime, u16, bool)>>>;
+
+struct Station {
+    name: &'static str,
+    sends: Vec<(u16, Duration)>,
+    log: Log,
+}
+
+impl Module for Station {
+    fn at_sim_start(&mut self, _stage: usize) {
+        for &(id, delay) in &self.sends {
+            send_in(Message::default().id(id), "eth", delay);
+        }
+    }
+
+    fn handle_message(&mut self, msg: Message) {
+        let id = msg.header().id;
+        let collided = msg.try_content::<Collision>().is_some();
+        self.log
+            .lock()
+            .unwrap()
+            .push((self.name.to_string(), SimTime::now(), id, collided));
+    }
+}
+
+fn bus() -> ChannelMetrics {
+    // 1 byte per ms, so a header-only message occupies the medium for 64ms
+    ChannelMetrics::new(
+        8000,
+        Duration::from_millis(5),
+        Duration::ZERO,
+        ChannelDropBehaviour::Drop,
+    )
+}
+
+fn medium_run(
+    delay: DelayModel,
+    sends: [Vec<(u16, Duration)>; 3],
+) -> (MediumRef, Vec<(String, SimTime, u16, bool)>) {
+    let log = Log::default();
+    let mut sim = Sim::new(());
+    let medium = Medium::new(bus());
+    medium.set_delay_model(delay);
+    for (name, sends) in ["a", "b", "c"].into_iter().zip(sends) {
+        sim.node(
+            name,
+            Station {
+                name,
+                sends,
+                log: log.clone(),
+            },
+        );
+        medium.attach(sim.gate(name, "eth"));
+    }
+
+    let _ = Builder::seeded(123)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+
+    let mut log = log.lock().unwrap().clone();
+    log.sort_by(|l, r| l.0.cmp(&r.0));
+    (medium, log)
+}
+
+#[test]
+#[serial]
+fn medium_broadcasts_to_all_other_attachees() {
+    let (medium, log) = medium_run(
+        DelayModel::Uniform,
+        [vec![(1, Duration::ZERO)], vec![], vec![]],
+    );
+
+    let arrival = SimTime::ZERO + Duration::from_millis(69);
+    assert_eq!(
+        log,
+        vec![
+            ("b".to_string(), arrival, 1, false),
+            ("c".to_string(), arrival, 1, false),
+        ]
+    );
+    assert_eq!(medium.transmissions(), 1);
+    assert_eq!(medium.collisions(), 0);
+}
+
+#[test]
+#[serial]
+fn medium_applies_delay_model() {
+    let delay = Duration::from_millis(3);
+    let (_, log) = medium_run(
+        DelayModel::custom(Uniform::new_inclusive(delay, delay).unwrap()),
+        [vec![(1, Duration::ZERO)], vec![], vec![]],
+    );
+
+    let arrival = SimTime::ZERO + Duration::from_millis(69) + delay;
+    assert_eq!(
+        log,
+        vec![
+            ("b".to_string(), arrival, 1, false),
+            ("c".to_string(), arrival, 1, false),
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn medium_sequential_transmissions_do_not_collide() {
+    let (medium, log) = medium_run(
+        DelayModel::Uniform,
+        [
+            vec![(1, Duration::ZERO)],
+            vec![(2, Duration::from_millis(100))],
+            vec![],
+        ],
+    );
+
+    assert_eq!(log.len(), 4);
+    assert!(log.iter().all(|entry| !entry.3));
+    assert!(log.iter().all(|entry| entry.0 != "a" || entry.2 == 2));
+    assert!(log.iter().all(|entry| entry.0 != "b" || entry.2 == 1));
+    assert_eq!(medium.transmissions(), 2);
+    assert_eq!(medium.collisions(), 0);
+}
+
+#[test]
+#[serial]
+fn medium_overlapping_transmissions_collide() {
+    let (medium, log) = medium_run(
+        DelayModel::Uniform,
+        [
+            vec![(1, Duration::ZERO)],
+            vec![(2, Duration::from_millis(10))],
+            vec![],
+        ],
+    );
+
+    let detected = SimTime::ZERO + Duration::from_millis(15);
+    assert_eq!(
+        log,
+        vec![
+            ("a".to_string(), detected, 1, true),
+            ("b".to_string(), detected, 2, true),
+        ]
+    );
+    assert_eq!(medium.transmissions(), 2);
+    assert_eq!(medium.collisions(), 2);
+    assert!(!medium.is_busy());
+}
+
+#[test]
+#[serial]
+fn medium_in_topology() {
+    let mut sim = Sim::new(());
+    let medium = Medium::new(bus());
+    for name in ["a", "b", "c"] {
+        sim.node(
+            name,
+            Station {
+                name,
+                sends: Vec::new(),
+                log: Log::default(),
+            },
+        );
+        medium.attach(sim.gate(name, "eth"));
+    }
+
+    let modules = ["a", "b", "c"].map(|name| sim.get(&name.into()).unwrap());
+    let topology = Topology::from_modules(&modules);
+    assert_eq!(topology.edges().count(), 3 * 2);
+    assert!(topology.bidirectional());
+    assert!(topology.connected());
+}
@@ -2,7 +2,10 @@ use common::*;
 use des::{net::ndl::Ndl, prelude::*, registry};
 use des_net_utils::ndl::error;
 use serial_test::serial;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 #[path = "common/mock.rs"]
 mod mock;
@@ -194,6 +197,79 @@ fn non_std_gate_connections() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn link_loss_models() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Sim::new(());
+    sim.node(
+        "",
+        Ndl::from_str(
+            &mut Registry::new().with_default_fallback(),
+            include_str!("ndl/lossy.yml"),
+        )?,
+    )?;
+
+    let mut channel = |gate: &str| sim.gate("", gate).channel().unwrap().loss_model();
+    assert_eq!(
+        channel("a"),
+        LossModel::GilbertElliott {
+            p: 0.1,
+            r: 0.5,
+            loss_good: 0.0,
+            loss_bad: 1.0
+        }
+    );
+    assert_eq!(channel("c"), LossModel::BitError(0.0001));
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn link_delay_models() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Sim::new(());
+    sim.node(
+        "",
+        Ndl::from_str(
+            &mut Registry::new().with_default_fallback(),
+            include_str!("ndl/jitter.yml"),
+        )?,
+    )?;
+
+    let mut channel = |gate: &str| sim.gate("", gate).channel().unwrap();
+    let ordered = channel("a");
+    assert_eq!(ordered.delay_model(), DelayModel::Normal);
+    assert!(ordered.is_fifo());
+
+    let heavy_tail = channel("c");
+    assert_eq!(heavy_tail.delay_model(), DelayModel::Pareto(1.5));
+    assert_eq!(heavy_tail.metrics().jitter, Duration::from_millis(10));
+    assert!(!heavy_tail.is_fifo());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn broadcast_media() -> Result<(), Box<dyn std::error::Error>> {
+    let mut sim = Sim::new(());
+    sim.node(
+        "",
+        Ndl::from_str(
+            &mut Registry::new().with_default_fallback(),
+            include_str!("ndl/bus.yml"),
+        )?,
+    )?;
+
+    let medium = sim.gate("router", "eth").medium().expect("medium");
+    assert_eq!(medium.attachees().len(), 4);
+    assert_eq!(medium.metrics().latency, Duration::from_millis(5));
+    assert_eq!(medium.delay_model(), DelayModel::Exponential);
+    for k in 0..3 {
+        let gate = sim.gate(format!("host[{k}]"), "eth");
+        assert!(Arc::ptr_eq(&gate.medium().expect("medium"), &medium));
+    }
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn registry_missing_symbol() {
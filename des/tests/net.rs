@@ -1,11 +1,13 @@
 use des::{
     net::{
         blocks::{AsyncFn, HandlerFn},
-        globals,
+        globals, NetEvents,
     },
     prelude::*,
+    runtime::RuntimeObserver,
 };
 use serial_test::serial;
+use std::{cell::RefCell, rc::Rc};
 
 #[derive(Default)]
 struct Receiver {
@@ -101,3 +103,119 @@ fn select_node_from_globals() -> Result<(), RuntimeError> {
 
     Builder::seeded(123).build(sim.freeze()).run().map(|_| ())
 }
+
+#[derive(Default)]
+struct PairSender;
+
+impl Module for PairSender {
+    fn at_sim_start(&mut self, _stage: usize) {
+        send(Message::default().id(1), "port");
+        send_in(Message::default().id(2), "port", Duration::from_secs(1));
+    }
+}
+
+fn pair_sim() -> Sim<()> {
+    let mut sim = Sim::new(());
+    sim.node("rx", HandlerFn::new(|_| {}));
+    sim.node("tx", PairSender);
+
+    let tx = sim.gate("tx", "port");
+    tx.connect(
+        sim.gate("rx", "port"),
+        Some(Channel::new(ChannelMetrics {
+            bitrate: 10000,
+            latency: Duration::from_millis(100),
+            jitter: Duration::ZERO,
+            drop_behaviour: ChannelDropBehaviour::Queue(None),
+        })),
+    );
+    sim.freeze()
+}
+
+#[test]
+#[serial]
+fn step_through_net_events() {
+    let mut rt = Builder::seeded(123).quiet().build(pair_sim());
+    rt.start();
+
+    let pending = rt
+        .pending_events()
+        .map(|(event, time)| (event.describe(), time))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pending,
+        [
+            ("ChannelUnbusyNotif".to_string(), SimTime::from(0.0512)),
+            (
+                "MessageExitingConnection { gate: rx.port, id: 1 }".to_string(),
+                SimTime::from(0.1512)
+            ),
+            (
+                "MessageExitingConnection { gate: tx.port, id: 2 }".to_string(),
+                SimTime::from(1.0)
+            ),
+        ]
+    );
+
+    let (next, time) = rt.peek_next().unwrap();
+    assert_eq!(next.describe(), "ChannelUnbusyNotif");
+    assert_eq!(time, SimTime::from(0.0512));
+
+    let mut steps = Vec::new();
+    while let Some(step) = rt.step() {
+        steps.push(step.to_string());
+    }
+    assert_eq!(
+        steps,
+        [
+            "#1 @ 51.2ms: ChannelUnbusyNotif",
+            "#2 @ 151.2ms: MessageExitingConnection { gate: rx.port, id: 1 }",
+            "#3 @ 151.2ms: HandleMessageEvent { module: rx, id: 1 }",
+            "#4 @ 1s: MessageExitingConnection { gate: tx.port, id: 2 }",
+            "#5 @ 1.0512s: ChannelUnbusyNotif",
+            "#6 @ 1.1512s: MessageExitingConnection { gate: rx.port, id: 2 }",
+            "#7 @ 1.1512s: HandleMessageEvent { module: rx, id: 2 }",
+        ]
+    );
+    assert!(rt.peek_next().is_none());
+
+    let (_, time, profiler) = rt.finish().unwrap();
+    assert_eq!(time, SimTime::from(1.1512));
+    assert_eq!(profiler.event_count, 7);
+}
+
+#[derive(Clone, Default)]
+struct Trace(Rc<RefCell<Vec<(SimTime, String)>>>);
+
+impl RuntimeObserver<NetEvents> for Trace {
+    fn on_event_dispatch(&mut self, time: SimTime, event: &NetEvents) {
+        self.0.borrow_mut().push((time, event.describe()));
+    }
+}
+
+#[test]
+#[serial]
+fn observed_runs_are_deterministic() {
+    let run = || {
+        let trace = Trace::default();
+        let _ = Builder::seeded(123)
+            .quiet()
+            .with_app()
+            .observer(trace.clone())
+            .build(pair_sim())
+            .run()
+            .unwrap();
+        trace.0.take()
+    };
+
+    let trace = run();
+    assert_eq!(trace.len(), 7);
+    assert_eq!(
+        trace[2],
+        (
+            SimTime::from(0.1512),
+            "HandleMessageEvent { module: rx, id: 1 }".to_string()
+        )
+    );
+    assert_eq!(trace, run());
+}
@@ -74,6 +74,7 @@ impl Module for SimPanicAtHandle {
             on_panic_restart: false,
             on_panic_drop_submodules: true,
             on_panic_inform_parent: false,
+            ..Default::default()
         });
         panic!("Oh no");
     }
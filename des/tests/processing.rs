@@ -6,6 +6,10 @@ use std::sync::atomic::Ordering::SeqCst;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
+#[path = "common/receiver.rs"]
+mod receiver;
+use receiver::receiver;
+
 mod lcommon {
     use des::net::processing::*;
     use des::prelude::*;
@@ -274,3 +278,93 @@ fn custom_default_pe() {
     let _ = rt.run();
     assert!(DONE.load(Ordering::SeqCst));
 }
+
+type Log = Arc<std::sync::Mutex<Vec<u16>>>;
+
+struct Tag {
+    tag: u16,
+    log: Log,
+}
+impl ProcessingElement for Tag {
+    fn incoming(&mut self, msg: Message) -> Option<Message> {
+        // Messages sent here only pass the elements closer to the network layer
+        send(Message::default().id(100 + self.tag), "out");
+        Some(msg)
+    }
+
+    fn outgoing(&mut self, mut msg: Message, gate: &GateRef) -> Option<Message> {
+        assert_eq!(gate.name(), "out");
+        self.log.lock().unwrap().push(self.tag);
+        msg.header_mut().id = msg.header().id * 10 + self.tag;
+        Some(msg)
+    }
+}
+
+struct DuplicateAndDropOdd;
+impl ProcessingElement for DuplicateAndDropOdd {
+    fn outgoing(&mut self, msg: Message, _gate: &GateRef) -> Option<Message> {
+        let id = msg.header().id;
+        if id % 2 == 1 {
+            return None;
+        }
+        send_in(Message::default().id(id + 2), "out", Duration::from_secs(1));
+        Some(msg)
+    }
+}
+
+fn run_outgoing_sim(stack: impl FnMut() -> ProcessingStack + 'static, ids: Vec<u16>) -> Vec<u16> {
+    struct Sender {
+        ids: Vec<u16>,
+    }
+    impl Module for Sender {
+        fn at_sim_start(&mut self, _: usize) {
+            for &id in &self.ids {
+                send(Message::default().id(id), "out");
+            }
+            schedule_in(Message::default(), Duration::ZERO);
+        }
+    }
+
+    let mut sim = Sim::new(());
+    sim.set_stack(stack);
+    sim.node("tx", Sender { ids });
+    sim.set_stack(ProcessingStack::default);
+    let received = receiver(&mut sim, "rx");
+    sim.gate("tx", "out").connect(sim.gate("rx", "in"), None);
+
+    let _ = Builder::seeded(123).quiet().build(sim.freeze()).run();
+    received.ids()
+}
+
+#[test]
+#[serial]
+fn outgoing_passes_stack_in_reverse_order() {
+    let log = Log::default();
+    let stack_log = log.clone();
+    let received = run_outgoing_sim(
+        move || {
+            (
+                Tag {
+                    tag: 1,
+                    log: stack_log.clone(),
+                },
+                Tag {
+                    tag: 2,
+                    log: stack_log.clone(),
+                },
+            )
+                .into()
+        },
+        vec![0],
+    );
+
+    assert_eq!(received, [21, 101, 1021]);
+    assert_eq!(*log.lock().unwrap(), [2, 1, 1]);
+}
+
+#[test]
+#[serial]
+fn outgoing_can_drop_and_duplicate() {
+    let received = run_outgoing_sim(|| DuplicateAndDropOdd.into(), vec![1, 2, 3]);
+    assert_eq!(received, [2, 4]);
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "net")]
+use std::sync::{Arc, Mutex};
+
+use des::{
+    net::{
+        processing::{module_address, Router},
+        SimBuilder,
+    },
+    prelude::*,
+};
+use serial_test::serial;
+
+type Log = Arc<Mutex<Vec<(String, SimTime)>>>;
+
+struct Node {
+    sends: Vec<(ModuleId, Duration)>,
+    log: Option<Log>,
+}
+
+impl Module for Node {
+    fn at_sim_start(&mut self, _stage: usize) {
+        for &(dst, delay) in &self.sends {
+            schedule_in(Message::default().dst(module_address(dst)), delay);
+        }
+    }
+
+    fn handle_message(&mut self, msg: Message) {
+        let log = self.log.as_ref().expect("unexpected local message");
+        let gate = msg.header().last_gate.as_ref().unwrap().name().to_string();
+        log.lock().unwrap().push((gate, SimTime::now()));
+    }
+}
+
+fn link() -> Option<ChannelRef> {
+    Some(Channel::new(ChannelMetrics::new(
+        51_200,
+        Duration::from_millis(10),
+        Duration::ZERO,
+        ChannelDropBehaviour::Queue(None),
+    )))
+}
+
+// a - b - c
+//  \     /
+//   d - e
+fn routed_sim(sends: &[Duration], router: fn() -> Router) -> (SimBuilder<()>, Log) {
+    let log = Log::default();
+    let mut sim = Sim::new(());
+    sim.set_stack(router);
+    sim.node(
+        "c",
+        Node {
+            sends: Vec::new(),
+            log: Some(log.clone()),
+        },
+    );
+    let c = sim.get(&"c".into()).unwrap().id();
+
+    sim.node(
+        "a",
+        Node {
+            sends: sends.iter().map(|&delay| (c, delay)).collect(),
+            log: None,
+        },
+    );
+    for node in ["b", "d", "e"] {
+        sim.node(
+            node,
+            Node {
+                sends: Vec::new(),
+                log: None,
+            },
+        );
+    }
+
+    for (from, to) in [("a", "b"), ("b", "c"), ("a", "d"), ("d", "e"), ("e", "c")] {
+        sim.gate(from, to).connect(sim.gate(to, from), link());
+    }
+    (sim, log)
+}
+
+#[test]
+#[serial]
+fn messages_are_forwarded_on_shortest_path() {
+    let (sim, log) = routed_sim(&[Duration::ZERO], Router::new);
+    let _ = Builder::seeded(123).quiet().build(sim.freeze()).run();
+
+    // two hops, each 10ms transmission and 10ms latency
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![("b".to_string(), SimTime::ZERO + Duration::from_millis(40))]
+    );
+}
+
+#[test]
+#[serial]
+fn routes_are_recomputed_on_link_failure() {
+    let (sim, log) = routed_sim(&[Duration::ZERO, Duration::from_millis(100)], Router::new);
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    rt.link_down_at("a.b", SimTime::ZERO + Duration::from_millis(50));
+    let _ = rt.run();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            ("b".to_string(), SimTime::ZERO + Duration::from_millis(40)),
+            ("e".to_string(), SimTime::ZERO + Duration::from_millis(160)),
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn routes_are_recomputed_on_link_reconfiguration() {
+    let (sim, log) = routed_sim(&[Duration::ZERO, Duration::from_millis(100)], || {
+        Router::new().with_weight(|edge| {
+            let channel = edge.from.gate().channel().unwrap();
+            channel.metrics().latency.as_secs_f64()
+        })
+    });
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    let mut metrics = link().unwrap().metrics();
+    metrics.latency = Duration::from_secs(1);
+    rt.set_link_metrics_at("a.b", metrics, SimTime::ZERO + Duration::from_millis(50));
+    let _ = rt.run();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            ("b".to_string(), SimTime::ZERO + Duration::from_millis(40)),
+            ("e".to_string(), SimTime::ZERO + Duration::from_millis(160)),
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn unreachable_messages_are_dropped() {
+    let (mut sim, log) = routed_sim(&[Duration::ZERO], Router::new);
+    sim.gate("a", "b").disconnect(&sim.gate("b", "a"));
+    sim.gate("a", "d").disconnect(&sim.gate("d", "a"));
+    let _ = Builder::seeded(123).quiet().build(sim.freeze()).run();
+
+    assert!(log.lock().unwrap().is_empty());
+}
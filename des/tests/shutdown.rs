@@ -1,7 +1,10 @@
 #![cfg(feature = "async")]
 
 use des::{
-    net::{blocks::ModuleFn, module::Module},
+    net::{
+        blocks::{FailabilityPolicy, HandlerFn, ModuleFn},
+        module::{Module, RestartOutcome, RestartPolicy, RestartRecord, Stereotyp},
+    },
     prelude::*,
     time::sleep,
 };
@@ -481,3 +484,94 @@ fn shutdown_prevents_accessing_parents() {
 
     let _ = Builder::seeded(123).build(sim.freeze()).run();
 }
+
+#[test]
+#[serial]
+fn failable_restart_applies_restart_policy() {
+    static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+    let mut sim = Sim::new(());
+    sim.node(
+        "alice",
+        HandlerFn::failable(
+            |_| {
+                HANDLED.fetch_add(1, Ordering::SeqCst);
+                Err(std::io::Error::other("failure"))
+            },
+            FailabilityPolicy::Restart,
+        ),
+    );
+    sim.get(&"alice".into()).unwrap().set_stereotyp(Stereotyp {
+        restart_policy: RestartPolicy::UNLIMITED
+            .with_limit(2, Duration::from_secs(100))
+            .with_backoff(Duration::from_secs(1), 2, Duration::from_secs(60)),
+        ..Stereotyp::HOST
+    });
+    let gate = sim.gate("alice", "port");
+
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    for t in [1.0, 1.5, 5.0, 10.0, 15.0] {
+        rt.add_message_onto(gate.clone(), Message::default(), t.into());
+    }
+    let (app, _, _) = rt.run().unwrap();
+
+    // the messages at 1.5s and 15s arrive while alice is shut down
+    assert_eq!(HANDLED.load(Ordering::SeqCst), 3);
+    let at = |secs: u64| SimTime::ZERO + Duration::from_secs(secs);
+    assert_eq!(
+        app.get(&"alice".into()).unwrap().restart_history(),
+        [
+            RestartRecord {
+                time: at(1),
+                outcome: RestartOutcome::Scheduled(at(2)),
+            },
+            RestartRecord {
+                time: at(5),
+                outcome: RestartOutcome::Scheduled(at(7)),
+            },
+            RestartRecord {
+                time: at(10),
+                outcome: RestartOutcome::GaveUp,
+            },
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn restart_window_resets_backoff() {
+    let mut sim = Sim::new(());
+    sim.node(
+        "alice",
+        HandlerFn::new(|_| current().shutdow_and_restart_in(Duration::from_secs(1))),
+    );
+    sim.get(&"alice".into()).unwrap().set_stereotyp(Stereotyp {
+        restart_policy: RestartPolicy::UNLIMITED
+            .with_limit(1, Duration::from_secs(10))
+            .with_backoff(Duration::from_secs(5), 2, Duration::from_secs(60)),
+        ..Stereotyp::HOST
+    });
+    let gate = sim.gate("alice", "port");
+
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    for t in [1.0, 20.0] {
+        rt.add_message_onto(gate.clone(), Message::default(), t.into());
+    }
+    let (app, _, _) = rt.run().unwrap();
+
+    let restarts = app
+        .get(&"alice".into())
+        .unwrap()
+        .restart_history()
+        .into_iter()
+        .map(|record| record.outcome)
+        .collect::<Vec<_>>();
+    let at = |secs: u64| SimTime::ZERO + Duration::from_secs(secs);
+    assert_eq!(
+        restarts,
+        [
+            RestartOutcome::Scheduled(at(6)),
+            RestartOutcome::Scheduled(at(25)),
+        ]
+    );
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "net")]
+use std::sync::{Arc, Mutex};
+
+use des::{
+    net::{globals, processing::ProcessingStack},
+    prelude::*,
+};
+use serial_test::serial;
+use time::at_millis;
+
+#[path = "common/time.rs"]
+mod time;
+
+type Log = Arc<Mutex<Vec<(String, SimTime)>>>;
+
+fn log(log: &Log, entry: impl Into<String>) {
+    log.lock().unwrap().push((entry.into(), SimTime::now()));
+}
+
+struct Worker {
+    log: Log,
+}
+
+impl Module for Worker {
+    fn at_sim_start(&mut self, stage: usize) {
+        log(&self.log, format!("start {stage}"));
+        schedule_in(Message::default().id(99), Duration::from_millis(50));
+    }
+
+    fn num_sim_start_stages(&self) -> usize {
+        2
+    }
+
+    fn handle_message(&mut self, msg: Message) {
+        log(&self.log, format!("recv {}", msg.header().id));
+    }
+
+    fn at_sim_end(&mut self) -> Result<(), RuntimeError> {
+        log(&self.log, "end");
+        Ok(())
+    }
+}
+
+struct Master {
+    log: Log,
+    terminate_at: Option<Duration>,
+}
+
+impl Module for Master {
+    fn at_sim_start(&mut self, _stage: usize) {
+        let ctx = current();
+        let spawner = ctx.spawner();
+        let worker = spawner.child(
+            "worker",
+            Worker {
+                log: self.log.clone(),
+            },
+            ProcessingStack::default(),
+        );
+        spawner.gate("out", 1);
+
+        let out = ctx.gate("out", 0).unwrap();
+        out.connect(
+            worker.create_gate("in"),
+            Some(Channel::new(ChannelMetrics::new(
+                51_200,
+                Duration::from_millis(10),
+                Duration::ZERO,
+                ChannelDropBehaviour::Drop,
+            ))),
+        );
+
+        // The worker is not yet started, but already visible
+        assert!(self.log.lock().unwrap().is_empty());
+        assert!(globals().get(&"master.worker".into()).is_some());
+        assert_eq!(Topology::current().nodes().len(), 2);
+
+        send(Message::default().id(1), "out");
+        if let Some(terminate_at) = self.terminate_at {
+            schedule_in(Message::default(), terminate_at);
+        }
+    }
+
+    fn handle_message(&mut self, _msg: Message) {
+        send(Message::default().id(2), "out");
+        current().spawner().terminate("worker");
+        log(&self.log, "terminate");
+
+        assert!(current().child("worker").is_err());
+    }
+}
+
+fn run(terminate_at: Option<Duration>) -> (Vec<(String, SimTime)>, Arc<Globals>) {
+    let log = Log::default();
+    let mut sim = Sim::new(());
+    sim.node(
+        "master",
+        Master {
+            log: log.clone(),
+            terminate_at,
+        },
+    );
+
+    let rt = Builder::seeded(123).quiet().build(sim.freeze());
+    let globals = rt.app.globals();
+    let _ = rt.run().unwrap();
+
+    let log = log.lock().unwrap().clone();
+    (log, globals)
+}
+
+#[test]
+#[serial]
+fn spawned_modules_are_started_and_live() {
+    let (log, globals) = run(None);
+    assert_eq!(
+        log,
+        vec![
+            ("start 0".to_string(), at_millis(0)),
+            ("start 1".to_string(), at_millis(0)),
+            ("recv 1".to_string(), at_millis(20)),
+            ("recv 99".to_string(), at_millis(50)),
+            ("recv 99".to_string(), at_millis(50)),
+            ("end".to_string(), at_millis(50)),
+        ]
+    );
+
+    let worker = globals.get(&"master.worker".into()).unwrap();
+    assert!(worker.is_active());
+    assert_eq!(globals.topology().edges().count(), 2);
+}
+
+#[test]
+#[serial]
+fn terminated_modules_are_torn_down() {
+    let (log, globals) = run(Some(Duration::from_millis(30)));
+    assert_eq!(
+        log,
+        vec![
+            ("start 0".to_string(), at_millis(0)),
+            ("start 1".to_string(), at_millis(0)),
+            ("recv 1".to_string(), at_millis(20)),
+            ("terminate".to_string(), at_millis(30)),
+            ("end".to_string(), at_millis(30)),
+        ]
+    );
+
+    assert!(globals.get(&"master.worker".into()).is_none());
+    assert_eq!(globals.topology().nodes().len(), 1);
+
+    let master = globals.get(&"master".into()).unwrap();
+    assert!(master.gate("out", 0).unwrap().next_gate().is_none());
+}
+
+struct StagedMaster {
+    log: Log,
+}
+
+impl Module for StagedMaster {
+    fn at_sim_start(&mut self, stage: usize) {
+        log(&self.log, format!("master {stage}"));
+        if stage == 0 {
+            current().spawner().child(
+                "worker",
+                Worker {
+                    log: self.log.clone(),
+                },
+                ProcessingStack::default(),
+            );
+        }
+    }
+
+    fn num_sim_start_stages(&self) -> usize {
+        2
+    }
+}
+
+#[test]
+#[serial]
+fn modules_spawned_during_multi_stage_start_are_started_once() {
+    let log = Log::default();
+    let mut sim = Sim::new(());
+    sim.node("master", StagedMaster { log: log.clone() });
+    let _ = Builder::seeded(123)
+        .quiet()
+        .build(sim.freeze())
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            ("master 0".to_string(), at_millis(0)),
+            ("start 0".to_string(), at_millis(0)),
+            ("start 1".to_string(), at_millis(0)),
+            ("master 1".to_string(), at_millis(0)),
+            ("recv 99".to_string(), at_millis(50)),
+            ("recv 99".to_string(), at_millis(50)),
+            ("end".to_string(), at_millis(50)),
+        ]
+    );
+}
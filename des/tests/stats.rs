@@ -0,0 +1,190 @@
+#![cfg(feature = "net")]
+
+use des::{net::blocks::HandlerFn, prelude::*, runtime::RuntimeLimit, stats};
+
+struct Pinger {
+    sent: usize,
+    delays: Option<stats::HistogramRef>,
+}
+
+impl Module for Pinger {
+    fn at_sim_start(&mut self, _stage: usize) {
+        self.delays = Some(stats::histogram("delay", 0.0..1.0, 10));
+        send(Message::default(), "out");
+    }
+
+    fn handle_message(&mut self, msg: Message) {
+        let delay = SimTime::now() - msg.header().creation_time;
+        if let Some(delays) = self.delays {
+            delays.record(delay.as_secs_f64());
+        }
+        stats::vector("inflight").record(0.0);
+
+        self.sent += 1;
+        if self.sent < 5 {
+            send(Message::default(), "out");
+            stats::vector("inflight").record(1.0);
+        }
+    }
+
+    fn at_sim_end(&mut self) -> Result<(), RuntimeError> {
+        stats::record_scalar("sent", self.sent as f64);
+        Ok(())
+    }
+}
+
+fn sim() -> Sim<()> {
+    let mut sim = Sim::new(());
+    sim.node(
+        "pinger",
+        Pinger {
+            sent: 0,
+            delays: None,
+        },
+    );
+    let mut received = 0;
+    sim.node(
+        "ponger",
+        HandlerFn::new(move |msg| {
+            received += 1;
+            stats::record_scalar("received", f64::from(received));
+            send(msg, "out");
+        }),
+    );
+
+    let ping = sim.gate("pinger", "out");
+    let pong = sim.gate("ponger", "out");
+    let metrics = ChannelMetrics::new(
+        usize::MAX,
+        Duration::from_millis(150),
+        Duration::ZERO,
+        ChannelDropBehaviour::Drop,
+    );
+    ping.connect(sim.gate("ponger", "in"), Some(Channel::new(metrics)));
+    pong.connect(sim.gate("pinger", "in"), Some(Channel::new(metrics)));
+    sim.freeze()
+}
+
+#[test]
+fn records_are_namespaced_by_module() {
+    let (sim, _, profiler) = Builder::seeded(123).quiet().build(sim()).run().unwrap();
+    let stats = sim.statistics();
+    assert_eq!(profiler.statistics, stats);
+
+    assert_eq!(stats.scalar("pinger", "sent"), Some(5.0));
+    assert_eq!(stats.scalar("ponger", "received"), Some(5.0));
+    assert_eq!(stats.scalar("ponger", "sent"), None);
+
+    let inflight = stats.vector("pinger", "inflight").unwrap();
+    assert_eq!(inflight.len(), 9);
+    assert_eq!(
+        inflight.samples()[0],
+        (SimTime::from_duration(Duration::from_millis(300)), 0.0)
+    );
+    assert_eq!(inflight.summary().max(), Some(1.0));
+
+    let delays = stats.histogram("pinger", "delay").unwrap();
+    assert_eq!(delays.summary().count(), 5);
+    assert_eq!(delays.overflow(), 0);
+    assert_eq!(delays.bins().map(|(_, n)| n).sum::<u64>(), 5);
+}
+
+#[test]
+fn records_outside_of_modules() {
+    let mut sim = Sim::new(());
+    sim.node("a", HandlerFn::new(|_| {}));
+    stats::record_scalar("nodes", 1.0);
+
+    let stats = sim.statistics();
+    assert_eq!(stats.scalar("", "nodes"), Some(1.0));
+}
+
+#[test]
+fn export_csv_and_json() {
+    let (sim, _, _) = Builder::seeded(123).quiet().build(sim()).run().unwrap();
+    let stats = sim.statistics();
+
+    let csv = stats.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("kind,module,name,key,value"));
+    assert_eq!(lines.next(), Some("scalar,ponger,received,,5"));
+    assert!(csv.contains("\nscalar,pinger,sent,,5\n"));
+    assert!(csv.contains("\nvector,pinger,inflight,0.3,0\n"));
+    assert!(csv.contains("\nhistogram,pinger,delay,-inf,0\n"));
+    assert!(csv.contains("\nhistogram,pinger,delay,1,0\n"));
+    // 2 scalars + 9 samples + 12 bins
+    assert_eq!(csv.lines().count(), 1 + 2 + 9 + 12);
+
+    let json = stats.to_json();
+    let parsed = serde_json::from_str::<stats::Statistics>(&json).unwrap();
+    assert_eq!(parsed, stats);
+}
+
+struct Sampler {
+    recorded: usize,
+}
+
+impl Module for Sampler {
+    fn at_sim_start(&mut self, _stage: usize) {
+        stats::record_scalar("started", 1.0);
+        schedule_in(Message::default(), Duration::from_secs(1));
+    }
+
+    fn handle_message(&mut self, _msg: Message) {
+        self.recorded += 1;
+        stats::vector("value").record(random::<f64>());
+        schedule_in(Message::default(), Duration::from_secs(1));
+    }
+}
+
+fn sampler() -> Sim<()> {
+    let mut sim = Sim::new(());
+    sim.node("sampler", Sampler { recorded: 0 });
+    sim.freeze()
+}
+
+#[test]
+fn warmup_discards_transient_statistics() {
+    let (sim, _, _) = Builder::seeded(123)
+        .quiet()
+        .warmup(5.5.into())
+        .max_time(10.0.into())
+        .build(sampler())
+        .run()
+        .unwrap();
+    let stats = sim.statistics();
+
+    assert_eq!(stats.scalar("sampler", "started"), None);
+    let value = stats.vector("sampler", "value").unwrap();
+    assert_eq!(
+        value
+            .samples()
+            .iter()
+            .map(|(t, _)| t.as_secs())
+            .collect::<Vec<_>>(),
+        [6, 7, 8, 9, 10]
+    );
+}
+
+#[test]
+fn precision_limit_stops_run() {
+    let (sim, time, _) = Builder::seeded(123)
+        .quiet()
+        .limit(RuntimeLimit::Precision(stats::PrecisionTarget::new(
+            "sampler", "value", 0.02,
+        )))
+        .max_time(1_000_000.0.into())
+        .build(sampler())
+        .run()
+        .unwrap();
+    assert!(time < SimTime::from(1_000_000.0));
+
+    let estimate = sim
+        .statistics()
+        .vector("sampler", "value")
+        .unwrap()
+        .batch_means()
+        .unwrap();
+    assert!(estimate.relative_half_width() < 0.02);
+    assert!(estimate.interval().contains(&0.5));
+}
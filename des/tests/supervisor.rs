@@ -0,0 +1,194 @@
+#![cfg(feature = "net")]
+use std::sync::{Arc, Mutex};
+
+use des::{
+    net::{
+        module::{RestartPolicy, Stereotyp, Supervision},
+        PanicError, SimBuilder,
+    },
+    prelude::*,
+};
+use serial_test::serial;
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Worker {
+    starts: Log,
+}
+
+impl Module for Worker {
+    fn reset(&mut self) {}
+
+    fn at_sim_start(&mut self, _: usize) {
+        self.starts
+            .lock()
+            .unwrap()
+            .push(current().path().to_string());
+    }
+
+    fn handle_message(&mut self, _: Message) {
+        panic!("worker failed");
+    }
+}
+
+struct Supervisor {
+    strategy: Supervision,
+    failures: Log,
+}
+
+impl Module for Supervisor {
+    fn reset(&mut self) {}
+
+    fn handle_child_failure(&mut self, child: ModuleRef, err: &PanicError) -> Supervision {
+        self.failures
+            .lock()
+            .unwrap()
+            .push(format!("{} <- {}", child.path(), err.path));
+        self.strategy
+    }
+}
+
+/// A supervisor `sup` with the workers `sup.a`, `sup.b` and `sup.c`.
+fn supervised_sim(strategy: Supervision, policy: RestartPolicy) -> (SimBuilder<()>, Log, Log) {
+    let starts = Log::default();
+    let failures = Log::default();
+
+    let mut sim = Sim::new(());
+    sim.node(
+        "sup",
+        Supervisor {
+            strategy,
+            failures: failures.clone(),
+        },
+    );
+    for worker in ["sup.a", "sup.b", "sup.c"] {
+        sim.node(
+            worker,
+            Worker {
+                starts: starts.clone(),
+            },
+        );
+        sim.get(&worker.into()).unwrap().set_stereotyp(Stereotyp {
+            restart_policy: policy,
+            ..Stereotyp::SUBPROCESS
+        });
+    }
+    (sim, starts, failures)
+}
+
+fn fail_at(mut sim: SimBuilder<()>, module: &str, times: &[f64]) -> Runtime<Sim<()>> {
+    let gate = sim.gate(module, "port");
+    let mut rt = Builder::seeded(123).quiet().build(sim.freeze());
+    for &t in times {
+        rt.add_message_onto(gate.clone(), Message::default(), t.into());
+    }
+    rt
+}
+
+fn count(starts: &Log, module: &str) -> usize {
+    starts
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|s| *s == module)
+        .count()
+}
+
+#[test]
+#[serial]
+fn restart_strategies() {
+    for (strategy, expected) in [
+        (Supervision::Ignore, [1, 1, 1]),
+        (Supervision::OneForOne, [1, 2, 1]),
+        (Supervision::RestForOne, [1, 2, 2]),
+        (Supervision::OneForAll, [2, 2, 2]),
+    ] {
+        let (sim, starts, failures) = supervised_sim(strategy, RestartPolicy::UNLIMITED);
+        let _ = fail_at(sim, "sup.b", &[1.0]).run().unwrap();
+
+        assert_eq!(*failures.lock().unwrap(), ["sup.b <- sup.b"]);
+        assert_eq!(
+            ["sup.a", "sup.b", "sup.c"].map(|m| count(&starts, m)),
+            expected,
+            "{strategy:?}"
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn restarted_child_can_fail_again() {
+    let (sim, starts, failures) = supervised_sim(Supervision::OneForOne, RestartPolicy::UNLIMITED);
+    let _ = fail_at(sim, "sup.b", &[1.0, 2.0, 3.0]).run().unwrap();
+
+    assert_eq!(failures.lock().unwrap().len(), 3);
+    assert_eq!(count(&starts, "sup.b"), 4);
+}
+
+#[test]
+#[serial]
+fn supervisor_giving_up_fails_root() {
+    let policy = RestartPolicy::UNLIMITED.with_limit(1, Duration::from_secs(60));
+    let (sim, starts, failures) = supervised_sim(Supervision::OneForOne, policy);
+    let errs = fail_at(sim, "sup.b", &[1.0, 2.0, 3.0]).run().unwrap_err();
+
+    // the third message is dropped, since sup.b stays shut down
+    assert_eq!(failures.lock().unwrap().len(), 2);
+    assert_eq!(count(&starts, "sup.b"), 2);
+    assert_eq!(
+        errs[0].as_any().downcast_ref::<PanicError>().unwrap().path,
+        ObjectPath::from("sup.b")
+    );
+}
+
+#[test]
+#[serial]
+fn escalation_propagates_up_the_tree() {
+    let starts = Log::default();
+    let failures = Log::default();
+    let top_failures = Log::default();
+
+    let mut sim = Sim::new(());
+    sim.node(
+        "top",
+        Supervisor {
+            strategy: Supervision::Ignore,
+            failures: top_failures.clone(),
+        },
+    );
+    sim.node(
+        "top.sup",
+        Supervisor {
+            strategy: Supervision::Escalate,
+            failures: failures.clone(),
+        },
+    );
+    sim.get(&"top.sup".into())
+        .unwrap()
+        .set_stereotyp(Stereotyp {
+            on_panic_catch: true,
+            on_panic_inform_parent: true,
+            ..Stereotyp::HOST
+        });
+    for worker in ["top.sup.a", "top.sup.b"] {
+        sim.node(
+            worker,
+            Worker {
+                starts: starts.clone(),
+            },
+        );
+        sim.get(&worker.into())
+            .unwrap()
+            .set_stereotyp(Stereotyp::SUBPROCESS);
+    }
+
+    let (app, _, _) = fail_at(sim, "top.sup.b", &[1.0, 2.0]).run().unwrap();
+
+    assert_eq!(*failures.lock().unwrap(), ["top.sup.b <- top.sup.b"]);
+    assert_eq!(*top_failures.lock().unwrap(), ["top.sup <- top.sup.b"]);
+    for module in ["top.sup", "top.sup.a", "top.sup.b"] {
+        assert!(!app.get(&module.into()).unwrap().is_active(), "{module}");
+    }
+    assert!(app.get(&"top".into()).unwrap().is_active());
+    assert_eq!(count(&starts, "top.sup.a"), 1);
+}
@@ -1,6 +1,9 @@
 use std::{fs, io::Write};
 
-use des::prelude::*;
+use des::{
+    net::topology::{Edge, ImportError},
+    prelude::*,
+};
 use serial_test::serial;
 
 #[test]
@@ -76,3 +79,356 @@ fn spanned_topology() {
     assert_eq!(topology.edges().count(), 2 * 4);
     assert!(!topology.nodes().iter().any(|n| n.module().name() == "bob"));
 }
+
+fn exported_sim(delay_model: DelayModel) -> des::net::SimBuilder<()> {
+    let mut sim = Sim::new(());
+    sim.node("alice", Fallback);
+    sim.node("bob", Fallback);
+    sim.node("carol", Fallback);
+
+    let metrics = ChannelMetrics::new(
+        1_000_000,
+        Duration::from_millis(10),
+        Duration::ZERO,
+        ChannelDropBehaviour::Queue(Some(64)),
+    );
+    let channel = Channel::new(metrics);
+    channel.set_delay_model(delay_model);
+    let ports = sim.gates("alice", "port", 2);
+    ports[0]
+        .clone()
+        .connect(sim.gate("bob", "port"), Some(channel));
+    ports[1].clone().connect(sim.gate("carol", "port"), None);
+    sim
+}
+
+fn assert_exported_topology(topo: &Topology<(), ()>) {
+    assert_eq!(topo.nodes().len(), 3);
+    assert_eq!(topo.edges().count(), 4);
+    assert!(topo.bidirectional());
+    assert!(topo.connected());
+
+    let edge = topo.edges_for("bob").next().unwrap();
+    assert_eq!(edge.from.gate().str(), "port");
+    assert_eq!(edge.to.gate().str(), "port[0]");
+    let channel = edge.from.gate().channel().unwrap();
+    assert_eq!(channel.delay_model(), DelayModel::Pareto(1.5));
+    let metrics = channel.metrics();
+    assert_eq!(metrics.bitrate, 1_000_000);
+    assert_eq!(metrics.latency, Duration::from_millis(10));
+    assert_eq!(
+        metrics.drop_behaviour,
+        ChannelDropBehaviour::Queue(Some(64))
+    );
+
+    let edge = topo.edges_for("carol").next().unwrap();
+    assert_eq!(edge.to.gate().str(), "port[1]");
+    assert!(edge.from.gate().channel().is_none());
+}
+
+fn sim_topology(sim: &mut des::net::SimBuilder<()>, nodes: &[&str]) -> Topology<(), ()> {
+    let modules = nodes
+        .iter()
+        .map(|&node| sim.get(&node.into()).unwrap())
+        .collect::<Vec<_>>();
+    Topology::from_modules(&modules)
+}
+
+#[test]
+#[serial]
+fn graphml_roundtrip() {
+    let graphml = sim_topology(
+        &mut exported_sim(DelayModel::Pareto(1.5)),
+        &["alice", "bob", "carol"],
+    )
+    .as_graphml();
+    assert!(graphml.contains("<port name=\"port[0]\"/>"));
+    assert!(graphml.contains("attr.name=\"bitrate\" attr.type=\"long\""));
+
+    let mut imported = Sim::new(());
+    imported
+        .nodes_from_graphml(&graphml, Registry::new().with_default_fallback())
+        .unwrap();
+    assert_exported_topology(&sim_topology(&mut imported, &["alice", "bob", "carol"]));
+}
+
+#[test]
+#[serial]
+fn json_roundtrip() {
+    let json = sim_topology(
+        &mut exported_sim(DelayModel::Pareto(1.5)),
+        &["alice", "bob", "carol"],
+    )
+    .with_edge_cost_attachment()
+    .as_json();
+    assert!(json.contains("\"alive\": true"));
+
+    let mut imported = Sim::new(());
+    imported
+        .nodes_from_json(&json, Registry::new().with_default_fallback())
+        .unwrap();
+    assert_exported_topology(&sim_topology(&mut imported, &["alice", "bob", "carol"]));
+}
+
+fn bob_channel(sim: &mut des::net::SimBuilder<()>) -> ChannelRef {
+    let topo = sim_topology(sim, &["alice", "bob", "carol"]);
+    let edge = topo.edges_for("bob").next().unwrap();
+    edge.from.gate().channel().unwrap()
+}
+
+#[test]
+#[serial]
+fn custom_delay_model_is_not_exported() {
+    let custom = DelayModel::custom(
+        rand::distr::Uniform::new(Duration::from_millis(5), Duration::from_millis(10)).unwrap(),
+    );
+    let (graphml, json) = {
+        let topo = sim_topology(&mut exported_sim(custom), &["alice", "bob", "carol"]);
+        (topo.as_graphml(), topo.as_json())
+    };
+    assert!(!graphml.contains("custom"));
+    assert!(!json.contains("custom"));
+
+    let mut imported = Sim::new(());
+    imported
+        .nodes_from_graphml(&graphml, Registry::new().with_default_fallback())
+        .unwrap();
+    assert_eq!(
+        bob_channel(&mut imported).delay_model(),
+        DelayModel::Uniform
+    );
+    drop(imported);
+
+    let mut imported = Sim::new(());
+    imported
+        .nodes_from_json(&json, Registry::new().with_default_fallback())
+        .unwrap();
+    assert_eq!(
+        bob_channel(&mut imported).delay_model(),
+        DelayModel::Uniform
+    );
+}
+
+#[test]
+#[serial]
+fn graphml_topology_zoo_import() {
+    let graphml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+          <key attr.name="label" attr.type="string" for="node" id="d0" />
+          <key attr.name="LinkSpeedRaw" attr.type="double" for="edge" id="d1" />
+          <graph edgedefault="undirected">
+            <node id="0"><data key="d0">Berlin</data></node>
+            <node id="1"><data key="d0">Hamburg</data></node>
+            <node id="2"><data key="d0">M&#252;nchen</data></node>
+            <edge source="0" target="1"><data key="d1">10000000.0</data></edge>
+            <edge source="0" target="2"><data key="d1">1000000.0</data></edge>
+            <edge source="1" target="2" />
+          </graph>
+        </graphml>"#;
+
+    let mut sim = Sim::new(());
+    sim.nodes_from_graphml(graphml, Registry::new().with_default_fallback())
+        .unwrap();
+
+    let topo = sim_topology(&mut sim, &["0", "1", "2"]);
+    assert_eq!(topo.edges().count(), 6);
+    assert!(topo.bidirectional());
+
+    let berlin = sim.get(&"0".into()).unwrap();
+    assert_eq!(berlin.gates().len(), 2);
+    let bitrates = berlin
+        .gates()
+        .iter()
+        .map(|gate| gate.channel().map(|ch| ch.metrics().bitrate))
+        .collect::<Vec<_>>();
+    assert_eq!(bitrates, [Some(10_000_000), Some(1_000_000)]);
+
+    let hamburg = sim.get(&"1".into()).unwrap();
+    assert!(hamburg.gate("port", 1).unwrap().channel().is_none());
+
+    let _ = Builder::seeded(123).quiet().build(sim.freeze()).run();
+}
+
+#[test]
+#[serial]
+fn graphml_doctype_import() {
+    let graphml = r#"<?xml version="1.0"?>
+        <!DOCTYPE graphml [
+          <!ELEMENT graphml ANY>
+          <!ENTITY speed "1000">
+        ]>
+        <graphml>
+          <key attr.name="type" attr.type="string" for="node" id="t" />
+          <key attr.name="bitrate" attr.type="int" for="edge" id="b" />
+          <graph>
+            <!-- <node id="ignored"/> -->
+            <node id="a"><data key="t"><![CDATA[Router]]></data></node>
+            <node id="b" />
+            <edge source="a" target="b"><data key="b">&speed;</data></edge>
+          </graph>
+        </graphml>"#;
+
+    let mut routers = Vec::new();
+    let mut sim = Sim::new(());
+    sim.nodes_from_graphml(
+        graphml,
+        Registry::new()
+            .symbol_fn("Router", |path| {
+                routers.push(path.to_string());
+                Fallback
+            })
+            .with_default_fallback(),
+    )
+    .unwrap();
+    assert_eq!(routers, ["a"]);
+    assert!(sim.get(&"ignored".into()).is_none());
+
+    let gate = sim.get(&"a".into()).unwrap().gate("port", 0).unwrap();
+    assert_eq!(gate.channel().unwrap().metrics().bitrate, 1000);
+}
+
+#[test]
+#[serial]
+fn json_node_link_import() {
+    let json = r#"{
+        "directed": false,
+        "multigraph": false,
+        "nodes": [{ "id": 0, "type": "Router" }, { "id": 1 }],
+        "links": [{ "source": 0, "target": 1, "LinkSpeedRaw": 1000.0 }]
+    }"#;
+
+    let mut routers = Vec::new();
+    let mut sim = Sim::new(());
+    sim.nodes_from_json(
+        json,
+        Registry::new()
+            .symbol_fn("Router", |path| {
+                routers.push(path.to_string());
+                Fallback
+            })
+            .with_default_fallback(),
+    )
+    .unwrap();
+    assert_eq!(routers, ["0"]);
+
+    let gate = sim.get(&"0".into()).unwrap().gate("port", 0).unwrap();
+    assert_eq!(gate.next_gate().unwrap().path().as_str(), "1.port");
+    assert_eq!(gate.channel().unwrap().metrics().bitrate, 1000);
+}
+
+#[test]
+#[serial]
+fn import_errors() {
+    let import =
+        |json: &str| Sim::new(()).nodes_from_json(json, Registry::new().with_default_fallback());
+
+    assert!(matches!(import("{"), Err(ImportError::Parse(_))));
+    assert!(matches!(
+        import(r#"{ "nodes": [{ "id": "a.b" }] }"#),
+        Err(ImportError::Missing(_))
+    ));
+    assert!(matches!(
+        import(r#"{ "nodes": [{ "id": "a" }], "edges": [{ "source": "a", "target": "b" }] }"#),
+        Err(ImportError::Missing(_))
+    ));
+    assert!(matches!(
+        import(
+            r#"{ "nodes": [{ "id": "a" }, { "id": "b" }], "edges": [
+                { "source": "a", "target": "b", "source_gate": "out", "target_gate": "in" },
+                { "source": "a", "target": "b", "source_gate": "out", "target_gate": "in" }
+            ] }"#
+        ),
+        Err(ImportError::Gate(_))
+    ));
+    assert!(matches!(
+        Sim::new(()).nodes_from_graphml(r#"<graphml><graph><node id="a"/>"#, Registry::new()),
+        Err(ImportError::Parse(_))
+    ));
+    assert!(matches!(
+        Sim::new(()).nodes_from_graphml(
+            r#"<graphml><graph><node id="a"/></graph></graphml>"#,
+            Registry::new()
+        ),
+        Err(ImportError::Registry(_))
+    ));
+}
+
+#[test]
+#[serial]
+fn failed_imports_leave_the_simulation_unchanged() {
+    let mut sim = Sim::new(());
+    let result = sim.nodes_from_json(
+        r#"{ "nodes": [{ "id": "a", "type": "Router" }, { "id": "b" }] }"#,
+        Registry::new().symbol_fn("Router", |_| Fallback),
+    );
+    assert!(matches!(result, Err(ImportError::Registry(_))));
+    assert!(sim.get(&"a".into()).is_none());
+
+    let result = sim.nodes_from_json(
+        r#"{ "nodes": [{ "id": "a" }, { "id": "b" }], "edges": [
+            { "source": "a", "target": "b" },
+            { "source": "a", "target": "b", "channel": { "bitrate": 1000, "latency": -1.0 } }
+        ] }"#,
+        Registry::new().with_default_fallback(),
+    );
+    assert!(matches!(result, Err(ImportError::Parse(_))));
+    assert!(sim.get(&"a".into()).is_none());
+}
+
+#[test]
+#[serial]
+fn weighted_graph_algorithms() {
+    // a square a-b-c-d-a, where the direct links of a are slow
+    let mut sim = Sim::new(());
+    for node in ["a", "b", "c", "d"] {
+        sim.node(node, Fallback);
+    }
+    for (from, to, bitrate, latency) in [
+        ("a", "b", 1_000, 50),
+        ("b", "c", 10_000, 1),
+        ("c", "d", 10_000, 1),
+        ("d", "a", 2_000, 1),
+    ] {
+        let channel = Channel::new(ChannelMetrics::new(
+            bitrate,
+            Duration::from_millis(latency),
+            Duration::ZERO,
+            ChannelDropBehaviour::Drop,
+        ));
+        sim.gate(from, to)
+            .connect(sim.gate(to, from), Some(channel));
+    }
+    let topo = sim_topology(&mut sim, &["a", "b", "c", "d"]);
+    let latency = |edge: Edge<'_, (), ()>| edge.latency().as_secs_f64();
+
+    let path = topo.shortest_path("a", "b", latency).unwrap();
+    let nodes = path.nodes();
+    assert_eq!(nodes, ["a", "d", "c", "b"].map(ObjectPath::from));
+    assert!((path.cost - 0.003).abs() < 1e-9);
+
+    let paths = topo.k_shortest_paths("a", "b", 3, latency);
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[1].len(), 1);
+
+    let all = topo.all_pairs_shortest_paths(latency);
+    assert_eq!(all.len(), 4);
+    assert!(all.values().all(|paths| paths.len() == 4));
+
+    let tree = topo.minimum_spanning_tree(latency);
+    assert_eq!(tree.len(), 3);
+    assert!(!tree
+        .iter()
+        .any(|edge| edge.latency() == Duration::from_millis(50)));
+
+    #[allow(clippy::cast_precision_loss)]
+    let flow = topo.max_flow("a", "c", |edge| {
+        edge.bitrate().map_or(f64::INFINITY, |b| b as f64)
+    });
+    assert_eq!(flow.value, 3_000.0);
+    assert_eq!(flow.cut.len(), 2);
+
+    let betweenness = topo.betweenness_centrality(latency);
+    assert!(betweenness[&"d".into()] > betweenness[&"b".into()]);
+    let closeness = topo.closeness_centrality(latency);
+    assert!(closeness[&"d".into()] > closeness[&"b".into()]);
+}
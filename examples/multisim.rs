@@ -4,53 +4,99 @@ use std::thread;
 
 use des::prelude::*;
 
+struct Counter {
+    count: usize,
+}
+
+impl Module for Counter {
+    fn at_sim_start(&mut self, _stage: usize) {
+        schedule_in(Message::default(), Duration::from_secs(1));
+    }
+
+    fn handle_message(&mut self, _msg: Message) {
+        self.count += 1;
+        if self.count < 100 {
+            let delay = Duration::from_secs_f64(random::<f64>());
+            schedule_in(Message::default(), delay);
+        }
+    }
+}
+
 fn main() {
     let n = 5;
-    let mut handles = Vec::with_capacity(n);
 
+    // Runtimes on different threads run in parallel, so all
+    // simulations must be active at the same time.
+    let results = run_on_threads(n, false);
+    assert_eq!(results.peak, n);
+
+    // Simulations with the same seed produce the same results,
+    // independent of the other simulations in the process.
+    assert!(results.times.iter().all(|t| *t == results.times[0]));
+
+    // Exclusive runtimes wait for each other.
+    let results = run_on_threads(n, true);
+    assert_eq!(results.peak, 1);
+}
+
+struct Results {
+    peak: usize,
+    times: Vec<SimTime>,
+}
+
+fn run_on_threads(n: usize, exclusive: bool) -> Results {
     let barrier = Arc::new(Barrier::new(n));
     let active = Arc::new(AtomicUsize::new(0));
-    let counter = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
 
-    for _ in 0..n {
-        let barrier_c = barrier.clone();
-        let active_c = active.clone();
-        let counter_c = counter.clone();
+    let handles = (0..n)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let active = active.clone();
+            let peak = peak.clone();
+            thread::spawn(move || create_runtime_and_wait(exclusive, barrier, active, peak))
+        })
+        .collect::<Vec<_>>();
 
-        handles.push(thread::spawn(move || {
-            create_runtime_and_wait(barrier_c, active_c, counter_c)
-        }))
-    }
-
-    for handle in handles {
-        handle.join().unwrap();
-    }
+    let times = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
 
     assert_eq!(active.load(SeqCst), 0);
-    assert_eq!(counter.load(SeqCst), n)
+    Results {
+        peak: peak.load(SeqCst),
+        times,
+    }
 }
 
 fn create_runtime_and_wait(
+    exclusive: bool,
     barrier: Arc<Barrier>,
     active: Arc<AtomicUsize>,
-    counter: Arc<AtomicUsize>,
-) {
+    peak: Arc<AtomicUsize>,
+) -> SimTime {
     barrier.wait();
 
     // Create runtime
-    let app = Sim::new(());
+    let mut sim = Sim::new(());
+    sim.node("counter", Counter { count: 0 });
+
+    let mut builder = Builder::seeded(42).quiet();
+    if exclusive {
+        builder = builder.exclusive();
+    }
+    let rt = builder.build(sim.freeze());
 
-    let rt = Builder::new().build(app.freeze());
-    let prev = active.fetch_add(1, SeqCst);
-    assert_eq!(prev, 0);
-    counter.fetch_add(1, SeqCst);
+    let now = active.fetch_add(1, SeqCst) + 1;
+    peak.fetch_max(now, SeqCst);
 
     // Do work
-    thread::sleep(Duration::from_millis(500));
+    thread::sleep(std::time::Duration::from_millis(500));
 
     // Deregister
-    let prev = active.fetch_sub(1, SeqCst);
-    assert_eq!(prev, 1);
+    active.fetch_sub(1, SeqCst);
 
-    drop(rt);
+    let (_, time, _) = rt.run().unwrap();
+    time
 }
@@ -89,7 +89,7 @@ impl ServerDone {
                         _pad: [0; 300],
                     }),
                     customer.duration,
-                )
+                );
             }
             None => {
                 rt.app.busy = false;